@@ -0,0 +1,416 @@
+//! Prepares `assets/` for the device build.
+//!
+//! - Checks the images and sounds against what `dino-core` expects: every
+//!   bundled name exists, tables (`<name>-table-<width>-<height>.png`) split
+//!   into whole frames, and images are twice the size the game draws them.
+//! - Writes the images drawn at half size to `assets/scaled/`, so the device
+//!   doesn't scale them at runtime.
+//! - Generates `asset_ids.rs` with a typed ID for every asset, included by
+//!   `src/assets.rs`. A renamed or missing asset fails the build.
+
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Write as _,
+    fs,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use dino_core::{
+    coin::COIN_SIZE,
+    layout,
+    math::Size,
+    obstacle::ObstacleKind,
+    power_up::PowerUpKind,
+    skin::{Accessory, SkinTable, CLASSIC_TABLE, GHOST_TABLE},
+    theme::{IMAGES, SOUNDS},
+};
+
+const ASSETS: &str = "assets";
+const SCALED: &str = "scaled";
+/// Folders that aren't game assets.
+const SKIPPED: &[&str] = &["launcher", SCALED];
+/// Images drawn at their original size.
+const FULL_SIZE: &[&str] = &["ground", "cloud"];
+const SCALE: f32 = 0.5;
+
+struct Image {
+    /// Path from `assets/` without the extension or table suffix.
+    name: String,
+    file: PathBuf,
+    /// The frame size of a table.
+    cell: Option<(u32, u32)>,
+    width: u32,
+    height: u32,
+}
+
+impl Image {
+    fn scale(&self) -> f32 {
+        if FULL_SIZE.contains(&self.name.as_str()) {
+            1.0
+        } else {
+            SCALE
+        }
+    }
+
+    fn frames(&self) -> u32 {
+        let (w, h) = self.cell.unwrap_or((self.width, self.height));
+        (self.width / w) * (self.height / h)
+    }
+
+    /// The size of a frame on screen.
+    fn screen_size(&self) -> (u32, u32) {
+        let (w, h) = self.cell.unwrap_or((self.width, self.height));
+        (scaled(w, self.scale()), scaled(h, self.scale()))
+    }
+}
+
+fn scaled(value: u32, scale: f32) -> u32 {
+    (value as f32 * scale) as u32
+}
+
+/// Splits `dino-table-160-94` into `dino` and its frame size.
+fn parse_table_name(stem: &str) -> Option<(&str, (u32, u32))> {
+    let (rest, height) = stem.rsplit_once('-')?;
+    let (rest, width) = rest.rsplit_once('-')?;
+    let name = rest.strip_suffix("-table")?;
+    Some((name, (width.parse().ok()?, height.parse().ok()?)))
+}
+
+fn collect(dir: &Path, prefix: &str, images: &mut Vec<Image>, sounds: &mut Vec<String>) {
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    entries.sort();
+    for path in entries {
+        let file_name = path.file_name().unwrap().to_str().unwrap().to_owned();
+        if path.is_dir() {
+            if prefix.is_empty() && SKIPPED.contains(&file_name.as_str()) {
+                continue;
+            }
+            collect(&path, &format!("{}{}/", prefix, file_name), images, sounds);
+            continue;
+        }
+        let stem = path.file_stem().unwrap().to_str().unwrap();
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => {
+                let (name, cell) = match parse_table_name(stem) {
+                    Some((name, cell)) => (name, Some(cell)),
+                    None => (stem, None),
+                };
+                let info = png::Decoder::new(fs::File::open(&path).unwrap())
+                    .read_info()
+                    .unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+                    .info()
+                    .clone();
+                images.push(Image {
+                    name: format!("{}{}", prefix, name),
+                    file: path.clone(),
+                    cell,
+                    width: info.width,
+                    height: info.height,
+                });
+            }
+            Some("wav") => sounds.push(format!("{}{}", prefix, stem)),
+            _ => {}
+        }
+    }
+}
+
+fn check(images: &[Image], sounds: &[String]) -> Vec<String> {
+    let mut errors = vec![];
+    let find = |name: &str| images.iter().find(|i| i.name == name);
+    for image in images {
+        if let Some((w, h)) = image.cell {
+            if w == 0 || h == 0 || image.width % w != 0 || image.height % h != 0 {
+                errors.push(format!(
+                    "{}: {}x{} doesn't split into {}x{} frames",
+                    image.file.display(),
+                    image.width,
+                    image.height,
+                    w,
+                    h
+                ));
+            }
+        }
+    }
+    for name in IMAGES {
+        if find(name).is_none() {
+            errors.push(format!("missing image {}/{}", ASSETS, name));
+        }
+    }
+    for name in SOUNDS {
+        if !sounds.iter().any(|s| s == name) {
+            errors.push(format!("missing sound {}/{}.wav", ASSETS, name));
+        }
+    }
+    // the frame layout of the skins
+    for table in [&CLASSIC_TABLE, &GHOST_TABLE] {
+        let SkinTable {
+            path,
+            count,
+            width,
+            height,
+            ..
+        } = *table;
+        match find(path) {
+            Some(image) if image.cell != Some((width, height)) => errors.push(format!(
+                "{}: frames are {:?}, the skin expects {}x{}",
+                image.file.display(),
+                image.cell,
+                width,
+                height
+            )),
+            Some(image) if image.frames() as usize != count => errors.push(format!(
+                "{}: {} frames, the skin expects {}",
+                image.file.display(),
+                image.frames(),
+                count
+            )),
+            _ => {}
+        }
+        if table.anchors.len() != count {
+            errors.push(format!(
+                "skin table {} has {} anchors",
+                path,
+                table.anchors.len()
+            ));
+        }
+    }
+    // on-screen sizes
+    let mut sizes: Vec<(String, Size)> = vec![
+        ("game-over".into(), layout::GAME_OVER.size()),
+        ("restart".into(), layout::RESTART_ICON.size()),
+        ("heart".into(), layout::LIFE_ICON_SIZE),
+        ("coin".into(), COIN_SIZE),
+    ];
+    for kind in PowerUpKind::ALL {
+        sizes.push((kind.get_path().into(), PowerUpKind::SIZE));
+    }
+    for accessory in Accessory::ALL {
+        sizes.push((accessory.get_path().into(), accessory.get_size()));
+    }
+    for kind in ObstacleKind::ALL {
+        let name = obstacle_image(kind);
+        // both birds use the same table
+        if !sizes.iter().any(|(n, _)| *n == name) {
+            sizes.push((name, kind.default_shape().size));
+        }
+    }
+    for (name, size) in sizes {
+        let Some(image) = find(&name) else {
+            continue;
+        };
+        let expected = (size.width as u32, size.height as u32);
+        if image.screen_size() != expected {
+            errors.push(format!(
+                "{}: drawn at {:?}, the game expects {:?}",
+                image.file.display(),
+                image.screen_size(),
+                expected
+            ));
+        }
+    }
+    errors
+}
+
+fn obstacle_image(kind: ObstacleKind) -> String {
+    if kind.is_bird() {
+        "bird".into()
+    } else {
+        format!("cactus/{}", kind.get_key())
+    }
+}
+
+/// Reads a PNG as 8-bit RGBA.
+fn read_rgba(path: &Path) -> (u32, u32, Vec<u8>) {
+    let mut decoder = png::Decoder::new(fs::File::open(path).unwrap());
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().unwrap();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+    let pixels = (info.width * info.height) as usize;
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buf[..pixels * 4].to_vec(),
+        png::ColorType::Rgb => buf[..pixels * 3]
+            .chunks(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buf[..pixels * 2]
+            .chunks(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => buf[..pixels]
+            .iter()
+            .flat_map(|g| [*g, *g, *g, 255])
+            .collect(),
+        png::ColorType::Indexed => unreachable!("expanded by the decoder"),
+    };
+    (info.width, info.height, rgba)
+}
+
+/// Scales every frame of `image` with nearest-neighbour sampling, like
+/// `drawScaledBitmap` does, and returns the PNG file.
+fn scale_image(image: &Image) -> Vec<u8> {
+    let (width, height, rgba) = read_rgba(&image.file);
+    let scale = image.scale();
+    let (cell_w, cell_h) = image.cell.unwrap_or((width, height));
+    let (out_cell_w, out_cell_h) = (scaled(cell_w, scale), scaled(cell_h, scale));
+    let (cols, rows) = (width / cell_w, height / cell_h);
+    let (out_w, out_h) = (cols * out_cell_w, rows * out_cell_h);
+    let mut out = vec![0; (out_w * out_h * 4) as usize];
+    for y in 0..out_h {
+        for x in 0..out_w {
+            let src_x = (x / out_cell_w) * cell_w + ((x % out_cell_w) as f32 / scale) as u32;
+            let src_y = (y / out_cell_h) * cell_h + ((y % out_cell_h) as f32 / scale) as u32;
+            let src = ((src_y * width + src_x) * 4) as usize;
+            let dst = ((y * out_w + x) * 4) as usize;
+            out[dst..dst + 4].copy_from_slice(&rgba[src..src + 4]);
+        }
+    }
+    let mut file = vec![];
+    {
+        let mut encoder = png::Encoder::new(BufWriter::new(&mut file), out_w, out_h);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&out).unwrap();
+    }
+    file
+}
+
+/// Where the scaled copy of `image` goes, relative to `assets/`.
+fn scaled_file(image: &Image) -> PathBuf {
+    let file_name = image.file.file_name().unwrap();
+    let stem = Path::new(&image.name).parent().unwrap();
+    let mut path = Path::new(SCALED).join(stem);
+    match image.cell {
+        Some((w, h)) => {
+            let name = Path::new(&image.name)
+                .file_name()
+                .unwrap()
+                .to_str()
+                .unwrap();
+            path.push(format!(
+                "{}-table-{}-{}.png",
+                name,
+                scaled(w, SCALE),
+                scaled(h, SCALE)
+            ));
+        }
+        None => path.push(file_name),
+    }
+    path
+}
+
+/// Writes `data` unless the file already holds it, so that the build script
+/// doesn't rerun because of its own output.
+fn write_if_changed(path: &Path, data: &[u8]) {
+    if fs::read(path).ok().as_deref() == Some(data) {
+        return;
+    }
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, data).unwrap();
+}
+
+/// `power-up/double-jump` is `DOUBLE_JUMP` in the `power_up` module.
+fn ident(name: &str) -> (Vec<String>, String) {
+    let mut parts: Vec<String> = name.split('/').map(|p| p.replace('-', "_")).collect();
+    let last = parts.pop().unwrap().to_uppercase();
+    (parts, last)
+}
+
+/// Emits `consts` as nested modules following the folders of the names.
+fn write_module(out: &mut String, module: &str, ty: &str, consts: &[(String, String)]) {
+    let mut tree: BTreeMap<Vec<String>, Vec<(String, &str)>> = BTreeMap::new();
+    for (name, value) in consts {
+        let (modules, ident) = ident(name);
+        tree.entry(modules).or_default().push((ident, value));
+    }
+    writeln!(out, "pub mod {} {{", module).unwrap();
+    writeln!(out, "use crate::assets::{};", ty).unwrap();
+    for (modules, consts) in &tree {
+        for module in modules {
+            writeln!(out, "pub mod {} {{", module).unwrap();
+            writeln!(out, "use crate::assets::{};", ty).unwrap();
+        }
+        for (ident, value) in consts {
+            writeln!(out, "pub const {}: {} = {} {};", ident, ty, ty, value).unwrap();
+        }
+        for _ in modules {
+            writeln!(out, "}}").unwrap();
+        }
+    }
+    writeln!(out, "}}").unwrap();
+}
+
+fn main() {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join(ASSETS);
+    println!("cargo:rerun-if-changed={}", ASSETS);
+    let (mut images, mut sounds) = (vec![], vec![]);
+    collect(&assets, "", &mut images, &mut sounds);
+
+    let errors = check(&images, &sounds);
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("cargo:warning={}", error);
+        }
+        panic!("invalid assets:\n{}", errors.join("\n"));
+    }
+
+    let mut image_ids = vec![];
+    let mut table_ids = vec![];
+    for image in &images {
+        let path = if image.scale() == 1.0 {
+            PathBuf::from(&image.name)
+        } else {
+            write_if_changed(&assets.join(scaled_file(image)), &scale_image(image));
+            Path::new(SCALED).join(&image.name)
+        };
+        let path = path.to_str().unwrap().replace('\\', "/");
+        match image.cell {
+            Some(_) => {
+                let (width, height) = image.screen_size();
+                table_ids.push((
+                    image.name.clone(),
+                    format!(
+                        "{{ name: {:?}, path: {:?}, count: {}, width: {}, height: {}, scale: {:?} }}",
+                        image.name,
+                        path,
+                        image.frames(),
+                        width,
+                        height,
+                        image.scale()
+                    ),
+                ));
+            }
+            None => {
+                let (width, height) = image.screen_size();
+                image_ids.push((
+                    image.name.clone(),
+                    format!(
+                        "{{ name: {:?}, path: {:?}, width: {}, height: {}, scale: {:?} }}",
+                        image.name,
+                        path,
+                        width,
+                        height,
+                        image.scale()
+                    ),
+                ));
+            }
+        }
+    }
+    let sound_ids: Vec<_> = sounds
+        .iter()
+        .map(|s| (s.clone(), format!("{{ name: {:?} }}", s)))
+        .collect();
+
+    let mut out = String::from("// Generated by build.rs from assets/, do not edit.\n");
+    write_module(&mut out, "image", "ImageId", &image_ids);
+    write_module(&mut out, "table", "TableId", &table_ids);
+    write_module(&mut out, "sound", "SoundId", &sound_ids);
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("asset_ids.rs"), out).unwrap();
+}
@@ -0,0 +1,16 @@
+pub const GRAVITY: f32 = 1000.0;
+pub const JUMP_VELOCITY: f32 = -500.0;
+pub const MOVE_ACCELERATION: f32 = 6.0;
+pub const INITIAL_MOVE_VELOCITY: f32 = 250.0;
+pub const MAX_MOVE_VELOCITY: f32 = 400.0;
+pub const DAY_NIGHT_CYCLE_SECS: usize = 30;
+pub const HURT_SECS: f32 = 0.5;
+pub const INVULNERABLE_SECS: f32 = 2.0;
+pub const POWER_UP_CHANCE: usize = 5;
+pub const SHIELD_SECS: f32 = 10.0;
+pub const DOUBLE_JUMP_SECS: f32 = 10.0;
+pub const SLOW_TIME_SECS: f32 = 5.0;
+pub const SLOW_TIME_SCALE: f32 = 0.6;
+pub const COIN_ARC_CHANCE: usize = 2;
+pub const EASY_SPEED_SCALE: f32 = 0.8;
+pub const HARD_SPEED_SCALE: f32 = 1.2;
@@ -0,0 +1,115 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    args,
+    math::{Rect, Size},
+    obstacle::Obstacle,
+    rng::Rng,
+    Event, GameState,
+};
+
+pub const COIN_SIZE: Size = Size::new(10.0, 10.0);
+
+/// Horizontal offsets of the coins in an arc, from the cactus' center.
+const ARC_OFFSETS: [f32; 5] = [-90.0, -45.0, 0.0, 45.0, 90.0];
+const ARC_HALF_WIDTH: f32 = 130.0;
+const ARC_HEIGHT: f32 = 95.0;
+const ARC_BASE_Y: f32 = 150.0;
+
+pub struct Coin {
+    id: u32,
+    bounds: Rect,
+}
+
+impl Coin {
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_bounds(&self) -> Rect {
+        self.bounds
+    }
+}
+
+/// Coins floating over the cacti, and how many were picked up this run.
+pub struct Coins {
+    coins: Vec<Coin>,
+    next_id: u32,
+    collected: u32,
+}
+
+impl Coins {
+    pub fn new() -> Self {
+        Self {
+            coins: vec![],
+            next_id: 0,
+            collected: 0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.coins.clear();
+        self.next_id = 0;
+        self.collected = 0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Coin> {
+        self.coins.iter()
+    }
+
+    /// Coins picked up in the current run.
+    pub fn get_collected(&self) -> u32 {
+        self.collected
+    }
+
+    /// Sometimes places an arc of coins over a freshly spawned cactus. The
+    /// arc follows a jump, so the coins at its top are only reachable by
+    /// jumping late and close to the cactus.
+    pub fn spawn_over(&mut self, obstacle: &Obstacle, rng: &mut Rng) {
+        if obstacle.get_kind().is_bird() || rng.gen_index(args::COIN_ARC_CHANCE) != 0 {
+            return;
+        }
+        let bounds = obstacle.get_bounds();
+        let center = bounds.x + bounds.width / 2.0;
+        for dx in ARC_OFFSETS {
+            let t = dx / ARC_HALF_WIDTH;
+            let y = ARC_BASE_Y - ARC_HEIGHT * (1.0 - t * t);
+            self.coins.push(Coin {
+                id: self.next_id,
+                bounds: Rect::new(
+                    center + dx - COIN_SIZE.width / 2.0,
+                    y,
+                    COIN_SIZE.width,
+                    COIN_SIZE.height,
+                ),
+            });
+            self.next_id += 1;
+        }
+    }
+
+    pub fn update(&mut self, game_state: GameState, velocity: f32, delta: f32) {
+        if game_state != GameState::Playing {
+            return;
+        }
+        for coin in &mut self.coins {
+            coin.bounds.x -= velocity * delta;
+        }
+        self.coins.retain(|c| c.bounds.right() >= 0.0);
+    }
+
+    /// Picks up every coin overlapping the dino's sprite.
+    pub fn collect(&mut self, dino: &Rect, events: &mut Vec<Event>) {
+        let before = self.coins.len();
+        self.coins.retain(|c| !c.bounds.intersects(dino));
+        for _ in self.coins.len()..before {
+            self.collected += 1;
+            events.push(Event::CoinCollected);
+        }
+    }
+}
+
+impl Default for Coins {
+    fn default() -> Self {
+        Self::new()
+    }
+}
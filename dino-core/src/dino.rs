@@ -0,0 +1,250 @@
+use alloc::vec::Vec;
+
+use crate::{
+    args,
+    display::DISPLAY_HEIGHT,
+    ground::Ground,
+    hitbox,
+    input::{Buttons, Input},
+    math::{Rect, Size},
+    GameState,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum DinoState {
+    Idle,
+    Run,
+    Jump,
+    Duck,
+    Dead,
+    /// Lost a life but keeps running.
+    Hurt,
+}
+
+pub const IMAGE_SIZE: Size = Size::new(160.0, 94.0);
+pub const SPRITE_SIZE: Size = Size::new(IMAGE_SIZE.width / 2.0, IMAGE_SIZE.height / 2.0);
+pub const INITLAL_BOUNDS: Rect = Rect {
+    x: 20.0,
+    y: DISPLAY_HEIGHT as f32 - Ground::COLLIDE_HEIGHT - SPRITE_SIZE.height,
+    width: SPRITE_SIZE.width,
+    height: SPRITE_SIZE.height,
+};
+
+impl DinoState {
+    fn transition(
+        &self,
+        game_state: GameState,
+        input: &Input,
+        bounds: &Rect,
+        hurt_time: f32,
+    ) -> Option<Self> {
+        // Idle -> Jump
+        if self == &Self::Idle {
+            if game_state != GameState::Ready {
+                return Some(Self::Run);
+            }
+            return None;
+        }
+        // Run -> {Jump, Duck, Dead}
+        if self == &Self::Run {
+            if game_state == GameState::Dead {
+                return Some(Self::Dead);
+            }
+            if input.pushed.contains(Buttons::A) {
+                return Some(Self::Jump);
+            }
+            if input.current.contains(Buttons::B) {
+                return Some(Self::Duck);
+            }
+            return None;
+        }
+        // Duck -> {Run, Dead}
+        if self == &Self::Duck {
+            if game_state == GameState::Dead {
+                return Some(Self::Dead);
+            }
+            if !input.current.contains(Buttons::B) {
+                return Some(Self::Run);
+            }
+            return None;
+        }
+        // Jump -> {Run, Dead}
+        if self == &Self::Jump {
+            if game_state == GameState::Dead {
+                return Some(Self::Dead);
+            }
+            if bounds.y >= INITLAL_BOUNDS.y {
+                return Some(Self::Run);
+            }
+            return None;
+        }
+        // Hurt -> {Run, Dead}
+        if self == &Self::Hurt {
+            if game_state == GameState::Dead {
+                return Some(Self::Dead);
+            }
+            if hurt_time <= 0.0 && bounds.y >= INITLAL_BOUNDS.y {
+                return Some(Self::Run);
+            }
+            return None;
+        }
+        // Dead -> Run
+        if self == &Self::Dead {
+            if game_state == GameState::Playing {
+                return Some(Self::Run);
+            }
+            return None;
+        }
+        unreachable!()
+    }
+}
+
+/// The collision boxes of the dino, in screen pixels relative to its
+/// sprite.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DinoShape {
+    pub standing: Vec<Rect>,
+    pub ducking: Vec<Rect>,
+}
+
+impl Default for DinoShape {
+    fn default() -> Self {
+        Self {
+            standing: hitbox::DINO_STANDING.to_vec(),
+            ducking: hitbox::DINO_DUCKING.to_vec(),
+        }
+    }
+}
+
+pub struct Dino {
+    state: DinoState,
+    bounds: Rect,
+    vertical_velocity: f32,
+    hurt_time: f32,
+    double_jumped: bool,
+    shape: DinoShape,
+}
+
+impl Dino {
+    pub fn new() -> Self {
+        Self {
+            state: DinoState::Idle,
+            bounds: INITLAL_BOUNDS,
+            vertical_velocity: 0.0,
+            hurt_time: 0.0,
+            double_jumped: false,
+            shape: DinoShape::default(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.state = DinoState::Idle;
+        self.bounds = INITLAL_BOUNDS;
+        self.vertical_velocity = 0.0;
+        self.hurt_time = 0.0;
+        self.double_jumped = false;
+    }
+
+    pub fn get_state(&self) -> DinoState {
+        self.state
+    }
+
+    pub fn get_bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub fn get_vertical_velocity(&self) -> f32 {
+        self.vertical_velocity
+    }
+
+    pub fn hitboxes(&self) -> &[Rect] {
+        match self.state {
+            DinoState::Duck => &self.shape.ducking,
+            _ => &self.shape.standing,
+        }
+    }
+
+    pub fn get_shape(&self) -> &DinoShape {
+        &self.shape
+    }
+
+    /// Replaces the collision boxes, e.g. with the ones of a theme pack.
+    pub fn set_shape(&mut self, shape: DinoShape) {
+        self.shape = shape;
+    }
+
+    /// The collision boxes if they differ from the bundled ones, for
+    /// replays.
+    pub fn custom_shape(&self) -> Option<DinoShape> {
+        Some(self.shape.clone()).filter(|s| *s != DinoShape::default())
+    }
+
+    /// Runs the state machine. Returns the previous state.
+    pub fn update_state(&mut self, game_state: GameState, input: &Input) -> DinoState {
+        let old_state = self.state;
+        if let Some(state) = self
+            .state
+            .transition(game_state, input, &self.bounds, self.hurt_time)
+        {
+            self.state = state;
+        }
+        old_state
+    }
+
+    /// Integrates velocity and gravity, and returns the bounds the dino wants
+    /// to move to. Nothing is moved until [`Dino::move_to`] is called.
+    pub fn step(&mut self, old_state: DinoState, delta: f32) -> Rect {
+        match (old_state, self.state) {
+            (DinoState::Idle, DinoState::Run) => self.vertical_velocity = args::JUMP_VELOCITY,
+            (DinoState::Run, DinoState::Jump) => self.vertical_velocity = args::JUMP_VELOCITY,
+            (DinoState::Dead, DinoState::Run) => {
+                self.bounds = INITLAL_BOUNDS;
+                self.vertical_velocity = 0.0;
+            }
+            _ => {}
+        }
+        self.hurt_time = (self.hurt_time - delta).max(0.0);
+        if self.state != DinoState::Jump {
+            self.double_jumped = false;
+        }
+        // add gravity
+        self.vertical_velocity += args::GRAVITY * delta;
+        // update position
+        let mut goal = self.bounds;
+        goal.y += self.vertical_velocity * delta;
+        if goal.y > INITLAL_BOUNDS.y {
+            goal.y = INITLAL_BOUNDS.y;
+        }
+        goal
+    }
+
+    /// Whether a second jump is possible, given the double jump power-up.
+    pub fn can_double_jump(&self) -> bool {
+        self.state == DinoState::Jump && !self.double_jumped
+    }
+
+    pub fn double_jump(&mut self) {
+        self.vertical_velocity = args::JUMP_VELOCITY;
+        self.double_jumped = true;
+    }
+
+    /// Plays the hurt animation for a moment. The dino keeps falling if it
+    /// was in the air.
+    pub fn hurt(&mut self) {
+        self.state = DinoState::Hurt;
+        self.hurt_time = args::HURT_SECS;
+    }
+
+    pub fn move_to(&mut self, goal: Rect) {
+        if goal.y == self.bounds.y {
+            self.vertical_velocity = 0.0;
+        }
+        self.bounds = goal;
+    }
+}
+
+impl Default for Dino {
+    fn default() -> Self {
+        Self::new()
+    }
+}
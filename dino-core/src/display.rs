@@ -0,0 +1,2 @@
+pub const DISPLAY_WIDTH: u32 = 400;
+pub const DISPLAY_HEIGHT: u32 = 240;
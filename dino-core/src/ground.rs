@@ -0,0 +1,68 @@
+use crate::args;
+
+pub struct Ground {
+    offset: f32,
+    velocity: f32,
+    /// Scales the speed and acceleration, see [`crate::settings::Difficulty`].
+    scale: f32,
+}
+
+impl Ground {
+    pub const WIDTH: f32 = 2400.0;
+    pub const HEIGHT: f32 = 64.0;
+    pub const COLLIDE_HEIGHT: f32 = 64.0 - 18.0;
+
+    pub fn new() -> Self {
+        Self {
+            offset: 0.0,
+            velocity: args::INITIAL_MOVE_VELOCITY,
+            scale: 1.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.offset = 0.0;
+        self.velocity = args::INITIAL_MOVE_VELOCITY * self.scale;
+    }
+
+    /// Takes effect from the next [`Ground::reset`].
+    pub fn set_speed_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    /// The x position of the first of the two ground strips. The second one
+    /// follows right after it.
+    pub fn get_offset(&self) -> f32 {
+        self.offset
+    }
+
+    pub fn get_velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    /// Overrides the speed. It keeps accelerating from there, up to the
+    /// usual maximum.
+    #[cfg(feature = "cheats")]
+    pub fn set_velocity(&mut self, velocity: f32) {
+        self.velocity = velocity;
+    }
+
+    pub fn update(&mut self, delta: f32) {
+        // move strips
+        self.offset -= self.velocity * delta;
+        if self.offset <= -Self::WIDTH {
+            self.offset += Self::WIDTH;
+        }
+        // update velocity
+        self.velocity += args::MOVE_ACCELERATION * self.scale * delta;
+        if self.velocity > args::MAX_MOVE_VELOCITY * self.scale {
+            self.velocity = args::MAX_MOVE_VELOCITY * self.scale;
+        }
+    }
+}
+
+impl Default for Ground {
+    fn default() -> Self {
+        Self::new()
+    }
+}
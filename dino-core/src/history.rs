@@ -0,0 +1,225 @@
+//! The history of finished runs, for balancing.
+//!
+//! Every run is a line of JSON in [`HISTORY_FILE`], so frontends only ever
+//! append to it and it can be read line by line on a workstation:
+//!
+//! ```text
+//! {"timestamp":1792368000,"profile":"P1","mode":"normal-3","seed":"5eed5eed5eed5eed","score":1234,"duration_ms":61234,"top_speed":400,"death":{"obstacle":"cactus-big-1","distance":21876.5,"dino_y":147},"jumps":42,"tuning":{"gravity":1000,...}}
+//! ```
+//!
+//! `tuning` holds the [`args`] the build was made with and the difficulty's
+//! speed scale, so runs of different builds can be told apart.
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::{args, obstacle::ObstacleKind, scores::Mode, World};
+
+/// In the frontend's folder for the player.
+pub const HISTORY_FILE: &str = "history.jsonl";
+/// Seconds from the Unix epoch to the Playdate's, 2000-01-01.
+pub const PLAYDATE_EPOCH: u64 = 946_684_800;
+
+/// Where a run ended.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Death {
+    pub obstacle: ObstacleKind,
+    /// Pixels into the run.
+    pub distance: f32,
+    /// The top of the dino, which tells a jump that came too late from one
+    /// that came too early.
+    pub dino_y: f32,
+}
+
+/// One line of the history.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RunRecord {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The profile that played the run, so histories pulled off several
+    /// devices can be merged.
+    pub profile: String,
+    pub mode: Mode,
+    pub seed: u64,
+    pub score: i32,
+    pub duration_ms: u32,
+    /// In pixels per second.
+    pub top_speed: f32,
+    pub death: Death,
+    pub jumps: u32,
+    pub speed_scale: f32,
+}
+
+impl RunRecord {
+    /// The run `profile` just ended on `obstacle`. `date` is in seconds
+    /// since 2000-01-01, like the high scores'.
+    pub fn of_run(world: &World, profile: &str, obstacle: ObstacleKind, date: u32) -> Self {
+        Self {
+            timestamp: u64::from(date) + PLAYDATE_EPOCH,
+            profile: String::from(profile),
+            mode: Mode::of_run(world),
+            seed: world.get_seed(),
+            score: world.scoreboard.get_score(),
+            duration_ms: world.get_elapsed_ms(),
+            top_speed: world.get_top_speed(),
+            death: Death {
+                obstacle,
+                distance: world.get_distance(),
+                dino_y: world.dino.get_bounds().y,
+            },
+            jumps: world.get_jumps(),
+            speed_scale: world.get_difficulty().get_speed_scale(),
+        }
+    }
+}
+
+/// The record as a line of JSON, without the line break.
+impl fmt::Display for RunRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{\"timestamp\":{},\"profile\":\"{}\",\"mode\":\"{}\",\"seed\":\"{:016x}\",\"score\":{},\"duration_ms\":{},\"top_speed\":{}",
+            self.timestamp,
+            JsonStr(&self.profile),
+            JsonStr(&self.mode.get_key()),
+            self.seed,
+            self.score,
+            self.duration_ms,
+            self.top_speed
+        )?;
+        write!(
+            f,
+            ",\"death\":{{\"obstacle\":\"{}\",\"distance\":{},\"dino_y\":{}}},\"jumps\":{}",
+            JsonStr(self.death.obstacle.get_key()),
+            self.death.distance,
+            self.death.dino_y,
+            self.jumps
+        )?;
+        write!(f, ",\"tuning\":{{")?;
+        let tuning: [(&str, f32); 12] = [
+            ("gravity", args::GRAVITY),
+            ("jump_velocity", args::JUMP_VELOCITY),
+            ("move_acceleration", args::MOVE_ACCELERATION),
+            ("initial_move_velocity", args::INITIAL_MOVE_VELOCITY),
+            ("max_move_velocity", args::MAX_MOVE_VELOCITY),
+            ("speed_scale", self.speed_scale),
+            ("hurt_secs", args::HURT_SECS),
+            ("invulnerable_secs", args::INVULNERABLE_SECS),
+            ("shield_secs", args::SHIELD_SECS),
+            ("double_jump_secs", args::DOUBLE_JUMP_SECS),
+            ("slow_time_secs", args::SLOW_TIME_SECS),
+            ("slow_time_scale", args::SLOW_TIME_SCALE),
+        ];
+        for (i, (key, value)) in tuning.iter().enumerate() {
+            let comma = if i == 0 { "" } else { "," };
+            write!(f, "{}\"{}\":{}", comma, key, value)?;
+        }
+        write!(
+            f,
+            ",\"power_up_chance\":{},\"coin_arc_chance\":{}}}}}",
+            args::POWER_UP_CHANCE,
+            args::COIN_ARC_CHANCE
+        )
+    }
+}
+
+/// The inside of a JSON string, with quotes, backslashes and control
+/// characters escaped.
+struct JsonStr<'a>(&'a str);
+
+impl fmt::Display for JsonStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                c if c < ' ' => write!(f, "\\u{:04x}", c as u32)?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString};
+
+    use super::*;
+    use crate::{input::Input, settings::Difficulty, Event};
+
+    fn example(profile: &str) -> RunRecord {
+        RunRecord {
+            timestamp: 1_792_368_000,
+            profile: String::from(profile),
+            mode: Mode {
+                difficulty: Difficulty::Hard,
+                lives: 1,
+            },
+            seed: 0x5eed,
+            score: 1234,
+            duration_ms: 61_234,
+            top_speed: 400.5,
+            death: Death {
+                obstacle: ObstacleKind::CactusBig1,
+                distance: 21_876.5,
+                dino_y: 147.0,
+            },
+            jumps: 42,
+            speed_scale: 1.25,
+        }
+    }
+
+    #[test]
+    fn line_format() {
+        let line = example("P1").to_string();
+        assert!(line.starts_with(
+            "{\"timestamp\":1792368000,\"profile\":\"P1\",\"mode\":\"hard-1\",\
+             \"seed\":\"0000000000005eed\",\"score\":1234,\"duration_ms\":61234,\
+             \"top_speed\":400.5,\"death\":{\"obstacle\":\"cactus-big-1\",\
+             \"distance\":21876.5,\"dino_y\":147},\"jumps\":42,\"tuning\":{\"gravity\":"
+        ));
+        assert!(line.contains(",\"speed_scale\":1.25,"));
+        assert!(line.ends_with(&format!(
+            ",\"power_up_chance\":{},\"coin_arc_chance\":{}}}}}",
+            args::POWER_UP_CHANCE,
+            args::COIN_ARC_CHANCE
+        )));
+        assert!(!line.contains('\n'));
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        let line = example("A\"B\\C\n\u{1}").to_string();
+        assert!(line.contains(r#""profile":"A\"B\\C\n\u0001","#));
+        assert!(!line.contains('\n'));
+        assert_eq!(JsonStr("normal-3").to_string(), "normal-3");
+        assert_eq!(JsonStr(r#"a"-\3"#).to_string(), r#"a\"-\\3"#);
+        assert_eq!(JsonStr("Ünïcode").to_string(), "Ünïcode");
+    }
+
+    #[test]
+    fn record_of_a_run() {
+        let mut world = World::new();
+        world.start(7);
+        let died = loop {
+            world.update(Input::default(), 33);
+            let died = world.events().iter().find_map(|e| match e {
+                Event::Died(kind) => Some(*kind),
+                _ => None,
+            });
+            if let Some(kind) = died {
+                break kind;
+            }
+        };
+        // 2026-10-19
+        let record = RunRecord::of_run(&world, "ANN", died, 845_683_200);
+        assert_eq!(record.timestamp, 1_792_368_000);
+        assert_eq!(record.profile, "ANN");
+        assert_eq!(record.seed, 7);
+        assert_eq!(record.death.obstacle, died);
+        assert_eq!(record.duration_ms, world.get_elapsed_ms());
+        assert_eq!(record.score, world.scoreboard.get_score());
+    }
+}
@@ -0,0 +1,126 @@
+//! Collision boxes, in screen pixels relative to the top-left corner of the
+//! sprite they belong to.
+//!
+//! Hits are decided by these boxes rather than by comparing the sprites'
+//! pixels, so every platform agrees on them. The boxes are derived from the
+//! sprites' alpha masks by `hitbox-tool --rust`, and follow the silhouettes
+//! to within `dino_host::hitbox::DEFAULT_TOLERANCE` pixels. A test in
+//! `dino-host` fails when they no longer match the images.
+
+use crate::math::Rect;
+
+pub const DINO_STANDING: &[Rect] = &[
+    Rect::new(38.0, 0.0, 24.0, 15.0),
+    Rect::new(18.0, 15.0, 40.0, 4.0),
+    Rect::new(18.0, 19.0, 36.0, 8.0),
+    Rect::new(18.0, 27.0, 32.0, 4.0),
+    Rect::new(22.0, 31.0, 26.0, 4.0),
+    Rect::new(26.0, 35.0, 23.0, 12.0),
+];
+
+pub const DINO_DUCKING: &[Rect] = &[
+    Rect::new(18.0, 17.0, 6.0, 1.0),
+    Rect::new(18.0, 18.0, 59.0, 11.0),
+    Rect::new(22.0, 29.0, 55.0, 4.0),
+    Rect::new(26.0, 33.0, 49.0, 4.0),
+    Rect::new(29.0, 37.0, 26.0, 5.0),
+    Rect::new(29.0, 42.0, 14.0, 5.0),
+];
+
+pub const BIRD: &[Rect] = &[
+    Rect::new(8.0, 0.0, 12.0, 4.0),
+    Rect::new(4.0, 4.0, 20.0, 4.0),
+    Rect::new(0.0, 8.0, 32.0, 4.0),
+    Rect::new(0.0, 12.0, 46.0, 8.0),
+    Rect::new(14.0, 20.0, 32.0, 6.0),
+    Rect::new(16.0, 26.0, 26.0, 2.0),
+    Rect::new(16.0, 28.0, 22.0, 2.0),
+    Rect::new(16.0, 30.0, 6.0, 4.0),
+];
+
+pub const CACTUS_SMALL_1: &[Rect] = &[
+    Rect::new(5.0, 0.0, 7.0, 4.0),
+    Rect::new(2.0, 4.0, 15.0, 5.0),
+    Rect::new(0.0, 9.0, 17.0, 11.0),
+    Rect::new(0.0, 20.0, 12.0, 4.0),
+    Rect::new(5.0, 24.0, 7.0, 11.0),
+];
+
+pub const CACTUS_SMALL_2: &[Rect] = &[
+    Rect::new(5.0, 0.0, 24.0, 3.0),
+    Rect::new(1.0, 3.0, 31.0, 2.0),
+    Rect::new(0.0, 5.0, 34.0, 17.0),
+    Rect::new(2.0, 22.0, 27.0, 12.0),
+    Rect::new(6.0, 34.0, 22.0, 1.0),
+];
+
+pub const CACTUS_SMALL_3: &[Rect] = &[
+    Rect::new(5.0, 0.0, 41.0, 4.0),
+    Rect::new(2.0, 4.0, 49.0, 5.0),
+    Rect::new(0.0, 9.0, 51.0, 11.0),
+    Rect::new(0.0, 20.0, 46.0, 4.0),
+    Rect::new(5.0, 24.0, 41.0, 11.0),
+];
+
+pub const CACTUS_BIG_1: &[Rect] = &[
+    Rect::new(8.0, 0.0, 9.0, 10.0),
+    Rect::new(8.0, 10.0, 16.0, 2.0),
+    Rect::new(0.0, 12.0, 25.0, 18.0),
+    Rect::new(2.0, 30.0, 19.0, 2.0),
+    Rect::new(4.0, 32.0, 13.0, 1.0),
+    Rect::new(8.0, 33.0, 12.0, 12.0),
+    Rect::new(4.0, 45.0, 17.0, 2.0),
+    Rect::new(5.0, 47.0, 12.0, 1.0),
+    Rect::new(15.0, 48.0, 3.0, 2.0),
+];
+
+pub const CACTUS_BIG_2: &[Rect] = &[
+    Rect::new(8.0, 0.0, 34.0, 10.0),
+    Rect::new(8.0, 10.0, 41.0, 2.0),
+    Rect::new(0.0, 12.0, 50.0, 18.0),
+    Rect::new(2.0, 30.0, 44.0, 2.0),
+    Rect::new(4.0, 32.0, 38.0, 1.0),
+    Rect::new(8.0, 33.0, 37.0, 12.0),
+    Rect::new(4.0, 45.0, 42.0, 2.0),
+    Rect::new(5.0, 47.0, 37.0, 1.0),
+    Rect::new(15.0, 48.0, 28.0, 2.0),
+];
+
+pub const CACTUS_BIG_3: &[Rect] = &[
+    Rect::new(8.0, 0.0, 59.0, 11.0),
+    Rect::new(8.0, 11.0, 65.0, 1.0),
+    Rect::new(0.0, 12.0, 75.0, 19.0),
+    Rect::new(3.0, 31.0, 67.0, 3.0),
+    Rect::new(8.0, 34.0, 62.0, 11.0),
+    Rect::new(5.0, 45.0, 66.0, 2.0),
+    Rect::new(4.0, 47.0, 64.0, 2.0),
+    Rect::new(16.0, 49.0, 51.0, 1.0),
+];
+
+/// Returns true if any box of `a` placed at `a_at` overlaps any box of `b`
+/// placed at `b_at`.
+pub fn collides(a: &[Rect], a_at: &Rect, b: &[Rect], b_at: &Rect) -> bool {
+    if !a_at.intersects(b_at) {
+        return false;
+    }
+    a.iter().any(|a| {
+        let a = a.offset(a_at.x, a_at.y);
+        b.iter().any(|b| a.intersects(&b.offset(b_at.x, b_at.y)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn near_miss_between_the_boxes_does_not_collide() {
+        let dino = Rect::new(0.0, 0.0, 80.0, 47.0);
+        // the cactus's top reaches under the dino's tail, inside its sprite
+        let cactus = Rect::new(51.0, 40.0, 17.0, 35.0);
+        assert!(dino.intersects(&cactus));
+        assert!(!collides(DINO_STANDING, &dino, CACTUS_SMALL_1, &cactus));
+        let cactus = Rect::new(46.0, 40.0, 17.0, 35.0);
+        assert!(collides(DINO_STANDING, &dino, CACTUS_SMALL_1, &cactus));
+    }
+}
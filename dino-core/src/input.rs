@@ -0,0 +1,52 @@
+use core::ops::BitOr;
+
+/// Button bits, laid out exactly like the Playdate's `PDButtons`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Buttons(u8);
+
+impl Buttons {
+    pub const NONE: Self = Self(0);
+    pub const LEFT: Self = Self(1 << 0);
+    pub const RIGHT: Self = Self(1 << 1);
+    pub const UP: Self = Self(1 << 2);
+    pub const DOWN: Self = Self(1 << 3);
+    pub const B: Self = Self(1 << 4);
+    pub const A: Self = Self(1 << 5);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits & 0x3f)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0 && other.0 != 0
+    }
+}
+
+impl BitOr for Buttons {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// The button state sampled for one frame.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Input {
+    pub current: Buttons,
+    pub pushed: Buttons,
+}
+
+impl Input {
+    /// The frame `buttons` go down: they are held and were just pushed.
+    pub const fn push(buttons: Buttons) -> Self {
+        Self {
+            current: buttons,
+            pushed: buttons,
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Where the HUD and menus sit on the screen. The device sprites and the
+//! host renderer both place themselves with these, so the golden images in
+//! `dino-host` catch a change here. They don't render the device's sprites,
+//! though.
+
+use alloc::{format, string::String};
+
+use crate::{
+    display::{DISPLAY_HEIGHT, DISPLAY_WIDTH},
+    math::{Rect, Size},
+    obstacle::ObstacleKind,
+    power_up::PowerUpKind,
+    scores::{self, Entry, Mode},
+    settings::OptionRow,
+    shop::{Item, Wallet},
+    skin::Skin,
+    stats::{self, Stats, StatsPage},
+};
+
+pub const CENTER_X: f32 = DISPLAY_WIDTH as f32 / 2.0;
+pub const CENTER_Y: f32 = DISPLAY_HEIGHT as f32 / 2.0;
+
+/// The "GAME OVER" image, drawn at half the size of the asset.
+pub const GAME_OVER: Rect = Rect::new(CENTER_X - 195.0 / 2.0, CENTER_Y - 66.0, 195.0, 15.0);
+/// The restart icon below the "GAME OVER" image, at half the asset's size.
+pub const RESTART_ICON: Rect = Rect::new(CENTER_X - 18.0, CENTER_Y - 36.0, 36.0, 32.0);
+
+pub const START_MESSAGE: &str = "Press Ⓐ to start";
+pub const START_MESSAGE_CENTER: (f32, f32) = (CENTER_X, CENTER_Y);
+pub const RESTART_MESSAGE: &str = "Press Ⓐ to restart";
+pub const RESTART_MESSAGE_CENTER: (f32, f32) = (CENTER_X, CENTER_Y + 18.0);
+pub const SHOP_MESSAGE: &str = "Press Ⓑ for the shop";
+pub const SHOP_MESSAGE_CENTER: (f32, f32) = (CENTER_X, CENTER_Y + 18.0);
+pub const HIGH_SCORES_MESSAGE: &str = "Press Up for high scores";
+pub const HIGH_SCORES_MESSAGE_CENTER: (f32, f32) = (CENTER_X, CENTER_Y - 18.0);
+pub const PROFILE_MESSAGE_CENTER: (f32, f32) = (CENTER_X, CENTER_Y - 36.0);
+
+pub fn profile_text(name: &str) -> String {
+    format!("Player {} - Down to switch", name)
+}
+/// The skin picker between runs, shown once a second skin is unlocked.
+pub const SKIN_MESSAGE_CENTER: (f32, f32) = (CENTER_X, CENTER_Y + 36.0);
+
+pub fn skin_text(skin: Skin) -> String {
+    format!("< {} >", skin.get_name())
+}
+
+/// Names an asset that failed to load, along the bottom edge.
+pub const ASSET_ERROR_CENTER: (f32, f32) = (CENTER_X, DISPLAY_HEIGHT as f32 - 10.0);
+
+pub fn asset_error_text(name: &str) -> String {
+    format!("Missing asset: {}", name)
+}
+
+/// Tells that the save file was damaged, above the asset errors.
+pub const SAVE_ERROR_CENTER: (f32, f32) = (CENTER_X, DISPLAY_HEIGHT as f32 - 28.0);
+
+pub fn save_error_text(restored: bool) -> &'static str {
+    if restored {
+        "Save damaged, restored a backup"
+    } else {
+        "Save damaged, progress reset"
+    }
+}
+
+/// The masks hiding the scene on the Ready screen, moved apart at
+/// `MASK_SPEED` px/s once the game starts.
+pub const MASK_WIDTH: f32 = DISPLAY_WIDTH as f32 - 80.0;
+pub const MASK_LEFT_X: f32 = 20.0 - MASK_WIDTH;
+pub const MASK_RIGHT_X: f32 = 100.0;
+pub const MASK_SPEED: f32 = 500.0;
+
+/// Gap between the score text and the right edge of the screen.
+pub const SCORE_MARGIN: i32 = 2;
+
+pub fn score_text(record: i32, score: i32) -> String {
+    format!("HI  {:05}  {:05}", record, score)
+}
+
+/// The top-left corner of the score text, right-aligned at the top.
+pub fn score_position(text_width: u32) -> (i32, i32) {
+    (DISPLAY_WIDTH as i32 - text_width as i32 - SCORE_MARGIN, 0)
+}
+
+/// The heart shown for each remaining life, at half the asset's size.
+pub const LIFE_ICON_SIZE: Size = Size::new(11.0, 10.0);
+const LIFE_ICON_GAP: i32 = 2;
+
+/// The top-left corner of the `index`-th life icon. Icons line up to the
+/// left of the score text, which starts at `score_x`.
+pub fn life_icon_position(score_x: i32, index: u32) -> (i32, i32) {
+    let step = LIFE_ICON_SIZE.width as i32 + LIFE_ICON_GAP;
+    (score_x - 4 - (index as i32 + 1) * step, 2)
+}
+
+pub fn coin_text(coins: u32) -> String {
+    format!("{}", coins)
+}
+
+/// Where the coin icon and the number of coins collected in the run go,
+/// right-aligned below the score.
+pub fn coin_counter_position(text_width: u32) -> ((i32, i32), (i32, i32)) {
+    let (x, _) = score_position(text_width);
+    let icon_x = x - 4 - crate::coin::COIN_SIZE.width as i32;
+    ((icon_x, 18), (x, 16))
+}
+
+/// The countdown of an active power-up, e.g. `SHIELD 7`.
+pub fn power_up_text(kind: PowerUpKind, time_left: f32) -> String {
+    format!("{} {}", kind.get_name(), ceil(time_left))
+}
+
+const POWER_UP_TOP: i32 = 30;
+const POWER_UP_LINE_HEIGHT: i32 = 14;
+
+/// The top-left corner of the `index`-th power-up countdown. They are
+/// stacked right-aligned below the score and the coin counter.
+pub fn power_up_position(text_width: u32, index: u32) -> (i32, i32) {
+    let (x, _) = score_position(text_width);
+    (x, POWER_UP_TOP + index as i32 * POWER_UP_LINE_HEIGHT)
+}
+
+/// Height of the strip at the top of the screen the HUD draws in, with room
+/// for every power-up's countdown.
+pub const HUD_HEIGHT: i32 = POWER_UP_TOP + PowerUpKind::ALL.len() as i32 * POWER_UP_LINE_HEIGHT;
+
+/// `f32::ceil` needs std.
+fn ceil(x: f32) -> i32 {
+    let i = x as i32;
+    if (i as f32) < x {
+        i + 1
+    } else {
+        i
+    }
+}
+
+pub const SHOP_TITLE_Y: i32 = 16;
+pub const SHOP_COINS_Y: i32 = 40;
+pub const SHOP_ROWS_Y: i32 = 72;
+pub const SHOP_ROW_HEIGHT: i32 = 22;
+pub const SHOP_LEFT_X: i32 = 60;
+pub const SHOP_RIGHT_X: i32 = DISPLAY_WIDTH as i32 - 60;
+pub const SHOP_MESSAGE_Y: i32 = 176;
+pub const SHOP_HINT: &str = "Ⓐ buy   Ⓑ back";
+pub const SHOP_HINT_Y: i32 = 204;
+
+pub fn shop_coins_text(wallet: &Wallet) -> String {
+    format!("COINS {}", wallet.get_coins())
+}
+
+/// The name column of an item in the shop, with a cursor if it is selected
+/// and how many are owned.
+pub fn shop_item_text(item: Item, wallet: &Wallet, selected: bool) -> String {
+    let cursor = if selected { "> " } else { "  " };
+    let owned = wallet.get_owned(item);
+    match item.get_perk() {
+        Some(_) if owned > 0 => format!("{}{} x{}", cursor, item.get_name(), owned),
+        None if owned > 0 => format!("{}{} (owned)", cursor, item.get_name()),
+        _ => format!("{}{}", cursor, item.get_name()),
+    }
+}
+
+pub fn shop_price_text(item: Item) -> String {
+    format!("{}", item.get_price())
+}
+
+/// The top-left corner of the `index`-th shop row.
+pub fn shop_row_y(index: usize) -> i32 {
+    SHOP_ROWS_Y + index as i32 * SHOP_ROW_HEIGHT
+}
+
+pub const OPTIONS_TITLE_Y: i32 = 16;
+pub const OPTIONS_ROWS_Y: i32 = 48;
+pub const OPTIONS_ROW_HEIGHT: i32 = 22;
+pub const OPTIONS_LEFT_X: i32 = 40;
+pub const OPTIONS_RIGHT_X: i32 = DISPLAY_WIDTH as i32 - 40;
+pub const OPTIONS_HINT: &str = "Ⓐ change   Ⓑ back";
+pub const OPTIONS_HINT_Y: i32 = 204;
+
+/// The name column of an options row, with a cursor if it is selected.
+pub fn options_row_text(row: OptionRow, selected: bool) -> String {
+    let cursor = if selected { "> " } else { "  " };
+    format!("{}{}", cursor, row.get_name())
+}
+
+/// The top-left corner of the `index`-th options row.
+pub fn options_row_y(index: usize) -> i32 {
+    OPTIONS_ROWS_Y + index as i32 * OPTIONS_ROW_HEIGHT
+}
+
+pub const SCORES_TITLE_Y: i32 = 12;
+pub const SCORES_MODE_Y: i32 = 32;
+pub const SCORES_ROWS_Y: i32 = 56;
+pub const SCORES_ROW_HEIGHT: i32 = 16;
+/// Where the rank column ends.
+pub const SCORES_RANK_RIGHT_X: i32 = 90;
+pub const SCORES_INITIALS_X: i32 = 100;
+/// Where the score column ends.
+pub const SCORES_SCORE_RIGHT_X: i32 = 210;
+pub const SCORES_DATE_X: i32 = 230;
+pub const SCORES_EMPTY: &str = "No scores yet";
+pub const SCORES_HINT: &str = "Left/Right mode   Down stats   Ⓑ back";
+pub const SCORES_HINT_Y: i32 = 220;
+
+pub fn scores_mode_text(mode: Mode) -> String {
+    format!("< {} >", mode.get_name())
+}
+
+/// The cells of a table row: rank, with a cursor if it is the run just
+/// entered, initials, score and date.
+pub fn scores_row_texts(rank: usize, entry: &Entry, highlighted: bool) -> [String; 4] {
+    let cursor = if highlighted { "> " } else { "" };
+    [
+        format!("{}{}.", cursor, rank + 1),
+        String::from(entry.get_initials()),
+        format!("{}", entry.score),
+        scores::format_date(entry.date),
+    ]
+}
+
+/// The top-left corner of the `rank`-th table row.
+pub fn scores_row_y(rank: usize) -> i32 {
+    SCORES_ROWS_Y + rank as i32 * SCORES_ROW_HEIGHT
+}
+
+pub const STATS_TITLE: &str = "STATS";
+pub const STATS_TITLE_Y: i32 = 12;
+pub const STATS_PAGE_Y: i32 = 32;
+pub const STATS_ROWS_Y: i32 = 56;
+pub const STATS_ROW_HEIGHT: i32 = 17;
+pub const STATS_LEFT_X: i32 = 60;
+/// Where the cleared column of the obstacles page ends.
+pub const STATS_MIDDLE_RIGHT_X: i32 = 270;
+/// Where the last column ends.
+pub const STATS_RIGHT_X: i32 = 340;
+pub const STATS_OBSTACLES_HEADER: [&str; 3] = ["Obstacle", "Cleared", "Deaths"];
+pub const STATS_HINT: &str = "Left/Right page   Ⓑ back";
+pub const STATS_HINT_Y: i32 = 220;
+
+pub fn stats_page_text(page: StatsPage) -> String {
+    format!("< {} >", page.get_name())
+}
+
+/// The rows of the totals page, as a label and a value.
+pub fn stats_total_rows(stats: &Stats) -> [[String; 2]; 8] {
+    let row = |label: &str, value: String| [String::from(label), value];
+    [
+        row("Runs", format!("{}", stats.runs)),
+        row("Distance", stats::format_distance(stats.distance)),
+        row("Play time", stats::format_duration(stats.play_time_ms)),
+        row("Jumps", format!("{}", stats.jumps)),
+        row("Ducks", format!("{}", stats.ducks)),
+        row("Power-ups", format!("{}", stats.power_ups)),
+        row(
+            "Longest run",
+            stats::format_duration(stats.longest_run_ms.into()),
+        ),
+        row("Average score", format!("{}", stats.get_average_score())),
+    ]
+}
+
+/// A row of the obstacles page: the obstacle, how often it was cleared and
+/// how many runs it ended.
+pub fn stats_obstacle_row(kind: ObstacleKind, stats: &Stats) -> [String; 3] {
+    [
+        String::from(kind.get_name()),
+        format!("{}", stats.get_cleared(kind)),
+        format!("{}", stats.get_deaths(kind)),
+    ]
+}
+
+/// The top-left corner of the `index`-th row, where the obstacles page
+/// starts with its header.
+pub fn stats_row_y(index: usize) -> i32 {
+    STATS_ROWS_Y + index as i32 * STATS_ROW_HEIGHT
+}
+
+pub const INITIALS_TITLE: &str = "NEW HIGH SCORE";
+pub const INITIALS_TITLE_Y: i32 = 40;
+pub const INITIALS_SCORE_Y: i32 = 64;
+pub const INITIALS_LETTERS_Y: i32 = 104;
+pub const INITIALS_SPACING: i32 = 24;
+pub const INITIALS_CURSOR: &str = "^";
+pub const INITIALS_CURSOR_Y: i32 = 122;
+pub const INITIALS_HINT: &str = "Up/Down or crank   Ⓐ next";
+pub const INITIALS_HINT_Y: i32 = 204;
+
+pub fn initials_score_text(score: i32, mode: Mode) -> String {
+    format!("{} - {}", score, mode.get_name())
+}
+
+/// The x the `index`-th letter, and the cursor under it, are centered on.
+pub fn initials_letter_center_x(index: usize) -> i32 {
+    CENTER_X as i32 + (index as i32 - 1) * INITIALS_SPACING
+}
+
+pub const PROFILES_TITLE: &str = "PROFILES";
+pub const PROFILES_TITLE_Y: i32 = 16;
+pub const PROFILES_ROWS_Y: i32 = 48;
+pub const PROFILES_ROW_HEIGHT: i32 = 22;
+pub const PROFILES_LEFT_X: i32 = 120;
+pub const PROFILES_NEW_ROW: &str = "New profile";
+pub const PROFILES_HINT: &str = "Ⓐ pick   Ⓑ back";
+pub const PROFILES_HINT_Y: i32 = 204;
+pub const PROFILES_NAMING_TITLE: &str = "NEW PROFILE";
+pub const PROFILES_NAMING_TEXT: &str = "Pick the player's initials";
+
+/// A row of the profile switcher, with a cursor if it is selected and a mark
+/// on the profile in use.
+pub fn profiles_row_text(name: &str, selected: bool, current: bool) -> String {
+    let cursor = if selected { "> " } else { "  " };
+    let mark = if current { " (playing)" } else { "" };
+    format!("{}{}{}", cursor, name, mark)
+}
+
+/// The top-left corner of the `index`-th profile row.
+pub fn profiles_row_y(index: usize) -> i32 {
+    PROFILES_ROWS_Y + index as i32 * PROFILES_ROW_HEIGHT
+}
+
+/// The x of a text of the given width centered on the screen.
+pub fn centered_x(text_width: u32) -> i32 {
+    CENTER_X as i32 - text_width as i32 / 2
+}
+
+/// The top-left corner of a message of the given size centered at `center`.
+pub fn centered(center: (f32, f32), width: f32, height: f32) -> (f32, f32) {
+    (center.0 - width / 2.0, center.1 - height / 2.0)
+}
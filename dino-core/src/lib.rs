@@ -0,0 +1,38 @@
+#![no_std]
+
+//! The gameplay rules of Dino, free of any Playdate API.
+//!
+//! The device build drives a [`World`] every frame and only mirrors its state
+//! into sprites. Host tools (e.g. the replay verifier) run the very same
+//! simulation without the SDK.
+
+extern crate alloc;
+
+pub mod args;
+pub mod coin;
+pub mod dino;
+pub mod display;
+pub mod ground;
+pub mod history;
+pub mod hitbox;
+pub mod input;
+pub mod layout;
+pub mod lives;
+pub mod log_filter;
+pub mod math;
+pub mod obstacle;
+pub mod power_up;
+pub mod profile;
+pub mod replay;
+pub mod rng;
+pub mod save;
+pub mod scoreboard;
+pub mod scores;
+pub mod settings;
+pub mod shop;
+pub mod skin;
+pub mod stats;
+pub mod theme;
+pub mod world;
+
+pub use world::{Event, GameState, World};
@@ -0,0 +1,87 @@
+use crate::args;
+
+/// How many more hits the dino can take. A run with a single life is the
+/// classic game: the first hit ends it.
+pub struct Lives {
+    max: u32,
+    remaining: u32,
+    invulnerable_time: f32,
+}
+
+impl Lives {
+    pub fn new(max: u32) -> Self {
+        Self {
+            max: max.max(1),
+            remaining: max.max(1),
+            invulnerable_time: 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.remaining = self.max;
+        self.invulnerable_time = 0.0;
+    }
+
+    pub fn get_max(&self) -> u32 {
+        self.max
+    }
+
+    /// Gives lives on top of the maximum, e.g. from a perk.
+    pub fn grant(&mut self, count: u32) {
+        self.remaining += count;
+    }
+
+    /// Takes effect on the next [`Lives::reset`].
+    pub fn set_max(&mut self, max: u32) {
+        self.max = max.max(1);
+    }
+
+    pub fn get_remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    /// Gives back lives, e.g. from a power-up. Never goes above the maximum.
+    pub fn add(&mut self, count: u32) {
+        self.remaining = (self.remaining + count).min(self.max);
+    }
+
+    /// Seconds of invulnerability left.
+    pub fn get_invulnerable_time(&self) -> f32 {
+        self.invulnerable_time
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_time > 0.0
+    }
+
+    /// Makes the dino invulnerable for at least `secs`.
+    pub fn make_invulnerable(&mut self, secs: f32) {
+        self.invulnerable_time = self.invulnerable_time.max(secs);
+    }
+
+    /// Whether a blinking dino is shown this frame. Blinks 10 times a second
+    /// while invulnerable.
+    pub fn is_blink_visible(&self) -> bool {
+        !self.is_invulnerable() || (self.invulnerable_time * 10.0) as i32 & 1 == 0
+    }
+
+    /// Takes a life. Returns false when it was the last one.
+    pub fn hit(&mut self) -> bool {
+        self.remaining = self.remaining.saturating_sub(1);
+        if self.remaining == 0 {
+            return false;
+        }
+        self.make_invulnerable(args::INVULNERABLE_SECS);
+        true
+    }
+
+    pub fn update(&mut self, delta: f32) {
+        self.invulnerable_time = (self.invulnerable_time - delta).max(0.0);
+    }
+}
+
+impl Default for Lives {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
@@ -0,0 +1,155 @@
+//! Per-module log levels, parsed the way `RUST_LOG` is:
+//!
+//! ```text
+//! warn,obstacle=trace,cheats=off
+//! ```
+//!
+//! A bare level sets the default and `module=level` sets the level of a
+//! module and the modules below it. The most specific entry wins.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Most to least severe. `Off` only appears in filters.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 6] = [
+        Level::Off,
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Level::Off => "off",
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.get_name() == name)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Filter {
+    default: Level,
+    /// Module paths below the crate, like `obstacle`, and their levels.
+    modules: Vec<(String, Level)>,
+}
+
+impl Filter {
+    /// Lets everything up to `default` through.
+    pub fn new(default: Level) -> Self {
+        Self {
+            default,
+            modules: Vec::new(),
+        }
+    }
+
+    /// Reads `spec`, starting from `default` when it names no default level.
+    pub fn parse(spec: &str, default: Level) -> Result<Self, String> {
+        let mut filter = Self::new(default);
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let (module, level) = match directive.split_once('=') {
+                Some((module, level)) => (Some(module.trim()), level.trim()),
+                None => (None, directive),
+            };
+            let level = Level::from_name(level).ok_or(format!("unknown level: {}", level))?;
+            match module {
+                Some(module) => filter.modules.push((module.to_string(), level)),
+                None => filter.default = level,
+            }
+        }
+        Ok(filter)
+    }
+
+    /// The level of the most specific entry that covers `module`.
+    pub fn get_level(&self, module: &str) -> Level {
+        self.modules
+            .iter()
+            .filter(|(m, _)| {
+                module
+                    .strip_prefix(m.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(m, _)| m.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_spec_keeps_the_default() {
+        let filter = Filter::parse("", Level::Info).unwrap();
+        assert_eq!(filter, Filter::new(Level::Info));
+        assert_eq!(filter.get_level("obstacle"), Level::Info);
+    }
+
+    #[test]
+    fn bare_level_sets_the_default() {
+        let filter = Filter::parse(" warn ", Level::Info).unwrap();
+        assert_eq!(filter.get_level("obstacle"), Level::Warn);
+        assert_eq!(filter.get_level("save"), Level::Warn);
+    }
+
+    #[test]
+    fn directives_set_module_levels() {
+        let filter = Filter::parse("warn, obstacle = trace,cheats=off,,", Level::Info).unwrap();
+        assert_eq!(filter.get_level("obstacle"), Level::Trace);
+        assert_eq!(filter.get_level("cheats"), Level::Off);
+        assert_eq!(filter.get_level("save"), Level::Warn);
+    }
+
+    #[test]
+    fn longest_prefix_wins() {
+        let filter = Filter::parse("ui=warn,ui::menu=debug", Level::Info).unwrap();
+        assert_eq!(filter.get_level("ui"), Level::Warn);
+        assert_eq!(filter.get_level("ui::button"), Level::Warn);
+        assert_eq!(filter.get_level("ui::menu"), Level::Debug);
+        assert_eq!(filter.get_level("ui::menu::item"), Level::Debug);
+    }
+
+    #[test]
+    fn prefixes_match_whole_segments() {
+        let filter = Filter::parse("ui=trace", Level::Error).unwrap();
+        assert_eq!(filter.get_level("ui_layer"), Level::Error);
+        assert_eq!(filter.get_level("u"), Level::Error);
+    }
+
+    #[test]
+    fn unknown_level_is_rejected() {
+        let e = Filter::parse("obstacle=loud", Level::Info).unwrap_err();
+        assert_eq!(e, "unknown level: loud");
+        assert!(Filter::parse("verbose", Level::Info).is_err());
+    }
+
+    #[test]
+    fn level_names_round_trip() {
+        for level in Level::ALL {
+            assert_eq!(Level::from_name(level.get_name()), Some(level));
+        }
+    }
+}
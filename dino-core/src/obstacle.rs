@@ -0,0 +1,275 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    dino,
+    display::{DISPLAY_HEIGHT, DISPLAY_WIDTH},
+    ground::Ground,
+    hitbox,
+    math::{Rect, Size},
+    rng::Rng,
+    Event, GameState,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ObstacleKind {
+    Bird,
+    LowBird,
+    CactusSmall1,
+    CactusSmall2,
+    CactusSmall3,
+    CactusBig1,
+    CactusBig2,
+    CactusBig3,
+}
+
+impl ObstacleKind {
+    pub const ALL: [ObstacleKind; 8] = [
+        ObstacleKind::Bird,
+        ObstacleKind::LowBird,
+        ObstacleKind::CactusSmall1,
+        ObstacleKind::CactusSmall2,
+        ObstacleKind::CactusSmall3,
+        ObstacleKind::CactusBig1,
+        ObstacleKind::CactusBig2,
+        ObstacleKind::CactusBig3,
+    ];
+
+    fn random(rng: &mut Rng) -> Self {
+        Self::ALL[rng.gen_index(Self::ALL.len())]
+    }
+
+    /// Name used in theme manifests and replays.
+    pub fn get_key(&self) -> &'static str {
+        match self {
+            ObstacleKind::Bird => "bird",
+            ObstacleKind::LowBird => "low-bird",
+            ObstacleKind::CactusSmall1 => "cactus-small-1",
+            ObstacleKind::CactusSmall2 => "cactus-small-2",
+            ObstacleKind::CactusSmall3 => "cactus-small-3",
+            ObstacleKind::CactusBig1 => "cactus-big-1",
+            ObstacleKind::CactusBig2 => "cactus-big-2",
+            ObstacleKind::CactusBig3 => "cactus-big-3",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.get_key() == key)
+    }
+
+    /// Name on the stats screen.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            ObstacleKind::Bird => "High bird",
+            ObstacleKind::LowBird => "Low bird",
+            ObstacleKind::CactusSmall1 => "Small cactus",
+            ObstacleKind::CactusSmall2 => "2 small cacti",
+            ObstacleKind::CactusSmall3 => "3 small cacti",
+            ObstacleKind::CactusBig1 => "Big cactus",
+            ObstacleKind::CactusBig2 => "2 big cacti",
+            ObstacleKind::CactusBig3 => "3 big cacti",
+        }
+    }
+
+    pub(crate) fn index(&self) -> usize {
+        Self::ALL.iter().position(|k| k == self).unwrap()
+    }
+
+    pub fn is_bird(&self) -> bool {
+        matches!(self, ObstacleKind::Bird | ObstacleKind::LowBird)
+    }
+
+    /// The bundled shape, see [`Obstacles::set_shape`] for themed ones.
+    pub fn default_shape(&self) -> Shape {
+        Shape {
+            size: self.default_size(),
+            hitboxes: self.default_hitboxes().to_vec(),
+        }
+    }
+
+    /// On-screen size, i.e. half the size of the source image.
+    fn default_size(&self) -> Size {
+        match self {
+            ObstacleKind::Bird | ObstacleKind::LowBird => Size::new(46.0, 34.0),
+            ObstacleKind::CactusSmall1 => Size::new(17.0, 35.0),
+            ObstacleKind::CactusSmall2 => Size::new(34.0, 35.0),
+            ObstacleKind::CactusSmall3 => Size::new(51.0, 35.0),
+            ObstacleKind::CactusBig1 => Size::new(25.0, 50.0),
+            ObstacleKind::CactusBig2 => Size::new(50.0, 50.0),
+            ObstacleKind::CactusBig3 => Size::new(75.0, 50.0),
+        }
+    }
+
+    fn default_hitboxes(&self) -> &'static [Rect] {
+        match self {
+            ObstacleKind::Bird | ObstacleKind::LowBird => hitbox::BIRD,
+            ObstacleKind::CactusSmall1 => hitbox::CACTUS_SMALL_1,
+            ObstacleKind::CactusSmall2 => hitbox::CACTUS_SMALL_2,
+            ObstacleKind::CactusSmall3 => hitbox::CACTUS_SMALL_3,
+            ObstacleKind::CactusBig1 => hitbox::CACTUS_BIG_1,
+            ObstacleKind::CactusBig2 => hitbox::CACTUS_BIG_2,
+            ObstacleKind::CactusBig3 => hitbox::CACTUS_BIG_3,
+        }
+    }
+
+    fn spawn_bounds(&self, size: Size) -> Rect {
+        const BIRD_Y: f32 = 88.0;
+        let pos_x = DISPLAY_WIDTH as f32;
+        let pos_y = match self {
+            ObstacleKind::Bird => BIRD_Y + 34.0,
+            ObstacleKind::LowBird => BIRD_Y + 60.0,
+            _ => DISPLAY_HEIGHT as f32 - Ground::COLLIDE_HEIGHT - size.height,
+        };
+        Rect::new(pos_x, pos_y, size.width, size.height)
+    }
+}
+
+/// The on-screen size of an obstacle and its collision boxes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Shape {
+    pub size: Size,
+    pub hitboxes: Vec<Rect>,
+}
+
+pub struct Obstacle {
+    id: u32,
+    kind: ObstacleKind,
+    bounds: Rect,
+    /// Touched the dino, so it doesn't count as cleared.
+    hit: bool,
+    /// Already behind the dino.
+    passed: bool,
+}
+
+impl Obstacle {
+    /// Unique per run, so frontends can match their sprites to obstacles.
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_kind(&self) -> ObstacleKind {
+        self.kind
+    }
+
+    pub fn get_bounds(&self) -> Rect {
+        self.bounds
+    }
+}
+
+pub struct Obstacles {
+    obstacles: Vec<Obstacle>,
+    next_id: u32,
+    shapes: Vec<Shape>,
+}
+
+impl Obstacles {
+    pub fn new() -> Self {
+        Self {
+            obstacles: vec![],
+            next_id: 0,
+            shapes: ObstacleKind::ALL
+                .iter()
+                .map(|k| k.default_shape())
+                .collect(),
+        }
+    }
+
+    pub fn get_shape(&self, kind: ObstacleKind) -> &Shape {
+        &self.shapes[kind.index()]
+    }
+
+    /// Replaces the shape of an obstacle kind, for theme packs. Shapes are
+    /// kept across runs.
+    pub fn set_shape(&mut self, kind: ObstacleKind, shape: Shape) {
+        self.shapes[kind.index()] = shape;
+    }
+
+    pub fn reset_shapes(&mut self) {
+        for kind in ObstacleKind::ALL {
+            self.shapes[kind.index()] = kind.default_shape();
+        }
+    }
+
+    /// The shapes that differ from the bundled ones.
+    pub fn custom_shapes(&self) -> Vec<(ObstacleKind, Shape)> {
+        ObstacleKind::ALL
+            .into_iter()
+            .filter(|k| *self.get_shape(*k) != k.default_shape())
+            .map(|k| (k, self.get_shape(k).clone()))
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        self.obstacles.clear();
+        self.next_id = 0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Obstacle> {
+        self.obstacles.iter()
+    }
+
+    /// Returns the first obstacle the given boxes would run into.
+    pub fn check_collision(&self, boxes: &[Rect], at: &Rect) -> Option<&Obstacle> {
+        self.obstacles
+            .iter()
+            .find(|o| hitbox::collides(boxes, at, &self.get_shape(o.kind).hitboxes, &o.bounds))
+    }
+
+    pub(crate) fn mark_hit(&mut self, id: u32) {
+        if let Some(obstacle) = self.obstacles.iter_mut().find(|o| o.id == id) {
+            obstacle.hit = true;
+        }
+    }
+
+    /// Moves the obstacles and spawns the next one. Returns the obstacle that
+    /// was just spawned, so power-ups and coins can be placed around it.
+    pub fn update(
+        &mut self,
+        game_state: GameState,
+        velocity: f32,
+        delta: f32,
+        rng: &mut Rng,
+        events: &mut Vec<Event>,
+    ) -> Option<&Obstacle> {
+        // Update obstacles
+        if game_state == GameState::Playing {
+            for obstacle in &mut self.obstacles {
+                obstacle.bounds.x -= velocity * delta;
+                if !obstacle.passed && obstacle.bounds.right() < dino::INITLAL_BOUNDS.x {
+                    obstacle.passed = true;
+                    if !obstacle.hit {
+                        events.push(Event::ObstacleCleared(obstacle.kind));
+                    }
+                }
+            }
+        }
+        // remove obstacles that are off screen
+        self.obstacles
+            .retain(|obstacle| obstacle.bounds.right() >= 0.0);
+        // Add new obstacles
+        if self.obstacles.is_empty() {
+            let kind = ObstacleKind::random(rng);
+            return Some(self.spawn(kind, events));
+        }
+        None
+    }
+
+    /// Adds an obstacle at the right edge of the screen.
+    pub(crate) fn spawn(&mut self, kind: ObstacleKind, events: &mut Vec<Event>) -> &Obstacle {
+        self.obstacles.push(Obstacle {
+            id: self.next_id,
+            kind,
+            bounds: kind.spawn_bounds(self.get_shape(kind).size),
+            hit: false,
+            passed: false,
+        });
+        self.next_id += 1;
+        events.push(Event::ObstacleSpawned(kind));
+        self.obstacles.last().unwrap()
+    }
+}
+
+impl Default for Obstacles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,224 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    args,
+    math::{Rect, Size},
+    obstacle::Obstacle,
+    rng::Rng,
+    Event, GameState,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PowerUpKind {
+    /// Absorbs one hit.
+    Shield,
+    /// Allows a second jump in mid-air.
+    DoubleJump,
+    /// Slows the world down.
+    SlowTime,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 3] = [
+        PowerUpKind::Shield,
+        PowerUpKind::DoubleJump,
+        PowerUpKind::SlowTime,
+    ];
+
+    pub const SIZE: Size = Size::new(16.0, 16.0);
+
+    fn random(rng: &mut Rng) -> Self {
+        Self::ALL[rng.gen_index(Self::ALL.len())]
+    }
+
+    /// How long the power-up lasts once collected.
+    pub fn get_duration(&self) -> f32 {
+        match self {
+            PowerUpKind::Shield => args::SHIELD_SECS,
+            PowerUpKind::DoubleJump => args::DOUBLE_JUMP_SECS,
+            PowerUpKind::SlowTime => args::SLOW_TIME_SECS,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            PowerUpKind::Shield => "SHIELD",
+            PowerUpKind::DoubleJump => "JUMP",
+            PowerUpKind::SlowTime => "SLOW",
+        }
+    }
+
+    /// Name of the image and the sound.
+    pub fn get_path(&self) -> &'static str {
+        match self {
+            PowerUpKind::Shield => "power-up/shield",
+            PowerUpKind::DoubleJump => "power-up/double-jump",
+            PowerUpKind::SlowTime => "power-up/slow-time",
+        }
+    }
+}
+
+/// Close to the top of a jump, see [`args::JUMP_VELOCITY`].
+const POWER_UP_Y: f32 = 40.0;
+
+/// A power-up waiting to be collected.
+pub struct PowerUp {
+    id: u32,
+    kind: PowerUpKind,
+    bounds: Rect,
+}
+
+impl PowerUp {
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_kind(&self) -> PowerUpKind {
+        self.kind
+    }
+
+    pub fn get_bounds(&self) -> Rect {
+        self.bounds
+    }
+}
+
+pub struct PowerUps {
+    power_ups: Vec<PowerUp>,
+    next_id: u32,
+    /// Seconds left for each kind, in [`PowerUpKind::ALL`] order.
+    active: [f32; 3],
+}
+
+impl PowerUps {
+    pub fn new() -> Self {
+        Self {
+            power_ups: vec![],
+            next_id: 0,
+            active: [0.0; 3],
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.power_ups.clear();
+        self.next_id = 0;
+        self.active = [0.0; 3];
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PowerUp> {
+        self.power_ups.iter()
+    }
+
+    fn index(kind: PowerUpKind) -> usize {
+        PowerUpKind::ALL.iter().position(|k| *k == kind).unwrap()
+    }
+
+    /// Seconds left of an active power-up, 0 if it's not active.
+    pub fn get_time_left(&self, kind: PowerUpKind) -> f32 {
+        self.active[Self::index(kind)]
+    }
+
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.get_time_left(kind) > 0.0
+    }
+
+    /// The active power-ups and their remaining seconds.
+    pub fn active(&self) -> impl Iterator<Item = (PowerUpKind, f32)> + '_ {
+        PowerUpKind::ALL
+            .iter()
+            .map(|k| (*k, self.get_time_left(*k)))
+            .filter(|(_, t)| *t > 0.0)
+    }
+
+    pub fn activate(&mut self, kind: PowerUpKind) {
+        self.active[Self::index(kind)] = kind.get_duration();
+    }
+
+    /// Uses up an active power-up before it runs out, e.g. a shield that
+    /// absorbed a hit.
+    pub fn consume(&mut self, kind: PowerUpKind) {
+        self.active[Self::index(kind)] = 0.0;
+    }
+
+    /// Scale of the world's speed, less than 1 while time is slowed down.
+    pub fn get_time_scale(&self) -> f32 {
+        if self.is_active(PowerUpKind::SlowTime) {
+            args::SLOW_TIME_SCALE
+        } else {
+            1.0
+        }
+    }
+
+    /// Sometimes places a power-up high above a freshly spawned cactus. Only a
+    /// jump over the cactus reaches it.
+    pub fn spawn_over(&mut self, obstacle: &Obstacle, rng: &mut Rng) {
+        if obstacle.get_kind().is_bird() || !self.power_ups.is_empty() {
+            return;
+        }
+        if rng.gen_index(args::POWER_UP_CHANCE) != 0 {
+            return;
+        }
+        let kind = PowerUpKind::random(rng);
+        let bounds = obstacle.get_bounds();
+        let size = PowerUpKind::SIZE;
+        self.power_ups.push(PowerUp {
+            id: self.next_id,
+            kind,
+            bounds: Rect::new(
+                bounds.x + (bounds.width - size.width) / 2.0,
+                POWER_UP_Y,
+                size.width,
+                size.height,
+            ),
+        });
+        self.next_id += 1;
+    }
+
+    /// Moves the collectibles along with the ground and counts down the
+    /// active power-ups. `delta` is real time, `world_delta` is scaled by
+    /// [`PowerUps::get_time_scale`].
+    pub fn update(
+        &mut self,
+        game_state: GameState,
+        velocity: f32,
+        delta: f32,
+        world_delta: f32,
+        events: &mut Vec<Event>,
+    ) {
+        if game_state != GameState::Playing {
+            return;
+        }
+        for power_up in &mut self.power_ups {
+            power_up.bounds.x -= velocity * world_delta;
+        }
+        self.power_ups.retain(|p| p.bounds.right() >= 0.0);
+        for (i, kind) in PowerUpKind::ALL.iter().enumerate() {
+            if self.active[i] > 0.0 {
+                self.active[i] = (self.active[i] - delta).max(0.0);
+                if self.active[i] == 0.0 {
+                    events.push(Event::PowerUpExpired(*kind));
+                }
+            }
+        }
+    }
+
+    /// Removes and returns the collectibles touched by the given boxes.
+    pub fn collect(&mut self, boxes: &[Rect], at: &Rect) -> Vec<PowerUpKind> {
+        let mut collected = vec![];
+        self.power_ups.retain(|p| {
+            let touched = boxes
+                .iter()
+                .any(|b| b.offset(at.x, at.y).intersects(&p.bounds));
+            if touched {
+                collected.push(p.kind);
+            }
+            !touched
+        });
+        collected
+    }
+}
+
+impl Default for PowerUps {
+    fn default() -> Self {
+        Self::new()
+    }
+}
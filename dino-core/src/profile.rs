@@ -0,0 +1,222 @@
+//! Player profiles, each with its own save, wallet, skins and replays.
+//!
+//! A profile is a folder in [`PROFILES_DIR`] named after its player's
+//! initials. The frontends keep the files, this is the menu that picks and
+//! creates them.
+
+use alloc::string::String;
+
+use crate::{
+    input::{Buttons, Input},
+    scores::{self, InitialsEntry, InitialsUpdate, DEFAULT_INITIALS, INITIALS_LEN},
+};
+
+pub const PROFILES_DIR: &str = "profiles";
+/// The profile the files of older versions are moved into.
+pub const DEFAULT_PROFILE: &str = "P1";
+pub const MAX_PROFILES: usize = 6;
+
+/// Whether `name` can be a profile's folder: up to [`INITIALS_LEN`] capital
+/// letters and digits.
+pub fn is_valid_name(name: &str) -> bool {
+    (1..=INITIALS_LEN).contains(&name.len())
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// The profile switcher: Up and Down pick a profile, Ⓐ switches to it and Ⓑ
+/// leaves. The row after the profiles creates a new one, named with the
+/// initials entry.
+pub struct ProfileMenu {
+    selected: usize,
+    naming: Option<InitialsEntry>,
+}
+
+impl ProfileMenu {
+    /// Starts at the profile in use.
+    pub fn new(current: usize) -> Self {
+        Self {
+            selected: current,
+            naming: None,
+        }
+    }
+
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    /// The name being picked for a new profile, if any.
+    pub fn get_naming(&self) -> Option<&InitialsEntry> {
+        self.naming.as_ref()
+    }
+
+    /// Whether the menu has a row to create a profile after `names`.
+    pub fn can_create(names: &[String]) -> bool {
+        names.len() < MAX_PROFILES
+    }
+
+    /// Handles a frame of input. `crank_steps` are the letters the crank
+    /// moved past while naming a profile.
+    pub fn update(
+        &mut self,
+        input: &Input,
+        crank_steps: i32,
+        names: &[String],
+    ) -> ProfileMenuUpdate {
+        if let Some(naming) = &mut self.naming {
+            // Ⓑ on the first letter gives up on the new profile
+            if input.pushed.contains(Buttons::B) && naming.get_cursor() == 0 {
+                self.naming = None;
+                return ProfileMenuUpdate::Open;
+            }
+            let InitialsUpdate::Done(letters) = naming.update(input, crank_steps) else {
+                return ProfileMenuUpdate::Open;
+            };
+            self.naming = None;
+            let name = String::from(scores::initials_text(&letters));
+            return match names.iter().position(|n| *n == name) {
+                Some(index) => ProfileMenuUpdate::Switch(index),
+                None => ProfileMenuUpdate::Create(name),
+            };
+        }
+        let rows = names.len() + usize::from(Self::can_create(names));
+        if input.pushed.contains(Buttons::B) {
+            return ProfileMenuUpdate::Closed;
+        }
+        if input.pushed.contains(Buttons::UP) {
+            self.selected = (self.selected + rows - 1) % rows;
+        }
+        if input.pushed.contains(Buttons::DOWN) {
+            self.selected = (self.selected + 1) % rows;
+        }
+        if !input.pushed.contains(Buttons::A) {
+            return ProfileMenuUpdate::Open;
+        }
+        if self.selected < names.len() {
+            ProfileMenuUpdate::Switch(self.selected)
+        } else {
+            self.naming = Some(InitialsEntry::new(DEFAULT_INITIALS));
+            ProfileMenuUpdate::Open
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ProfileMenuUpdate {
+    Open,
+    /// Switch to the `index`-th profile.
+    Switch(usize),
+    /// Create a profile with this name and switch to it.
+    Create(String),
+    Closed,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec, vec::Vec};
+
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| String::from(*n)).collect()
+    }
+
+    #[test]
+    fn valid_names() {
+        for name in ["P1", "ANN", "A", "007"] {
+            assert!(is_valid_name(name), "{}", name);
+        }
+        for name in ["", "ANNA", "ann", "A-B", "A B", "É"] {
+            assert!(!is_valid_name(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn menu_picks_and_leaves() {
+        let names = names(&["P1", "ANN", "BOB"]);
+        let mut menu = ProfileMenu::new(1);
+        let mut update = |buttons| menu.update(&Input::push(buttons), 0, &names);
+        assert_eq!(update(Buttons::DOWN), ProfileMenuUpdate::Open);
+        assert_eq!(update(Buttons::A), ProfileMenuUpdate::Switch(2));
+        assert_eq!(update(Buttons::B), ProfileMenuUpdate::Closed);
+    }
+
+    #[test]
+    fn menu_wraps_around_the_new_row() {
+        let names = names(&["P1", "ANN"]);
+        let mut menu = ProfileMenu::new(0);
+        menu.update(&Input::push(Buttons::UP), 0, &names);
+        assert_eq!(menu.get_selected(), 2);
+        menu.update(&Input::push(Buttons::DOWN), 0, &names);
+        assert_eq!(menu.get_selected(), 0);
+    }
+
+    #[test]
+    fn full_menu_has_no_new_row() {
+        let names: Vec<String> = (0..MAX_PROFILES).map(|i| format!("P{}", i)).collect();
+        assert!(!ProfileMenu::can_create(&names));
+        let mut menu = ProfileMenu::new(0);
+        menu.update(&Input::push(Buttons::UP), 0, &names);
+        assert_eq!(menu.get_selected(), MAX_PROFILES - 1);
+        assert_eq!(
+            menu.update(&Input::push(Buttons::A), 0, &names),
+            ProfileMenuUpdate::Switch(MAX_PROFILES - 1)
+        );
+    }
+
+    #[test]
+    fn new_row_names_a_profile() {
+        let names = names(&["P1"]);
+        let mut menu = ProfileMenu::new(1);
+        assert_eq!(
+            menu.update(&Input::push(Buttons::A), 0, &names),
+            ProfileMenuUpdate::Open
+        );
+        assert!(menu.get_naming().is_some());
+        // Ⓑ after the first letter goes back a letter
+        menu.update(&Input::push(Buttons::A), 1, &names);
+        menu.update(&Input::push(Buttons::B), 0, &names);
+        assert!(menu.get_naming().is_some());
+        let steps = [13, 1, 13];
+        let updates: Vec<_> = steps
+            .iter()
+            .map(|s| menu.update(&Input::push(Buttons::A), *s, &names))
+            .collect();
+        assert_eq!(
+            updates,
+            vec![
+                ProfileMenuUpdate::Open,
+                ProfileMenuUpdate::Open,
+                ProfileMenuUpdate::Create(String::from("OBN")),
+            ]
+        );
+        assert!(menu.get_naming().is_none());
+    }
+
+    #[test]
+    fn naming_an_existing_profile_switches_to_it() {
+        let names = names(&["P1", "ANN", "BOB"]);
+        let mut menu = ProfileMenu::new(3);
+        menu.update(&Input::push(Buttons::A), 0, &names);
+        menu.update(&Input::push(Buttons::A), 1, &names);
+        menu.update(&Input::push(Buttons::A), 14, &names);
+        assert_eq!(
+            menu.update(&Input::push(Buttons::A), 1, &names),
+            ProfileMenuUpdate::Switch(2)
+        );
+    }
+
+    #[test]
+    fn b_on_the_first_letter_gives_up_naming() {
+        let names = names(&["P1"]);
+        let mut menu = ProfileMenu::new(1);
+        menu.update(&Input::push(Buttons::A), 0, &names);
+        assert_eq!(
+            menu.update(&Input::push(Buttons::B), 0, &names),
+            ProfileMenuUpdate::Open
+        );
+        assert!(menu.get_naming().is_none());
+        assert_eq!(menu.get_selected(), 1);
+    }
+}
@@ -0,0 +1,559 @@
+//! Recorded runs.
+//!
+//! A replay is the seed of a run plus the input and frame time of every
+//! update. Since the [`World`] is deterministic, this is all that is needed
+//! to re-simulate the run on any platform. The format is line based text:
+//!
+//! ```text
+//! dino-replay 8
+//! seed 5eed5eed5eed5eed
+//! lives 3
+//! difficulty normal
+//! perks 01
+//! theme themes/desert-night
+//! shape cactus-big-1 25 50 0,0,25,17 4,17,17,33
+//! dino standing 38,0,24,15 18,15,40,4 18,19,36,8 18,27,32,4 22,31,26,4 26,35,23,12
+//! dino ducking 18,17,59,15 28,32,28,15
+//! score 1234
+//! death 3702
+//! f 33 00 20
+//! c 30 8f1c02aa
+//! ```
+//!
+//! `lives` is the number of lives the run started with, 1 if it's missing,
+//! `difficulty` the [`Difficulty`] it was played at, normal if it's missing,
+//! and `perks` the bits of the [`Perks`] it started with. `theme` is the
+//! folder of the theme pack it was played with, and `shape` and `dino` lines
+//! are the obstacle and dino shapes that pack changed, see [`crate::theme`].
+//! A `dino` line has at least one box.
+//! `f <delta ms> <current buttons> <pushed buttons>` is one update of at most
+//! [`MAX_FRAME_MS`], and `c <tick> <checksum>` is a [`World::checksum`] taken
+//! after every [`CHECKPOINT_INTERVAL`]th tick.
+//!
+//! [`Replay::find_forgery`] catches settings the game can't have produced,
+//! which a re-simulation would happily reproduce.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    dino::DinoShape,
+    input::{Buttons, Input},
+    math::Size,
+    obstacle::{ObstacleKind, Shape},
+    settings::{Difficulty, LIVES_OPTIONS},
+    shop::Perks,
+    theme::{parse_f32, parse_rect},
+    world::MAX_FRAME_MS,
+    GameState, World,
+};
+
+const HEADER: &str = "dino-replay";
+pub const REPLAY_VERSION: u32 = 8;
+pub const CHECKPOINT_INTERVAL: u32 = 30;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Frame {
+    pub delta_ms: u32,
+    pub input: Input,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Checkpoint {
+    pub tick: u32,
+    pub checksum: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Replay {
+    pub seed: u64,
+    pub lives: u32,
+    pub difficulty: Difficulty,
+    pub perks: Perks,
+    /// The folder of the theme pack, if the run wasn't played with the
+    /// bundled assets.
+    pub theme: Option<String>,
+    /// Obstacle shapes that differ from the bundled ones.
+    pub shapes: Vec<(ObstacleKind, Shape)>,
+    /// The dino's collision boxes, if they differ from the bundled ones.
+    pub dino_shape: Option<DinoShape>,
+    /// The score the recording device reported.
+    pub score: i32,
+    pub death_tick: Option<u32>,
+    pub frames: Vec<Frame>,
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+/// Where a re-simulation stopped agreeing with the recording.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Divergence {
+    Checkpoint {
+        tick: u32,
+        expected: u32,
+        actual: u32,
+    },
+    /// The simulated dino died while there were still frames left.
+    DiedEarly { tick: u32, remaining_frames: usize },
+    /// No checkpoint was recorded for a tick that should have one.
+    MissingCheckpoint { tick: u32 },
+    /// The recording says the dino died, but it survived every frame.
+    Survived,
+    DeathTick {
+        recorded: Option<u32>,
+        simulated: Option<u32>,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Divergence::Checkpoint {
+                tick,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "checksum mismatch at tick {}: recorded {:08x}, simulated {:08x}",
+                tick, expected, actual
+            ),
+            Divergence::DiedEarly {
+                tick,
+                remaining_frames,
+            } => write!(
+                f,
+                "dino died at tick {} but {} recorded frames follow",
+                tick, remaining_frames
+            ),
+            Divergence::MissingCheckpoint { tick } => {
+                write!(f, "no checkpoint recorded at tick {}", tick)
+            }
+            Divergence::Survived => write!(f, "dino survived every recorded frame"),
+            Divergence::DeathTick {
+                recorded,
+                simulated,
+            } => write!(
+                f,
+                "recorded death tick {:?}, simulated death tick {:?}",
+                recorded, simulated
+            ),
+        }
+    }
+}
+
+/// A setting in a replay that the game can't have produced.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Forgery {
+    /// Not one of [`LIVES_OPTIONS`].
+    Lives(u32),
+    /// A shape that differs from the bundled one, without a theme pack that
+    /// could have changed it.
+    Shape(ObstacleKind),
+    /// Dino boxes that differ from the bundled ones, without a theme pack.
+    DinoShape,
+    /// An update longer than [`MAX_FRAME_MS`].
+    FrameTime { frame: usize, delta_ms: u32 },
+}
+
+impl fmt::Display for Forgery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Forgery::Lives(lives) => write!(f, "{} lives is not an option", lives),
+            Forgery::Shape(kind) => write!(
+                f,
+                "{} shape differs from the bundled one without a theme",
+                kind.get_key()
+            ),
+            Forgery::DinoShape => {
+                write!(f, "dino shape differs from the bundled one without a theme")
+            }
+            Forgery::FrameTime { frame, delta_ms } => write!(
+                f,
+                "frame {} takes {} ms, more than {} ms",
+                frame, delta_ms, MAX_FRAME_MS
+            ),
+        }
+    }
+}
+
+/// The outcome of re-simulating a replay.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Simulation {
+    pub score: i32,
+    pub death_tick: Option<u32>,
+    /// The first divergence found, if any.
+    pub divergence: Option<Divergence>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, lives: u32, difficulty: Difficulty, perks: Perks) -> Self {
+        Self {
+            seed,
+            lives,
+            difficulty,
+            perks,
+            theme: None,
+            shapes: Vec::new(),
+            dino_shape: None,
+            score: 0,
+            death_tick: None,
+            frames: Vec::new(),
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Records an update that has just been applied to `world`.
+    pub fn record(&mut self, input: Input, delta_ms: u32, world: &World) {
+        self.frames.push(Frame { delta_ms, input });
+        let tick = world.get_tick();
+        if tick.is_multiple_of(CHECKPOINT_INTERVAL) {
+            self.checkpoints.push(Checkpoint {
+                tick,
+                checksum: world.checksum(),
+            });
+        }
+        self.score = world.scoreboard.get_score();
+        self.death_tick = world.get_death_tick();
+    }
+
+    /// The first setting the game can't have produced, if any.
+    pub fn find_forgery(&self) -> Option<Forgery> {
+        if !LIVES_OPTIONS.contains(&self.lives) {
+            return Some(Forgery::Lives(self.lives));
+        }
+        if self.theme.is_none() {
+            if let Some((kind, _)) = self.shapes.iter().find(|(k, s)| *s != k.default_shape()) {
+                return Some(Forgery::Shape(*kind));
+            }
+            if self
+                .dino_shape
+                .as_ref()
+                .is_some_and(|s| *s != DinoShape::default())
+            {
+                return Some(Forgery::DinoShape);
+            }
+        }
+        self.frames
+            .iter()
+            .position(|f| f.delta_ms > MAX_FRAME_MS)
+            .map(|frame| Forgery::FrameTime {
+                frame,
+                delta_ms: self.frames[frame].delta_ms,
+            })
+    }
+
+    pub fn simulate(&self) -> Simulation {
+        let mut world = World::new();
+        world.lives.set_max(self.lives);
+        world.set_difficulty(self.difficulty);
+        world.set_perks(self.perks);
+        for (kind, shape) in &self.shapes {
+            world.obstacles.set_shape(*kind, shape.clone());
+        }
+        if let Some(shape) = &self.dino_shape {
+            world.dino.set_shape(shape.clone());
+        }
+        world.start(self.seed);
+        let mut checkpoints = self.checkpoints.iter();
+        let mut divergence = None;
+        for (i, frame) in self.frames.iter().enumerate() {
+            if world.get_state() != GameState::Playing {
+                divergence.get_or_insert(Divergence::DiedEarly {
+                    tick: world.get_tick() - 1,
+                    remaining_frames: self.frames.len() - i,
+                });
+                break;
+            }
+            world.update(frame.input, frame.delta_ms);
+            let tick = world.get_tick();
+            if !tick.is_multiple_of(CHECKPOINT_INTERVAL) {
+                continue;
+            }
+            let actual = world.checksum();
+            match checkpoints.next() {
+                Some(c) if c.tick == tick && c.checksum != actual => {
+                    divergence.get_or_insert(Divergence::Checkpoint {
+                        tick,
+                        expected: c.checksum,
+                        actual,
+                    });
+                }
+                Some(c) if c.tick == tick => {}
+                _ => {
+                    divergence.get_or_insert(Divergence::MissingCheckpoint { tick });
+                }
+            }
+        }
+        let death_tick = world.get_death_tick();
+        if self.death_tick.is_some() && death_tick.is_none() {
+            divergence.get_or_insert(Divergence::Survived);
+        } else if self.death_tick != death_tick {
+            divergence.get_or_insert(Divergence::DeathTick {
+                recorded: self.death_tick,
+                simulated: death_tick,
+            });
+        }
+        Simulation {
+            score: world.scoreboard.get_score(),
+            death_tick,
+            divergence,
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut lines = text.lines().enumerate().map(|(i, l)| (i + 1, l.trim()));
+        let error = |line, message| ParseError { line, message };
+        let version = lines
+            .next()
+            .and_then(|(_, header)| header.strip_prefix(HEADER))
+            .ok_or(error(1, "not a dino replay"))?;
+        if version.trim().parse() != Ok(REPLAY_VERSION) {
+            return Err(error(1, "unsupported replay version"));
+        }
+        let mut replay = Replay::new(0, 1, Difficulty::Normal, Perks::NONE);
+        for (line, text) in lines {
+            let mut words = text.split_whitespace();
+            let Some(tag) = words.next() else {
+                continue;
+            };
+            let mut next = || words.next().ok_or(error(line, "missing field"));
+            match tag {
+                "seed" => {
+                    replay.seed = u64::from_str_radix(next()?, 16)
+                        .map_err(|_| error(line, "invalid seed"))?;
+                }
+                "lives" => {
+                    replay.lives = next()?.parse().map_err(|_| error(line, "invalid lives"))?;
+                }
+                "difficulty" => {
+                    replay.difficulty =
+                        Difficulty::from_key(next()?).ok_or(error(line, "unknown difficulty"))?;
+                }
+                "perks" => {
+                    let bits = u8::from_str_radix(next()?, 16)
+                        .map_err(|_| error(line, "invalid perks"))?;
+                    replay.perks = Perks::from_bits(bits);
+                    if replay.perks.bits() != bits {
+                        return Err(error(line, "unknown perks"));
+                    }
+                }
+                "theme" => {
+                    // folder names may have spaces
+                    let dir = text[tag.len()..].trim();
+                    if dir.is_empty() {
+                        return Err(error(line, "missing field"));
+                    }
+                    replay.theme = Some(String::from(dir));
+                }
+                "shape" => {
+                    let kind =
+                        ObstacleKind::from_key(next()?).ok_or(error(line, "unknown obstacle"))?;
+                    let width = parse_f32(next()?).ok_or(error(line, "invalid size"))?;
+                    let height = parse_f32(next()?).ok_or(error(line, "invalid size"))?;
+                    let hitboxes = words
+                        .map(parse_rect)
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or(error(line, "invalid hitbox"))?;
+                    let size = Size::new(width, height);
+                    replay.shapes.push((kind, Shape { size, hitboxes }));
+                }
+                "dino" => {
+                    let pose = next()?;
+                    let hitboxes = words
+                        .map(parse_rect)
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or(error(line, "invalid hitbox"))?;
+                    // a dino without boxes can't be hit
+                    if hitboxes.is_empty() {
+                        return Err(error(line, "missing hitbox"));
+                    }
+                    let shape = replay.dino_shape.get_or_insert_with(DinoShape::default);
+                    match pose {
+                        "standing" => shape.standing = hitboxes,
+                        "ducking" => shape.ducking = hitboxes,
+                        _ => return Err(error(line, "unknown pose")),
+                    }
+                }
+                "score" => {
+                    replay.score = next()?.parse().map_err(|_| error(line, "invalid score"))?;
+                }
+                "death" => {
+                    replay.death_tick = match next()? {
+                        "-" => None,
+                        t => Some(t.parse().map_err(|_| error(line, "invalid death tick"))?),
+                    };
+                }
+                "f" => {
+                    let delta_ms = next()?
+                        .parse()
+                        .map_err(|_| error(line, "invalid frame time"))?;
+                    let current = parse_buttons(next()?).ok_or(error(line, "invalid buttons"))?;
+                    let pushed = parse_buttons(next()?).ok_or(error(line, "invalid buttons"))?;
+                    replay.frames.push(Frame {
+                        delta_ms,
+                        input: Input { current, pushed },
+                    });
+                }
+                "c" => {
+                    let tick = next()?.parse().map_err(|_| error(line, "invalid tick"))?;
+                    let checksum = u32::from_str_radix(next()?, 16)
+                        .map_err(|_| error(line, "invalid checksum"))?;
+                    replay.checkpoints.push(Checkpoint { tick, checksum });
+                }
+                _ => return Err(error(line, "unknown entry")),
+            }
+        }
+        Ok(replay)
+    }
+}
+
+fn parse_buttons(s: &str) -> Option<Buttons> {
+    u8::from_str_radix(s, 16).ok().map(Buttons::from_bits)
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", HEADER, REPLAY_VERSION)?;
+        writeln!(f, "seed {:016x}", self.seed)?;
+        writeln!(f, "lives {}", self.lives)?;
+        writeln!(f, "difficulty {}", self.difficulty.get_key())?;
+        writeln!(f, "perks {:02x}", self.perks.bits())?;
+        if let Some(theme) = &self.theme {
+            writeln!(f, "theme {}", theme)?;
+        }
+        for (kind, shape) in &self.shapes {
+            write!(
+                f,
+                "shape {} {} {}",
+                kind.get_key(),
+                shape.size.width,
+                shape.size.height
+            )?;
+            for b in &shape.hitboxes {
+                write!(f, " {},{},{},{}", b.x, b.y, b.width, b.height)?;
+            }
+            writeln!(f)?;
+        }
+        if let Some(shape) = &self.dino_shape {
+            for (pose, hitboxes) in [("standing", &shape.standing), ("ducking", &shape.ducking)] {
+                write!(f, "dino {}", pose)?;
+                for b in hitboxes {
+                    write!(f, " {},{},{},{}", b.x, b.y, b.width, b.height)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        writeln!(f, "score {}", self.score)?;
+        match self.death_tick {
+            Some(tick) => writeln!(f, "death {}", tick)?,
+            None => writeln!(f, "death -")?,
+        }
+        let mut checkpoints = self.checkpoints.iter().peekable();
+        for (i, frame) in self.frames.iter().enumerate() {
+            writeln!(
+                f,
+                "f {} {:02x} {:02x}",
+                frame.delta_ms,
+                frame.input.current.bits(),
+                frame.input.pushed.bits()
+            )?;
+            while let Some(c) = checkpoints.next_if(|c| c.tick as usize <= i + 1) {
+                writeln!(f, "c {} {:08x}", c.tick, c.checksum)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString};
+
+    use super::*;
+
+    /// Records a run that jumps every second until the dino dies.
+    fn record_run(seed: u64) -> Replay {
+        let mut world = World::new();
+        world.start(seed);
+        let mut replay = Replay::new(seed, 1, Difficulty::Normal, Perks::NONE);
+        while world.get_state() == GameState::Playing {
+            let input = if world.get_tick().is_multiple_of(30) {
+                Input::push(Buttons::A)
+            } else {
+                Input::default()
+            };
+            world.update(input, 33);
+            replay.record(input, 33, &world);
+        }
+        replay
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let replay = record_run(7);
+        let text = replay.to_string();
+        let parsed = Replay::parse(&text).unwrap();
+        assert_eq!(parsed.seed, replay.seed);
+        assert_eq!(parsed.score, replay.score);
+        assert_eq!(parsed.death_tick, replay.death_tick);
+        assert_eq!(parsed.frames, replay.frames);
+        assert_eq!(parsed.checkpoints, replay.checkpoints);
+        assert_eq!(parsed.to_string(), text);
+    }
+
+    #[test]
+    fn recorded_run_verifies() {
+        let replay = Replay::parse(&record_run(7).to_string()).unwrap();
+        assert!(replay.death_tick.is_some());
+        let simulation = replay.simulate();
+        assert_eq!(simulation.divergence, None);
+        assert_eq!(simulation.score, replay.score);
+        assert_eq!(simulation.death_tick, replay.death_tick);
+    }
+
+    #[test]
+    fn long_frames_are_recorded_in_steps() {
+        let mut world = World::new();
+        world.start(7);
+        let mut replay = Replay::new(7, 1, Difficulty::Normal, Perks::NONE);
+        replay.theme = Some(String::from("themes/night sky"));
+        while world.get_state() == GameState::Playing {
+            world.advance(Input::push(Buttons::A), 120, |input, step_ms, world| {
+                replay.record(input, step_ms, world)
+            });
+        }
+        assert!(replay.frames.iter().all(|f| f.delta_ms <= MAX_FRAME_MS));
+        assert_eq!(replay.frames[1].input.pushed, Buttons::NONE);
+        assert_eq!(replay.find_forgery(), None);
+        let parsed = Replay::parse(&replay.to_string()).unwrap();
+        assert_eq!(parsed.theme, replay.theme);
+        assert_eq!(parsed.simulate().divergence, None);
+    }
+
+    #[test]
+    fn tampered_seed_diverges() {
+        let mut replay = record_run(7);
+        replay.seed = 8;
+        assert!(replay.simulate().divergence.is_some());
+    }
+
+    #[test]
+    fn unknown_version_is_rejected() {
+        let header = format!("{} {}", HEADER, REPLAY_VERSION);
+        let text = record_run(7)
+            .to_string()
+            .replacen(&header, "dino-replay 99", 1);
+        assert_eq!(Replay::parse(&text).unwrap_err().line, 1);
+    }
+}
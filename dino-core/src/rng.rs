@@ -0,0 +1,44 @@
+use core::ops::Range;
+
+/// A small PCG32 generator.
+///
+/// Replays only work if every platform draws the same numbers for a given
+/// seed, so the simulation never touches the system RNG.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    const MULTIPLIER: u64 = 6364136223846793005;
+    const INCREMENT: u64 = 1442695040888963407;
+
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Self { state: 0 };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old
+            .wrapping_mul(Self::MULTIPLIER)
+            .wrapping_add(Self::INCREMENT);
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xorshifted.rotate_right(rot)
+    }
+
+    /// Uniform index in `0..len`.
+    pub fn gen_index(&mut self, len: usize) -> usize {
+        ((self.next_u32() as u64 * len as u64) >> 32) as usize
+    }
+
+    /// Uniform float in `range`.
+    pub fn gen_f32(&mut self, range: Range<f32>) -> f32 {
+        let unit = (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32;
+        range.start + (range.end - range.start) * unit
+    }
+}
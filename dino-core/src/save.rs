@@ -0,0 +1,358 @@
+//! The save file.
+//!
+//! Progress is kept as line based text, like replays. The first line names
+//! the format version and the last one is a checksum of everything before
+//! it, so a truncated or hand-edited save is rejected instead of misread:
+//!
+//! ```text
+//! dino-save 1
+//! record 1234
+//! initials ABC
+//! score normal-1 1234 ABC 845123456 5eed5eed5eed5eed 61234
+//! stat runs 42
+//! stat cleared bird 17
+//! setting volume 7
+//! setting theme packs/night
+//! checksum 8f1c02aa
+//! ```
+//!
+//! `score <mode> <score> <initials> <date> <seed> <duration ms>` lines fill
+//! the [`HighScores`], and `initials` are the ones entered last. `stat <key>
+//! <value>` and `stat <cleared|deaths> <obstacle> <count>` lines hold the
+//! lifetime [`Stats`]. `setting <key> <value>` lines hold the [`Settings`],
+//! where the value is the rest of the line.
+//!
+//! A save of another version is rejected. When the save is damaged,
+//! [`Save::recover`] falls back to the newest intact backup. The only older
+//! file is the `record` the first release kept, see
+//! [`Save::from_legacy_record`].
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use crate::{
+    replay::ParseError,
+    scores::{self, HighScores, DEFAULT_INITIALS, INITIALS_LEN},
+    settings::Settings,
+    stats::Stats,
+    world::Fnv,
+};
+
+const HEADER: &str = "dino-save";
+const CHECKSUM: &str = "checksum";
+pub const SAVE_VERSION: u32 = 1;
+pub const SAVE_FILE: &str = "save";
+/// Where a save is written before it is renamed over [`SAVE_FILE`].
+pub const TEMP_FILE: &str = "save.tmp";
+/// The saves before the current one, newest first.
+pub const BACKUP_FILES: [&str; 2] = ["save.1", "save.2"];
+
+/// How a damaged save was recovered.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Recovery {
+    /// From a backup, which may miss the last runs.
+    Backup,
+    /// No backup was readable either, so progress starts over.
+    Reset,
+}
+
+/// What [`Save::recover`] found in the save files.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Recovered {
+    /// The newest intact save and the file it was read from.
+    pub save: Option<(&'static str, Save)>,
+    pub recovery: Option<Recovery>,
+    /// The files that exist but couldn't be read, newest first.
+    pub errors: Vec<(&'static str, ParseError)>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Save {
+    /// The best score so far, in any mode.
+    pub record: i32,
+    pub high_scores: HighScores,
+    /// Offered for the next high score.
+    pub initials: [u8; INITIALS_LEN],
+    pub stats: Stats,
+    pub settings: Settings,
+}
+
+impl Default for Save {
+    fn default() -> Self {
+        Self {
+            record: 0,
+            high_scores: HighScores::default(),
+            initials: DEFAULT_INITIALS,
+            stats: Stats::default(),
+            settings: Settings::default(),
+        }
+    }
+}
+
+fn checksum(body: &str) -> u32 {
+    let mut hash = Fnv::new();
+    hash.write_bytes(body.as_bytes());
+    hash.finish()
+}
+
+impl Save {
+    /// Reads the `record` file older versions of the game kept, which held
+    /// the record and nothing else.
+    pub fn from_legacy_record(text: &str) -> Option<Self> {
+        let record = text.trim().parse().ok()?;
+        Some(Self {
+            record,
+            ..Self::default()
+        })
+    }
+
+    /// Reads the newest intact save among [`SAVE_FILE`], [`TEMP_FILE`] and
+    /// the [`BACKUP_FILES`]. `read` returns a file's text, or `None` if it
+    /// doesn't exist. Without any save file, the returned save is `None`.
+    pub fn recover(mut read: impl FnMut(&'static str) -> Option<String>) -> Recovered {
+        let mut recovered = Recovered {
+            save: None,
+            recovery: None,
+            errors: Vec::new(),
+        };
+        let mut damaged = false;
+        for name in [SAVE_FILE, TEMP_FILE].into_iter().chain(BACKUP_FILES) {
+            let Some(text) = read(name) else {
+                continue;
+            };
+            match Save::parse(&text) {
+                Ok(save) => {
+                    recovered.save = Some((name, save));
+                    if damaged {
+                        recovered.recovery = Some(Recovery::Backup);
+                    }
+                    return recovered;
+                }
+                Err(e) => {
+                    // a crash mid-write leaves a partial temp file behind
+                    damaged |= name != TEMP_FILE;
+                    recovered.errors.push((name, e));
+                }
+            }
+        }
+        if damaged {
+            recovered.recovery = Some(Recovery::Reset);
+        }
+        recovered
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let error = |line, message| ParseError { line, message };
+        // the checksum covers every byte up to its own line
+        let body_len = text.trim_end().rfind('\n').map_or(0, |i| i + 1);
+        let (body, last) = text.split_at(body_len);
+        let lines = body.lines().count();
+        let expected = last
+            .trim()
+            .strip_prefix(CHECKSUM)
+            .ok_or(error(lines + 1, "missing checksum"))?;
+        let expected = u32::from_str_radix(expected.trim(), 16)
+            .map_err(|_| error(lines + 1, "invalid checksum"))?;
+        if checksum(body) != expected {
+            return Err(error(lines + 1, "checksum mismatch"));
+        }
+        let mut lines = body.lines().enumerate().map(|(i, l)| (i + 1, l.trim()));
+        let version = lines
+            .next()
+            .and_then(|(_, header)| header.strip_prefix(HEADER))
+            .ok_or(error(1, "not a dino save"))?;
+        if version.trim().parse() != Ok(SAVE_VERSION) {
+            return Err(error(1, "unsupported save version"));
+        }
+        let mut save = Save::default();
+        for (line, text) in lines {
+            let mut words = text.split_whitespace();
+            let Some(tag) = words.next() else {
+                continue;
+            };
+            let mut next = || words.next().ok_or(error(line, "missing field"));
+            match tag {
+                "record" => {
+                    save.record = next()?.parse().map_err(|_| error(line, "invalid record"))?;
+                }
+                "initials" => {
+                    save.initials =
+                        scores::parse_initials(next()?).ok_or(error(line, "invalid initials"))?;
+                }
+                "score" => {
+                    save.high_scores
+                        .parse_entry(&text[tag.len()..])
+                        .map_err(|message| error(line, message))?;
+                }
+                "stat" => {
+                    save.stats
+                        .parse_entry(&text[tag.len()..])
+                        .map_err(|message| error(line, message))?;
+                }
+                "setting" => {
+                    let key = next()?;
+                    let value = text[tag.len()..].trim_start()[key.len()..].trim();
+                    save.settings
+                        .parse_entry(key, value)
+                        .map_err(|message| error(line, message))?;
+                }
+                _ => return Err(error(line, "unknown entry")),
+            }
+        }
+        Ok(save)
+    }
+}
+
+impl fmt::Display for Save {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut body = String::new();
+        writeln!(body, "{} {}", HEADER, SAVE_VERSION)?;
+        writeln!(body, "record {}", self.record)?;
+        writeln!(body, "initials {}", scores::initials_text(&self.initials))?;
+        write!(body, "{}", self.high_scores)?;
+        write!(body, "{}", self.stats)?;
+        write!(body, "{}", self.settings)?;
+        write!(f, "{}", body)?;
+        writeln!(f, "{} {:08x}", CHECKSUM, checksum(&body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, format, string::ToString};
+
+    use super::*;
+
+    /// Ends `body` with its checksum, the way a valid save does.
+    fn sign(body: &str) -> String {
+        format!("{}{} {:08x}\n", body, CHECKSUM, checksum(body))
+    }
+
+    fn example() -> Save {
+        let mut save = Save {
+            record: 1234,
+            initials: *b"ABC",
+            ..Save::default()
+        };
+        save.stats.runs = 3;
+        save
+    }
+
+    #[test]
+    fn round_trip() {
+        let save = example();
+        assert_eq!(Save::parse(&save.to_string()), Ok(save));
+    }
+
+    #[test]
+    fn truncated_save_is_rejected() {
+        let text = example().to_string();
+        let cut = text.find("initials").unwrap();
+        assert!(Save::parse(&text[..cut]).is_err());
+        assert!(Save::parse("").is_err());
+    }
+
+    #[test]
+    fn edited_checksum_is_rejected() {
+        let text = example().to_string();
+        let (body, _) = text.split_at(text.find(CHECKSUM).unwrap());
+        let edited = format!("{}{} {:08x}\n", body, CHECKSUM, checksum(body) ^ 1);
+        assert_eq!(
+            Save::parse(&edited).unwrap_err().message,
+            "checksum mismatch"
+        );
+    }
+
+    #[test]
+    fn edited_body_is_rejected() {
+        let text = example().to_string().replace("record 1234", "record 9999");
+        assert_eq!(Save::parse(&text).unwrap_err().message, "checksum mismatch");
+    }
+
+    #[test]
+    fn unknown_version_is_rejected() {
+        for version in [0, SAVE_VERSION + 1] {
+            let text = sign(&format!("{} {}\nrecord 1234\n", HEADER, version));
+            let error = Save::parse(&text).unwrap_err();
+            assert_eq!(error.line, 1);
+            assert_eq!(error.message, "unsupported save version");
+        }
+    }
+
+    #[test]
+    fn legacy_record() {
+        let save = Save::from_legacy_record("1234\n").unwrap();
+        assert_eq!(save.record, 1234);
+        assert_eq!(save.high_scores, HighScores::default());
+        assert_eq!(Save::from_legacy_record(" 0 "), Some(Save::default()));
+        assert_eq!(Save::from_legacy_record("12a"), None);
+        assert_eq!(Save::from_legacy_record(""), None);
+    }
+
+    fn recover(files: &[(&str, String)]) -> Recovered {
+        let files: BTreeMap<&str, String> = files.iter().cloned().collect();
+        Save::recover(|name| files.get(name).cloned())
+    }
+
+    #[test]
+    fn damaged_save_falls_back_to_the_first_backup() {
+        let mut older = example();
+        older.record = 1000;
+        let recovered = recover(&[
+            (SAVE_FILE, String::from("dino-save 1\nrecord 12")),
+            (BACKUP_FILES[0], example().to_string()),
+            (BACKUP_FILES[1], older.to_string()),
+        ]);
+        assert_eq!(recovered.save, Some((BACKUP_FILES[0], example())));
+        assert_eq!(recovered.recovery, Some(Recovery::Backup));
+        assert_eq!(recovered.errors.len(), 1);
+        assert_eq!(recovered.errors[0].0, SAVE_FILE);
+    }
+
+    #[test]
+    fn damaged_backups_are_skipped() {
+        let recovered = recover(&[
+            (SAVE_FILE, String::from("garbage")),
+            (BACKUP_FILES[0], String::from("garbage")),
+            (BACKUP_FILES[1], example().to_string()),
+        ]);
+        assert_eq!(recovered.save, Some((BACKUP_FILES[1], example())));
+        assert_eq!(recovered.recovery, Some(Recovery::Backup));
+    }
+
+    #[test]
+    fn nothing_intact_resets() {
+        let recovered = recover(&[
+            (SAVE_FILE, String::from("garbage")),
+            (BACKUP_FILES[0], String::from("garbage")),
+        ]);
+        assert_eq!(recovered.save, None);
+        assert_eq!(recovered.recovery, Some(Recovery::Reset));
+        assert_eq!(recovered.errors.len(), 2);
+    }
+
+    #[test]
+    fn partial_temp_file_is_not_damage() {
+        let recovered = recover(&[(TEMP_FILE, String::from("dino-save 1\nrec"))]);
+        assert_eq!(recovered.save, None);
+        assert_eq!(recovered.recovery, None);
+        let recovered = recover(&[
+            (SAVE_FILE, example().to_string()),
+            (TEMP_FILE, String::from("dino-save 1\nrec")),
+        ]);
+        assert_eq!(recovered.save, Some((SAVE_FILE, example())));
+        assert_eq!(recovered.recovery, None);
+    }
+
+    #[test]
+    fn no_files_is_a_new_player() {
+        assert_eq!(
+            recover(&[]),
+            Recovered {
+                save: None,
+                recovery: None,
+                errors: Vec::new(),
+            }
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use alloc::vec::Vec;
+
+use crate::{Event, GameState};
+
+pub struct Scoreboard {
+    accumulated_time: f32,
+}
+
+impl Scoreboard {
+    pub fn new() -> Self {
+        Self {
+            accumulated_time: 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.accumulated_time = 0.0;
+    }
+
+    pub fn get_score(&self) -> i32 {
+        (self.accumulated_time * 10.0) as i32
+    }
+
+    /// Skips ahead, or back, to `score`.
+    #[cfg(feature = "cheats")]
+    pub fn set_score(&mut self, score: i32) {
+        self.accumulated_time = score as f32 / 10.0;
+    }
+
+    /// Seconds survived in the current run.
+    pub fn get_time(&self) -> f32 {
+        self.accumulated_time
+    }
+
+    pub fn update(&mut self, game_state: GameState, delta: f32, events: &mut Vec<Event>) {
+        if game_state == GameState::Playing {
+            self.accumulated_time += delta;
+            let score = self.get_score();
+            if score > 0 && score % 100 == 0 {
+                events.push(Event::Achievement(score));
+            }
+        }
+    }
+}
+
+impl Default for Scoreboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,467 @@
+//! The local high score tables, one per mode, and the screens to enter
+//! initials and browse them.
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    input::{Buttons, Input},
+    settings::{Difficulty, Settings, LIVES_OPTIONS},
+    World,
+};
+
+pub const TABLE_SIZE: usize = 10;
+pub const INITIALS_LEN: usize = 3;
+pub const DEFAULT_INITIALS: [u8; INITIALS_LEN] = *b"AAA";
+
+/// The settings that change how far a run gets, so their scores are ranked
+/// apart.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Mode {
+    pub difficulty: Difficulty,
+    pub lives: u32,
+}
+
+impl Mode {
+    /// The mode the next run is played in.
+    pub fn of(settings: &Settings) -> Self {
+        Self {
+            difficulty: settings.difficulty,
+            lives: settings.lives,
+        }
+    }
+
+    /// The mode the current run is played in, which settings changed during
+    /// it don't affect.
+    pub fn of_run(world: &World) -> Self {
+        Self {
+            difficulty: world.get_difficulty(),
+            lives: world.lives.get_max(),
+        }
+    }
+
+    /// Every mode, in the order the tables are browsed.
+    pub fn all() -> impl Iterator<Item = Mode> {
+        Difficulty::ALL.into_iter().flat_map(|difficulty| {
+            LIVES_OPTIONS
+                .into_iter()
+                .map(move |lives| Mode { difficulty, lives })
+        })
+    }
+
+    pub fn get_key(self) -> String {
+        format!("{}-{}", self.difficulty.get_key(), self.lives)
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        let (difficulty, lives) = key.rsplit_once('-')?;
+        let mode = Mode {
+            difficulty: Difficulty::from_key(difficulty)?,
+            lives: lives.parse().ok()?,
+        };
+        LIVES_OPTIONS.contains(&mode.lives).then_some(mode)
+    }
+
+    pub fn get_name(self) -> String {
+        let lives = if self.lives == 1 { "life" } else { "lives" };
+        format!("{}, {} {}", self.difficulty.get_name(), self.lives, lives)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Entry {
+    pub score: i32,
+    /// Capital letters.
+    pub initials: [u8; INITIALS_LEN],
+    /// Seconds since 2000-01-01, the Playdate's epoch.
+    pub date: u32,
+    pub seed: u64,
+    pub duration_ms: u32,
+}
+
+impl Entry {
+    pub fn get_initials(&self) -> &str {
+        initials_text(&self.initials)
+    }
+
+    /// Reads the fields of a `score` entry of the save file, after the mode.
+    pub fn parse(words: &mut dyn Iterator<Item = &str>) -> Result<Self, &'static str> {
+        let mut next = || words.next().ok_or("missing field");
+        let score = next()?.parse().map_err(|_| "invalid score")?;
+        let initials = parse_initials(next()?).ok_or("invalid initials")?;
+        let date = next()?.parse().map_err(|_| "invalid date")?;
+        let seed = u64::from_str_radix(next()?, 16).map_err(|_| "invalid seed")?;
+        let duration_ms = next()?.parse().map_err(|_| "invalid duration")?;
+        Ok(Self {
+            score,
+            initials,
+            date,
+            seed,
+            duration_ms,
+        })
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {:016x} {}",
+            self.score,
+            self.get_initials(),
+            self.date,
+            self.seed,
+            self.duration_ms
+        )
+    }
+}
+
+pub fn initials_text(initials: &[u8; INITIALS_LEN]) -> &str {
+    core::str::from_utf8(initials).unwrap_or("???")
+}
+
+pub fn parse_initials(text: &str) -> Option<[u8; INITIALS_LEN]> {
+    let initials: [u8; INITIALS_LEN] = text.as_bytes().try_into().ok()?;
+    initials
+        .iter()
+        .all(u8::is_ascii_uppercase)
+        .then_some(initials)
+}
+
+/// `date` as `YYYY-MM-DD`, see [`Entry::date`].
+pub fn format_date(date: u32) -> String {
+    // days since 0000-03-01, so leap days end the year
+    let days = date as i64 / 86_400 + 730_425;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// The best [`TABLE_SIZE`] runs of every mode, best first.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct HighScores {
+    tables: Vec<(Mode, Vec<Entry>)>,
+}
+
+impl HighScores {
+    pub fn get(&self, mode: Mode) -> &[Entry] {
+        self.tables
+            .iter()
+            .find(|(m, _)| *m == mode)
+            .map_or(&[], |(_, entries)| entries.as_slice())
+    }
+
+    /// Whether a run with `score` would make it into the table.
+    pub fn qualifies(&self, mode: Mode, score: i32) -> bool {
+        let entries = self.get(mode);
+        score > 0 && (entries.len() < TABLE_SIZE || entries.iter().any(|e| score > e.score))
+    }
+
+    /// Adds `entry` below the runs with the same score and returns its rank
+    /// from 0, or `None` if it didn't make it.
+    pub fn insert(&mut self, mode: Mode, entry: Entry) -> Option<usize> {
+        if !self.qualifies(mode, entry.score) {
+            return None;
+        }
+        let index = match self.tables.iter().position(|(m, _)| *m == mode) {
+            Some(index) => index,
+            None => {
+                self.tables.push((mode, Vec::new()));
+                self.tables.len() - 1
+            }
+        };
+        let entries = &mut self.tables[index].1;
+        let rank = entries
+            .iter()
+            .position(|e| entry.score > e.score)
+            .unwrap_or(entries.len());
+        entries.insert(rank, entry);
+        entries.truncate(TABLE_SIZE);
+        Some(rank)
+    }
+
+    /// Reads one `score <mode> ...` entry of the save file, keeping the
+    /// table sorted even if the file wasn't.
+    pub fn parse_entry(&mut self, text: &str) -> Result<(), &'static str> {
+        let mut words = text.split_whitespace();
+        let mode = Mode::from_key(words.next().ok_or("missing field")?).ok_or("unknown mode")?;
+        let entry = Entry::parse(&mut words)?;
+        self.insert(mode, entry);
+        Ok(())
+    }
+}
+
+/// The save file's entries for the tables.
+impl fmt::Display for HighScores {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for mode in Mode::all() {
+            for entry in self.get(mode) {
+                writeln!(f, "score {} {}", mode.get_key(), entry)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Picking initials for a new high score: Up, Down and the crank change the
+/// letter, Left and Right move between letters, Ⓐ moves on and Ⓑ goes back.
+pub struct InitialsEntry {
+    letters: [u8; INITIALS_LEN],
+    cursor: usize,
+}
+
+impl InitialsEntry {
+    /// Starts from the initials entered last time.
+    pub fn new(initials: [u8; INITIALS_LEN]) -> Self {
+        Self {
+            letters: initials,
+            cursor: 0,
+        }
+    }
+
+    pub fn get_letters(&self) -> [u8; INITIALS_LEN] {
+        self.letters
+    }
+
+    pub fn get_cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Handles a frame of input. `crank_steps` are the letters the crank
+    /// moved past since the last frame.
+    pub fn update(&mut self, input: &Input, crank_steps: i32) -> InitialsUpdate {
+        let mut step = crank_steps;
+        if input.pushed.contains(Buttons::UP) {
+            step += 1;
+        }
+        if input.pushed.contains(Buttons::DOWN) {
+            step -= 1;
+        }
+        let letter = &mut self.letters[self.cursor];
+        *letter = b'A' + (*letter as i32 - b'A' as i32 + step).rem_euclid(26) as u8;
+        if input.pushed.contains(Buttons::LEFT) || input.pushed.contains(Buttons::B) {
+            self.cursor = self.cursor.saturating_sub(1);
+        }
+        if input.pushed.contains(Buttons::RIGHT) {
+            self.cursor = (self.cursor + 1).min(INITIALS_LEN - 1);
+        }
+        if input.pushed.contains(Buttons::A) {
+            if self.cursor == INITIALS_LEN - 1 {
+                return InitialsUpdate::Done(self.letters);
+            }
+            self.cursor += 1;
+        }
+        InitialsUpdate::Editing
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InitialsUpdate {
+    Editing,
+    Done([u8; INITIALS_LEN]),
+}
+
+/// Browsing the tables: Left and Right switch modes, Down moves on to the
+/// stats and Ⓐ or Ⓑ leave.
+pub struct ScoreBrowser {
+    mode: Mode,
+    /// The rank of the run just entered, if the browser shows it.
+    highlight: Option<usize>,
+}
+
+impl ScoreBrowser {
+    pub fn new(mode: Mode, highlight: Option<usize>) -> Self {
+        Self { mode, highlight }
+    }
+
+    pub fn get_mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn get_highlight(&self) -> Option<usize> {
+        self.highlight
+    }
+
+    /// Handles a frame of input.
+    pub fn update(&mut self, input: &Input) -> ScoreBrowserUpdate {
+        if input.pushed.contains(Buttons::A) || input.pushed.contains(Buttons::B) {
+            return ScoreBrowserUpdate::Closed;
+        }
+        if input.pushed.contains(Buttons::DOWN) {
+            return ScoreBrowserUpdate::Stats;
+        }
+        let step = if input.pushed.contains(Buttons::LEFT) {
+            -1
+        } else if input.pushed.contains(Buttons::RIGHT) {
+            1
+        } else {
+            return ScoreBrowserUpdate::Open;
+        };
+        let modes: Vec<Mode> = Mode::all().collect();
+        let index = modes.iter().position(|m| *m == self.mode).unwrap_or(0) as i32;
+        self.mode = modes[(index + step).rem_euclid(modes.len() as i32) as usize];
+        self.highlight = None;
+        ScoreBrowserUpdate::Open
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScoreBrowserUpdate {
+    Open,
+    /// Leave for the stats screen.
+    Stats,
+    Closed,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    const NORMAL_3: Mode = Mode {
+        difficulty: Difficulty::Normal,
+        lives: 3,
+    };
+
+    fn entry(score: i32, initials: &[u8; INITIALS_LEN]) -> Entry {
+        Entry {
+            score,
+            initials: *initials,
+            date: 845_683_200,
+            seed: 7,
+            duration_ms: score as u32 * 50,
+        }
+    }
+
+    #[test]
+    fn insert_ranks_ties_below_earlier_runs() {
+        let mut high_scores = HighScores::default();
+        assert_eq!(high_scores.insert(NORMAL_3, entry(420, b"AAA")), Some(0));
+        assert_eq!(high_scores.insert(NORMAL_3, entry(1234, b"BBB")), Some(0));
+        assert_eq!(high_scores.insert(NORMAL_3, entry(77, b"CCC")), Some(2));
+        assert_eq!(high_scores.insert(NORMAL_3, entry(1234, b"DDD")), Some(1));
+        let initials: Vec<&str> = high_scores
+            .get(NORMAL_3)
+            .iter()
+            .map(Entry::get_initials)
+            .collect();
+        assert_eq!(initials, ["BBB", "DDD", "AAA", "CCC"]);
+    }
+
+    #[test]
+    fn tables_keep_the_best_runs() {
+        let mut high_scores = HighScores::default();
+        for score in 1..=TABLE_SIZE as i32 {
+            high_scores.insert(NORMAL_3, entry(score * 100, b"AAA"));
+        }
+        assert!(!high_scores.qualifies(NORMAL_3, 100));
+        assert_eq!(high_scores.insert(NORMAL_3, entry(100, b"BBB")), None);
+        assert_eq!(
+            high_scores.insert(NORMAL_3, entry(150, b"BBB")),
+            Some(TABLE_SIZE - 1)
+        );
+        let table = high_scores.get(NORMAL_3);
+        assert_eq!(table.len(), TABLE_SIZE);
+        assert_eq!(table[TABLE_SIZE - 1].score, 150);
+    }
+
+    #[test]
+    fn modes_are_ranked_apart() {
+        let mut high_scores = HighScores::default();
+        let hard_1 = Mode {
+            difficulty: Difficulty::Hard,
+            lives: 1,
+        };
+        high_scores.insert(NORMAL_3, entry(1000, b"AAA"));
+        assert_eq!(high_scores.insert(hard_1, entry(10, b"BBB")), Some(0));
+        assert_eq!(high_scores.get(NORMAL_3).len(), 1);
+        assert!(!high_scores.qualifies(hard_1, 0));
+        assert!(high_scores
+            .get(Mode {
+                difficulty: Difficulty::Easy,
+                lives: 5,
+            })
+            .is_empty());
+    }
+
+    #[test]
+    fn save_entries_round_trip() {
+        let mut high_scores = HighScores::default();
+        high_scores.insert(NORMAL_3, entry(420, b"ABC"));
+        high_scores.insert(NORMAL_3, entry(980, b"XYZ"));
+        let mut parsed = HighScores::default();
+        for line in high_scores.to_string().lines() {
+            parsed
+                .parse_entry(line.strip_prefix("score").unwrap())
+                .unwrap();
+        }
+        assert_eq!(parsed, high_scores);
+        assert_eq!(
+            parsed.parse_entry(" normal-2 10 ABC 0 7 0"),
+            Err("unknown mode")
+        );
+        assert_eq!(
+            parsed.parse_entry(" normal-3 10 abc 0 7 0"),
+            Err("invalid initials")
+        );
+    }
+
+    #[test]
+    fn initials_entry_edits_letters() {
+        let mut entry = InitialsEntry::new(*b"AAA");
+        let mut update = |buttons, crank_steps| entry.update(&Input::push(buttons), crank_steps);
+        assert_eq!(update(Buttons::DOWN, 0), InitialsUpdate::Editing);
+        update(Buttons::A, 0);
+        update(Buttons::NONE, 2);
+        update(Buttons::A, 0);
+        assert_eq!(update(Buttons::A, 0), InitialsUpdate::Done(*b"ZCA"));
+    }
+
+    #[test]
+    fn initials_entry_moves_between_letters() {
+        let mut entry = InitialsEntry::new(*b"ABC");
+        entry.update(&Input::push(Buttons::B), 0);
+        assert_eq!(entry.get_cursor(), 0);
+        for _ in 0..INITIALS_LEN + 1 {
+            entry.update(&Input::push(Buttons::RIGHT), 0);
+        }
+        assert_eq!(entry.get_cursor(), INITIALS_LEN - 1);
+        entry.update(&Input::push(Buttons::UP), 0);
+        entry.update(&Input::push(Buttons::LEFT), -3);
+        assert_eq!(entry.get_letters(), *b"ABA");
+        assert_eq!(entry.get_cursor(), 1);
+    }
+
+    #[test]
+    fn browser_switches_modes() {
+        let mut browser = ScoreBrowser::new(NORMAL_3, Some(2));
+        browser.update(&Input::push(Buttons::RIGHT));
+        assert_eq!(browser.get_mode().lives, 5);
+        assert_eq!(browser.get_highlight(), None);
+        browser.update(&Input::push(Buttons::RIGHT));
+        assert_eq!(browser.get_mode().difficulty, Difficulty::Hard);
+        assert_eq!(
+            browser.update(&Input::push(Buttons::DOWN)),
+            ScoreBrowserUpdate::Stats
+        );
+        assert_eq!(
+            browser.update(&Input::push(Buttons::B)),
+            ScoreBrowserUpdate::Closed
+        );
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(format_date(0), "2000-01-01");
+        assert_eq!(format_date(845_683_200), "2026-10-19");
+        assert_eq!(format_date(5_097_600), "2000-02-29");
+    }
+}
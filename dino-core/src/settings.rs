@@ -0,0 +1,470 @@
+//! Player preferences, kept in the save file and changed on the options
+//! screen.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+
+use crate::{
+    args,
+    input::{Buttons, Input},
+};
+
+/// The number of lives a run can start with.
+pub const LIVES_OPTIONS: [u32; 3] = [1, 3, 5];
+pub const MAX_VOLUME: u32 = 10;
+
+/// Which buttons jump and duck during a run. The menus always use Ⓐ and Ⓑ.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Controls {
+    /// Ⓐ jumps, Ⓑ ducks.
+    Classic,
+    /// Ⓑ jumps, Ⓐ ducks.
+    Swapped,
+    /// Up jumps, Down ducks, and Ⓐ and Ⓑ still work.
+    DPad,
+}
+
+impl Controls {
+    pub const ALL: [Controls; 3] = [Controls::Classic, Controls::Swapped, Controls::DPad];
+
+    pub fn get_key(self) -> &'static str {
+        match self {
+            Controls::Classic => "classic",
+            Controls::Swapped => "swapped",
+            Controls::DPad => "d-pad",
+        }
+    }
+
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Controls::Classic => "Ⓐ jump, Ⓑ duck",
+            Controls::Swapped => "Ⓑ jump, Ⓐ duck",
+            Controls::DPad => "Up jump, Down duck",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.get_key() == key)
+    }
+
+    /// Turns the buttons pressed into the Ⓐ and Ⓑ the world reads.
+    pub fn map(self, input: Input) -> Input {
+        let map = |buttons: Buttons| {
+            let (a, b) = (Buttons::A.bits(), Buttons::B.bits());
+            let bits = buttons.bits();
+            match self {
+                Controls::Classic => buttons,
+                Controls::Swapped => {
+                    let mut swapped = bits & !(a | b);
+                    if buttons.contains(Buttons::A) {
+                        swapped |= b;
+                    }
+                    if buttons.contains(Buttons::B) {
+                        swapped |= a;
+                    }
+                    Buttons::from_bits(swapped)
+                }
+                Controls::DPad => {
+                    let mut mapped = buttons;
+                    if buttons.contains(Buttons::UP) {
+                        mapped = mapped | Buttons::A;
+                    }
+                    if buttons.contains(Buttons::DOWN) {
+                        mapped = mapped | Buttons::B;
+                    }
+                    mapped
+                }
+            }
+        };
+        Input {
+            current: map(input.current),
+            pushed: map(input.pushed),
+        }
+    }
+}
+
+/// How fast the ground moves. Replays record it, since it changes the run.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn get_key(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.get_key() == key)
+    }
+
+    /// Scales the ground's speed and acceleration.
+    pub fn get_speed_scale(self) -> f32 {
+        match self {
+            Difficulty::Easy => args::EASY_SPEED_SCALE,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => args::HARD_SPEED_SCALE,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Settings {
+    pub show_fps: bool,
+    /// Sound effects, from 0 to [`MAX_VOLUME`].
+    pub volume: u32,
+    pub controls: Controls,
+    pub difficulty: Difficulty,
+    /// One of [`LIVES_OPTIONS`].
+    pub lives: u32,
+    /// Turns off the day and night cycle and steadies blinking sprites.
+    pub reduce_flashing: bool,
+    /// The folder of the theme pack, empty for the bundled assets.
+    pub theme: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            show_fps: true,
+            volume: MAX_VOLUME,
+            controls: Controls::Classic,
+            difficulty: Difficulty::Normal,
+            lives: LIVES_OPTIONS[0],
+            reduce_flashing: false,
+            theme: String::new(),
+        }
+    }
+}
+
+fn parse_switch(value: &str) -> Result<bool, &'static str> {
+    match value {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        _ => Err("expected on or off"),
+    }
+}
+
+fn switch(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+/// Steps through `options` from `current`, wrapping around.
+fn cycle<T: Copy + PartialEq>(options: &[T], current: T, step: i32) -> T {
+    let index = options.iter().position(|o| *o == current).unwrap_or(0) as i32;
+    options[(index + step).rem_euclid(options.len() as i32) as usize]
+}
+
+impl Settings {
+    /// Reads one `setting <key> <value>` entry of the save file.
+    pub fn parse_entry(&mut self, key: &str, value: &str) -> Result<(), &'static str> {
+        match key {
+            "fps" => self.show_fps = parse_switch(value)?,
+            "volume" => {
+                self.volume = value
+                    .parse()
+                    .ok()
+                    .filter(|v| *v <= MAX_VOLUME)
+                    .ok_or("invalid volume")?;
+            }
+            "controls" => self.controls = Controls::from_key(value).ok_or("unknown controls")?,
+            "difficulty" => {
+                self.difficulty = Difficulty::from_key(value).ok_or("unknown difficulty")?;
+            }
+            "lives" => {
+                self.lives = value
+                    .parse()
+                    .ok()
+                    .filter(|l| LIVES_OPTIONS.contains(l))
+                    .ok_or("invalid lives")?;
+            }
+            "reduce-flashing" => self.reduce_flashing = parse_switch(value)?,
+            "theme" => self.theme = value.to_string(),
+            _ => return Err("unknown setting"),
+        }
+        Ok(())
+    }
+
+    /// The sound effects' volume, from 0 to 1.
+    pub fn get_volume(&self) -> f32 {
+        self.volume as f32 / MAX_VOLUME as f32
+    }
+
+    /// Changes `row` by `step`, where switches just flip.
+    pub fn adjust(&mut self, row: OptionRow, step: i32) {
+        match row {
+            OptionRow::Fps => self.show_fps = !self.show_fps,
+            OptionRow::Volume => {
+                self.volume = (self.volume as i32 + step).clamp(0, MAX_VOLUME as i32) as u32;
+            }
+            OptionRow::Controls => self.controls = cycle(&Controls::ALL, self.controls, step),
+            OptionRow::Difficulty => {
+                self.difficulty = cycle(&Difficulty::ALL, self.difficulty, step);
+            }
+            OptionRow::Lives => self.lives = cycle(&LIVES_OPTIONS, self.lives, step),
+            OptionRow::ReduceFlashing => self.reduce_flashing = !self.reduce_flashing,
+        }
+    }
+
+    /// The value column of `row` on the options screen.
+    pub fn get_value_text(&self, row: OptionRow) -> String {
+        match row {
+            OptionRow::Fps | OptionRow::ReduceFlashing => {
+                let on = if row == OptionRow::Fps {
+                    self.show_fps
+                } else {
+                    self.reduce_flashing
+                };
+                String::from(if on { "On" } else { "Off" })
+            }
+            OptionRow::Volume => format!("{}", self.volume),
+            OptionRow::Controls => String::from(self.controls.get_name()),
+            OptionRow::Difficulty => String::from(self.difficulty.get_name()),
+            OptionRow::Lives => format!("{}", self.lives),
+        }
+    }
+}
+
+/// The save file's entries for the settings.
+impl fmt::Display for Settings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "setting fps {}", switch(self.show_fps))?;
+        writeln!(f, "setting volume {}", self.volume)?;
+        writeln!(f, "setting controls {}", self.controls.get_key())?;
+        writeln!(f, "setting difficulty {}", self.difficulty.get_key())?;
+        writeln!(f, "setting lives {}", self.lives)?;
+        writeln!(
+            f,
+            "setting reduce-flashing {}",
+            switch(self.reduce_flashing)
+        )?;
+        if !self.theme.is_empty() {
+            writeln!(f, "setting theme {}", self.theme)?;
+        }
+        Ok(())
+    }
+}
+
+/// The rows of the options screen. The theme is picked in the system menu.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OptionRow {
+    Fps,
+    Volume,
+    Controls,
+    Difficulty,
+    Lives,
+    ReduceFlashing,
+}
+
+impl OptionRow {
+    pub const ALL: [OptionRow; 6] = [
+        OptionRow::Fps,
+        OptionRow::Volume,
+        OptionRow::Controls,
+        OptionRow::Difficulty,
+        OptionRow::Lives,
+        OptionRow::ReduceFlashing,
+    ];
+
+    pub fn get_name(self) -> &'static str {
+        match self {
+            OptionRow::Fps => "FPS counter",
+            OptionRow::Volume => "Volume",
+            OptionRow::Controls => "Controls",
+            OptionRow::Difficulty => "Difficulty",
+            OptionRow::Lives => "Lives",
+            OptionRow::ReduceFlashing => "Reduce flashing",
+        }
+    }
+}
+
+/// The options screen: Up and Down pick a row, Left, Right and Ⓐ change it
+/// and Ⓑ leaves.
+pub struct Options {
+    selected: usize,
+}
+
+impl Options {
+    pub fn new() -> Self {
+        Self { selected: 0 }
+    }
+
+    pub fn get_selected(&self) -> OptionRow {
+        OptionRow::ALL[self.selected]
+    }
+
+    /// Handles a frame of input.
+    pub fn update(&mut self, input: &Input, settings: &mut Settings) -> OptionsUpdate {
+        let rows = OptionRow::ALL.len();
+        if input.pushed.contains(Buttons::B) {
+            return OptionsUpdate::Closed;
+        }
+        if input.pushed.contains(Buttons::UP) {
+            self.selected = (self.selected + rows - 1) % rows;
+        }
+        if input.pushed.contains(Buttons::DOWN) {
+            self.selected = (self.selected + 1) % rows;
+        }
+        let step = if input.pushed.contains(Buttons::LEFT) {
+            -1
+        } else if input.pushed.contains(Buttons::RIGHT) || input.pushed.contains(Buttons::A) {
+            1
+        } else {
+            return OptionsUpdate::Open;
+        };
+        settings.adjust(self.get_selected(), step);
+        OptionsUpdate::Changed
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OptionsUpdate {
+    Open,
+    /// A setting changed and should be applied.
+    Changed,
+    Closed,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn options_navigate_and_change_rows() {
+        let mut settings = Settings::default();
+        let mut options = Options::new();
+        let mut update = |buttons| options.update(&Input::push(buttons), &mut settings);
+        assert_eq!(update(Buttons::DOWN), OptionsUpdate::Open);
+        assert_eq!(update(Buttons::LEFT), OptionsUpdate::Changed);
+        assert_eq!(update(Buttons::DOWN), OptionsUpdate::Open);
+        assert_eq!(update(Buttons::A), OptionsUpdate::Changed);
+        assert_eq!(update(Buttons::DOWN), OptionsUpdate::Open);
+        assert_eq!(settings.volume, 9);
+        assert_eq!(settings.controls, Controls::Swapped);
+        assert_eq!(options.get_selected(), OptionRow::Difficulty);
+    }
+
+    #[test]
+    fn options_wrap_around() {
+        let mut settings = Settings::default();
+        let mut options = Options::new();
+        options.update(&Input::push(Buttons::UP), &mut settings);
+        assert_eq!(options.get_selected(), OptionRow::ReduceFlashing);
+        options.update(&Input::push(Buttons::DOWN), &mut settings);
+        assert_eq!(options.get_selected(), OptionRow::Fps);
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn options_close_without_changes() {
+        let mut settings = Settings::default();
+        let mut options = Options::new();
+        let input = Input::push(Buttons::B | Buttons::A);
+        assert_eq!(options.update(&input, &mut settings), OptionsUpdate::Closed);
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn adjust_clamps_and_cycles() {
+        let mut settings = Settings::default();
+        settings.adjust(OptionRow::Volume, 1);
+        assert_eq!(settings.volume, MAX_VOLUME);
+        settings.volume = 0;
+        settings.adjust(OptionRow::Volume, -1);
+        assert_eq!(settings.volume, 0);
+        settings.adjust(OptionRow::Lives, -1);
+        assert_eq!(settings.lives, 5);
+        settings.adjust(OptionRow::Difficulty, 1);
+        assert_eq!(settings.difficulty, Difficulty::Hard);
+        settings.adjust(OptionRow::Fps, -1);
+        assert!(!settings.show_fps);
+    }
+
+    #[test]
+    fn parse_entries() {
+        let mut settings = Settings::default();
+        settings.parse_entry("fps", "off").unwrap();
+        settings.parse_entry("volume", "3").unwrap();
+        settings.parse_entry("controls", "d-pad").unwrap();
+        settings.parse_entry("difficulty", "easy").unwrap();
+        settings.parse_entry("lives", "3").unwrap();
+        settings.parse_entry("reduce-flashing", "on").unwrap();
+        settings.parse_entry("theme", "packs/night sky").unwrap();
+        let expected = Settings {
+            show_fps: false,
+            volume: 3,
+            controls: Controls::DPad,
+            difficulty: Difficulty::Easy,
+            lives: 3,
+            reduce_flashing: true,
+            theme: "packs/night sky".to_string(),
+        };
+        assert_eq!(settings, expected);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_entries() {
+        let mut settings = Settings::default();
+        assert_eq!(
+            settings.parse_entry("fps", "yes"),
+            Err("expected on or off")
+        );
+        assert_eq!(settings.parse_entry("volume", "11"), Err("invalid volume"));
+        assert_eq!(settings.parse_entry("volume", "-1"), Err("invalid volume"));
+        assert_eq!(settings.parse_entry("lives", "2"), Err("invalid lives"));
+        assert_eq!(
+            settings.parse_entry("controls", "mirrored"),
+            Err("unknown controls")
+        );
+        assert_eq!(settings.parse_entry("speed", "9"), Err("unknown setting"));
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let settings = Settings {
+            volume: 4,
+            controls: Controls::Swapped,
+            theme: "packs/night sky".to_string(),
+            ..Settings::default()
+        };
+        let mut parsed = Settings::default();
+        for line in settings.to_string().lines() {
+            let entry = line.strip_prefix("setting ").unwrap();
+            let (key, value) = entry.split_once(' ').unwrap();
+            parsed.parse_entry(key, value).unwrap();
+        }
+        assert_eq!(parsed, settings);
+    }
+}
@@ -0,0 +1,279 @@
+//! Spending coins.
+//!
+//! The [`Wallet`] is what frontends persist: the coins, the perks bought for
+//! coming runs and the cosmetics unlocked for good. It is saved as text:
+//!
+//! ```text
+//! coins 120
+//! perk extra-life 2
+//! unlock night-start
+//! ```
+
+use alloc::string::String;
+use core::{
+    fmt::{self, Write},
+    ops::BitOr,
+};
+
+use crate::input::{Buttons, Input};
+
+/// Perks a run starts with, see [`crate::World::set_perks`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Perks(u8);
+
+impl Perks {
+    pub const NONE: Self = Self(0);
+    /// One more life than the chosen number of lives.
+    pub const EXTRA_LIFE: Self = Self(1 << 0);
+    /// A shield power-up, active from the start.
+    pub const SHIELD: Self = Self(1 << 1);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits & 0x3)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0 && other.0 != 0
+    }
+}
+
+impl BitOr for Perks {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Item {
+    ExtraLife,
+    StartShield,
+    /// Cosmetic: runs start at night.
+    NightStart,
+}
+
+impl Item {
+    pub const ALL: [Item; 3] = [Item::ExtraLife, Item::StartShield, Item::NightStart];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Item::ExtraLife => "Extra life",
+            Item::StartShield => "Starting shield",
+            Item::NightStart => "Night start",
+        }
+    }
+
+    /// Name used in the wallet file.
+    fn get_key(&self) -> &'static str {
+        match self {
+            Item::ExtraLife => "extra-life",
+            Item::StartShield => "start-shield",
+            Item::NightStart => "night-start",
+        }
+    }
+
+    pub fn get_price(&self) -> u32 {
+        match self {
+            Item::ExtraLife => 50,
+            Item::StartShield => 30,
+            Item::NightStart => 200,
+        }
+    }
+
+    /// Perks are used up by the next run, cosmetics are unlocked for good.
+    pub fn get_perk(&self) -> Option<Perks> {
+        match self {
+            Item::ExtraLife => Some(Perks::EXTRA_LIFE),
+            Item::StartShield => Some(Perks::SHIELD),
+            Item::NightStart => None,
+        }
+    }
+
+    fn index(&self) -> usize {
+        Item::ALL.iter().position(|i| i == self).unwrap()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BuyError {
+    NotEnoughCoins,
+    AlreadyUnlocked,
+}
+
+impl fmt::Display for BuyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuyError::NotEnoughCoins => write!(f, "Not enough coins"),
+            BuyError::AlreadyUnlocked => write!(f, "Already unlocked"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Wallet {
+    coins: u32,
+    /// Bought perks per item, or 1 for an unlocked cosmetic.
+    owned: [u32; 3],
+}
+
+impl Wallet {
+    pub fn get_coins(&self) -> u32 {
+        self.coins
+    }
+
+    pub fn add_coins(&mut self, coins: u32) {
+        self.coins = self.coins.saturating_add(coins);
+    }
+
+    /// How many of a perk are in stock, or 1 if a cosmetic is unlocked.
+    pub fn get_owned(&self, item: Item) -> u32 {
+        self.owned[item.index()]
+    }
+
+    pub fn is_unlocked(&self, item: Item) -> bool {
+        self.get_owned(item) > 0
+    }
+
+    pub fn buy(&mut self, item: Item) -> Result<(), BuyError> {
+        if item.get_perk().is_none() && self.is_unlocked(item) {
+            return Err(BuyError::AlreadyUnlocked);
+        }
+        if self.coins < item.get_price() {
+            return Err(BuyError::NotEnoughCoins);
+        }
+        self.coins -= item.get_price();
+        self.owned[item.index()] += 1;
+        Ok(())
+    }
+
+    /// Takes one of every perk in stock, for the run about to start.
+    pub fn take_perks(&mut self) -> Perks {
+        let mut perks = Perks::NONE;
+        for item in Item::ALL {
+            if let Some(perk) = item.get_perk() {
+                if self.owned[item.index()] > 0 {
+                    self.owned[item.index()] -= 1;
+                    perks = perks | perk;
+                }
+            }
+        }
+        perks
+    }
+
+    /// Reads a saved wallet. Unknown entries are skipped, so wallets from
+    /// newer versions still load.
+    pub fn parse(text: &str) -> Self {
+        let mut wallet = Self::default();
+        for line in text.lines() {
+            let mut words = line.split_whitespace();
+            match (words.next(), words.next(), words.next()) {
+                (Some("coins"), Some(coins), _) => {
+                    wallet.coins = coins.parse().unwrap_or(0);
+                }
+                (Some("perk"), Some(key), Some(count)) => {
+                    if let Some(item) = Item::ALL.iter().find(|i| i.get_key() == key) {
+                        wallet.owned[item.index()] = count.parse().unwrap_or(0);
+                    }
+                }
+                (Some("unlock"), Some(key), _) => {
+                    if let Some(item) = Item::ALL.iter().find(|i| i.get_key() == key) {
+                        wallet.owned[item.index()] = 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        wallet
+    }
+}
+
+impl fmt::Display for Wallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "coins {}", self.coins)?;
+        for item in Item::ALL {
+            let owned = self.get_owned(item);
+            if owned == 0 {
+                continue;
+            }
+            match item.get_perk() {
+                Some(_) => writeln!(f, "perk {} {}", item.get_key(), owned)?,
+                None => writeln!(f, "unlock {}", item.get_key())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The shop screen: Up and Down pick an item, Ⓐ buys it and Ⓑ leaves.
+pub struct Shop {
+    selected: usize,
+    message: String,
+}
+
+impl Shop {
+    pub fn new() -> Self {
+        Self {
+            selected: 0,
+            message: String::new(),
+        }
+    }
+
+    pub fn get_selected(&self) -> Item {
+        Item::ALL[self.selected]
+    }
+
+    /// The result of the last purchase.
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Handles a frame of input.
+    pub fn update(&mut self, input: &Input, wallet: &mut Wallet) -> ShopUpdate {
+        if input.pushed.contains(Buttons::B) {
+            self.message.clear();
+            return ShopUpdate::Closed;
+        }
+        if input.pushed.contains(Buttons::UP) {
+            self.selected = (self.selected + Item::ALL.len() - 1) % Item::ALL.len();
+            self.message.clear();
+        }
+        if input.pushed.contains(Buttons::DOWN) {
+            self.selected = (self.selected + 1) % Item::ALL.len();
+            self.message.clear();
+        }
+        if input.pushed.contains(Buttons::A) {
+            let item = self.get_selected();
+            self.message.clear();
+            match wallet.buy(item) {
+                Ok(()) => {
+                    self.message.push_str("Bought ");
+                    self.message.push_str(item.get_name());
+                    return ShopUpdate::Bought(item);
+                }
+                Err(e) => {
+                    let _ = write!(self.message, "{}", e);
+                }
+            }
+        }
+        ShopUpdate::Open
+    }
+}
+
+impl Default for Shop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShopUpdate {
+    Open,
+    /// Something was bought, the wallet should be saved.
+    Bought(Item),
+    Closed,
+}
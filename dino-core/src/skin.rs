@@ -0,0 +1,313 @@
+//! Dino skins.
+//!
+//! A skin is a bitmap table with its own frame map, plus an optional
+//! accessory drawn over the head. Every frame of a table has an anchor, the
+//! top-left corner of the head in sprite coordinates, so accessories follow
+//! the head when the dino ducks.
+//!
+//! Skins are unlocked by reaching score achievements. The [`Wardrobe`] is
+//! what frontends persist, as text:
+//!
+//! ```text
+//! skin ghost
+//! unlock top-hat
+//! unlock ghost
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{dino::DinoState, math::Size};
+
+/// The frames of one dino state and how long each is shown.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Animation {
+    pub frames: &'static [usize],
+    pub frame_time: f32,
+}
+
+impl Animation {
+    const fn new(frames: &'static [usize], frame_time: f32) -> Self {
+        Self { frames, frame_time }
+    }
+}
+
+/// Which table frames play for each [`DinoState`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FrameMap {
+    pub idle: Animation,
+    pub jump: Animation,
+    pub run: Animation,
+    pub duck: Animation,
+    pub dead: Animation,
+    pub hurt: Animation,
+}
+
+impl FrameMap {
+    pub fn get(&self, state: DinoState) -> Animation {
+        match state {
+            DinoState::Idle => self.idle,
+            DinoState::Jump => self.jump,
+            DinoState::Run => self.run,
+            DinoState::Duck => self.duck,
+            DinoState::Dead => self.dead,
+            DinoState::Hurt => self.hurt,
+        }
+    }
+}
+
+/// A bitmap table of dino frames, at twice the on-screen size.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SkinTable {
+    /// The table's name, without the `-table-<w>-<h>` suffix.
+    pub path: &'static str,
+    pub count: usize,
+    pub width: u32,
+    pub height: u32,
+    pub frames: FrameMap,
+    /// The head position of every frame, on screen scale.
+    pub anchors: &'static [(f32, f32)],
+}
+
+const STANDING_HEAD: (f32, f32) = (42.0, 2.0);
+const DUCKING_HEAD: (f32, f32) = (57.0, 20.0);
+
+pub const CLASSIC_TABLE: SkinTable = SkinTable {
+    path: "dino",
+    count: 8,
+    width: 160,
+    height: 94,
+    frames: FrameMap {
+        idle: Animation::new(&[1, 2], 0.5),
+        jump: Animation::new(&[1], 0.5),
+        run: Animation::new(&[3, 4], 0.2),
+        duck: Animation::new(&[5, 6], 0.2),
+        dead: Animation::new(&[7], 1.0),
+        hurt: Animation::new(&[7, 3], 0.1),
+    },
+    anchors: &[
+        STANDING_HEAD,
+        STANDING_HEAD,
+        STANDING_HEAD,
+        STANDING_HEAD,
+        STANDING_HEAD,
+        DUCKING_HEAD,
+        DUCKING_HEAD,
+        STANDING_HEAD,
+    ],
+};
+
+/// The classic frames drawn as outlines, without the unused first frame.
+pub const GHOST_TABLE: SkinTable = SkinTable {
+    path: "dino-ghost",
+    count: 7,
+    width: 160,
+    height: 94,
+    frames: FrameMap {
+        idle: Animation::new(&[0, 1], 0.5),
+        jump: Animation::new(&[0], 0.5),
+        run: Animation::new(&[2, 3], 0.2),
+        duck: Animation::new(&[4, 5], 0.2),
+        dead: Animation::new(&[6], 1.0),
+        hurt: Animation::new(&[6, 2], 0.1),
+    },
+    anchors: &[
+        STANDING_HEAD,
+        STANDING_HEAD,
+        STANDING_HEAD,
+        STANDING_HEAD,
+        DUCKING_HEAD,
+        DUCKING_HEAD,
+        STANDING_HEAD,
+    ],
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Accessory {
+    TopHat,
+    Crown,
+}
+
+impl Accessory {
+    pub const ALL: [Accessory; 2] = [Accessory::TopHat, Accessory::Crown];
+
+    pub fn get_path(&self) -> &'static str {
+        match self {
+            Accessory::TopHat => "accessory/top-hat",
+            Accessory::Crown => "accessory/crown",
+        }
+    }
+
+    /// On-screen size, half the size of the image.
+    pub fn get_size(&self) -> Size {
+        match self {
+            Accessory::TopHat => Size::new(12.0, 8.0),
+            Accessory::Crown => Size::new(12.0, 7.0),
+        }
+    }
+
+    /// Where the accessory goes for a given head anchor, so that it sits on
+    /// top of the head.
+    pub fn get_position(&self, anchor: (f32, f32)) -> (f32, f32) {
+        (anchor.0 + 2.0, anchor.1 - self.get_size().height)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Skin {
+    Classic,
+    TopHat,
+    Ghost,
+    Crown,
+}
+
+impl Skin {
+    pub const ALL: [Skin; 4] = [Skin::Classic, Skin::TopHat, Skin::Ghost, Skin::Crown];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Skin::Classic => "CLASSIC",
+            Skin::TopHat => "TOP HAT",
+            Skin::Ghost => "GHOST",
+            Skin::Crown => "CROWN",
+        }
+    }
+
+    /// Name used in the wardrobe file.
+    fn get_key(&self) -> &'static str {
+        match self {
+            Skin::Classic => "classic",
+            Skin::TopHat => "top-hat",
+            Skin::Ghost => "ghost",
+            Skin::Crown => "crown",
+        }
+    }
+
+    pub fn get_table(&self) -> &'static SkinTable {
+        match self {
+            Skin::Classic | Skin::TopHat | Skin::Crown => &CLASSIC_TABLE,
+            Skin::Ghost => &GHOST_TABLE,
+        }
+    }
+
+    pub fn get_accessory(&self) -> Option<Accessory> {
+        match self {
+            Skin::TopHat => Some(Accessory::TopHat),
+            Skin::Crown => Some(Accessory::Crown),
+            Skin::Classic | Skin::Ghost => None,
+        }
+    }
+
+    /// The score achievement that unlocks the skin, or 0 if it is always
+    /// available.
+    pub fn get_unlock_score(&self) -> i32 {
+        match self {
+            Skin::Classic => 0,
+            Skin::TopHat => 500,
+            Skin::Ghost => 1000,
+            Skin::Crown => 2000,
+        }
+    }
+
+    fn index(&self) -> usize {
+        Skin::ALL.iter().position(|s| s == self).unwrap()
+    }
+}
+
+/// The skins unlocked so far and the one in use.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Wardrobe {
+    selected: Skin,
+    unlocked: [bool; 4],
+}
+
+impl Wardrobe {
+    pub fn new() -> Self {
+        Self {
+            selected: Skin::Classic,
+            unlocked: [true, false, false, false],
+        }
+    }
+
+    pub fn get_selected(&self) -> Skin {
+        self.selected
+    }
+
+    pub fn is_unlocked(&self, skin: Skin) -> bool {
+        self.unlocked[skin.index()]
+    }
+
+    pub fn unlocked(&self) -> impl Iterator<Item = Skin> + '_ {
+        Skin::ALL.into_iter().filter(|s| self.is_unlocked(*s))
+    }
+
+    /// Selects `skin` if it is unlocked.
+    pub fn select(&mut self, skin: Skin) -> bool {
+        if self.is_unlocked(skin) {
+            self.selected = skin;
+        }
+        self.selected == skin
+    }
+
+    /// Moves the selection to the next unlocked skin, or the previous one if
+    /// `step` is negative.
+    pub fn cycle(&mut self, step: isize) {
+        let count = Skin::ALL.len() as isize;
+        let mut index = self.selected.index() as isize;
+        loop {
+            index = (index + step).rem_euclid(count);
+            if self.unlocked[index as usize] {
+                self.selected = Skin::ALL[index as usize];
+                return;
+            }
+        }
+    }
+
+    /// Unlocks the skins earned by reaching the `score` achievement, and
+    /// returns the ones that were new.
+    pub fn unlock_for(&mut self, score: i32) -> Vec<Skin> {
+        let mut unlocked = Vec::new();
+        for skin in Skin::ALL {
+            if !self.is_unlocked(skin) && score >= skin.get_unlock_score() {
+                self.unlocked[skin.index()] = true;
+                unlocked.push(skin);
+            }
+        }
+        unlocked
+    }
+
+    /// Reads a saved wardrobe. Unknown entries are skipped.
+    pub fn parse(text: &str) -> Self {
+        let mut wardrobe = Self::new();
+        let find = |key| Skin::ALL.into_iter().find(|s| s.get_key() == key);
+        let mut selected = None;
+        for line in text.lines() {
+            let mut words = line.split_whitespace();
+            match (words.next(), words.next().and_then(find)) {
+                (Some("skin"), Some(skin)) => selected = Some(skin),
+                (Some("unlock"), Some(skin)) => wardrobe.unlocked[skin.index()] = true,
+                _ => {}
+            }
+        }
+        if let Some(skin) = selected {
+            wardrobe.select(skin);
+        }
+        wardrobe
+    }
+}
+
+impl Default for Wardrobe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Wardrobe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "skin {}", self.selected.get_key())?;
+        for skin in self.unlocked().filter(|s| s.get_unlock_score() > 0) {
+            writeln!(f, "unlock {}", skin.get_key())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,338 @@
+//! Lifetime statistics of a profile, kept in the save file and shown on the
+//! stats screen.
+
+use alloc::{format, string::String};
+use core::fmt;
+
+use crate::{
+    input::{Buttons, Input},
+    obstacle::ObstacleKind,
+    Event, World,
+};
+
+/// How far the ground moves for a meter of distance, about half the dino's
+/// height.
+pub const PIXELS_PER_METER: f32 = 20.0;
+
+const KINDS: usize = ObstacleKind::ALL.len();
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Stats {
+    /// Finished runs.
+    pub runs: u32,
+    /// In meters, see [`PIXELS_PER_METER`].
+    pub distance: u64,
+    pub play_time_ms: u64,
+    pub jumps: u32,
+    pub ducks: u32,
+    /// Power-ups collected.
+    pub power_ups: u32,
+    /// Obstacles that went past the dino, by [`ObstacleKind::ALL`] order.
+    pub cleared: [u32; KINDS],
+    /// The obstacles that ended runs, by [`ObstacleKind::ALL`] order.
+    pub deaths: [u32; KINDS],
+    pub longest_run_ms: u32,
+    /// The sum of every run's score, for the average.
+    pub total_score: u64,
+}
+
+impl Stats {
+    /// Counts the last update of the world, and the run once it ends.
+    pub fn record(&mut self, world: &World) {
+        for event in world.events() {
+            match event {
+                Event::Jumped => self.jumps += 1,
+                Event::Ducked => self.ducks += 1,
+                Event::PowerUpCollected(_) => self.power_ups += 1,
+                Event::ObstacleCleared(kind) => self.cleared[kind.index()] += 1,
+                Event::Died(kind) => {
+                    self.deaths[kind.index()] += 1;
+                    self.finish_run(world);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn finish_run(&mut self, world: &World) {
+        self.runs += 1;
+        self.distance += (world.get_distance() / PIXELS_PER_METER) as u64;
+        self.play_time_ms += u64::from(world.get_elapsed_ms());
+        self.longest_run_ms = self.longest_run_ms.max(world.get_elapsed_ms());
+        self.total_score += world.scoreboard.get_score().max(0) as u64;
+    }
+
+    pub fn get_cleared(&self, kind: ObstacleKind) -> u32 {
+        self.cleared[kind.index()]
+    }
+
+    pub fn get_deaths(&self, kind: ObstacleKind) -> u32 {
+        self.deaths[kind.index()]
+    }
+
+    pub fn get_average_score(&self) -> u64 {
+        self.total_score
+            .checked_div(u64::from(self.runs))
+            .unwrap_or(0)
+    }
+
+    /// Reads one `stat <key> ...` entry of the save file.
+    pub fn parse_entry(&mut self, text: &str) -> Result<(), &'static str> {
+        let mut words = text.split_whitespace();
+        let key = words.next().ok_or("missing field")?;
+        let counters = match key {
+            "cleared" => Some(&mut self.cleared),
+            "deaths" => Some(&mut self.deaths),
+            _ => None,
+        };
+        if let Some(counters) = counters {
+            let kind = words.next().ok_or("missing field")?;
+            let kind = ObstacleKind::from_key(kind).ok_or("unknown obstacle")?;
+            let value = words.next().ok_or("missing field")?;
+            counters[kind.index()] = value.parse().map_err(|_| "invalid stat")?;
+            return Ok(());
+        }
+        let value = words.next().ok_or("missing field")?;
+        let invalid = |_| "invalid stat";
+        match key {
+            "runs" => self.runs = value.parse().map_err(invalid)?,
+            "distance" => self.distance = value.parse().map_err(invalid)?,
+            "play-time" => self.play_time_ms = value.parse().map_err(invalid)?,
+            "jumps" => self.jumps = value.parse().map_err(invalid)?,
+            "ducks" => self.ducks = value.parse().map_err(invalid)?,
+            "power-ups" => self.power_ups = value.parse().map_err(invalid)?,
+            "longest-run" => self.longest_run_ms = value.parse().map_err(invalid)?,
+            "total-score" => self.total_score = value.parse().map_err(invalid)?,
+            _ => return Err("unknown stat"),
+        }
+        Ok(())
+    }
+}
+
+/// The save file's entries for the stats. Obstacles never met are left out.
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "stat runs {}", self.runs)?;
+        writeln!(f, "stat distance {}", self.distance)?;
+        writeln!(f, "stat play-time {}", self.play_time_ms)?;
+        writeln!(f, "stat jumps {}", self.jumps)?;
+        writeln!(f, "stat ducks {}", self.ducks)?;
+        writeln!(f, "stat power-ups {}", self.power_ups)?;
+        writeln!(f, "stat longest-run {}", self.longest_run_ms)?;
+        writeln!(f, "stat total-score {}", self.total_score)?;
+        for kind in ObstacleKind::ALL {
+            if self.get_cleared(kind) > 0 {
+                writeln!(
+                    f,
+                    "stat cleared {} {}",
+                    kind.get_key(),
+                    self.get_cleared(kind)
+                )?;
+            }
+            if self.get_deaths(kind) > 0 {
+                writeln!(
+                    f,
+                    "stat deaths {} {}",
+                    kind.get_key(),
+                    self.get_deaths(kind)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `ms` as hours and minutes, or minutes and seconds for less than an hour.
+pub fn format_duration(ms: u64) -> String {
+    let secs = ms / 1000;
+    if secs >= 3600 {
+        format!("{}h {:02}m", secs / 3600, secs / 60 % 60)
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
+
+/// `meters` in kilometers from 10 km on.
+pub fn format_distance(meters: u64) -> String {
+    if meters >= 10_000 {
+        format!("{}.{} km", meters / 1000, meters / 100 % 10)
+    } else {
+        format!("{} m", meters)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatsPage {
+    Totals,
+    Obstacles,
+}
+
+impl StatsPage {
+    pub fn get_name(self) -> &'static str {
+        match self {
+            StatsPage::Totals => "Totals",
+            StatsPage::Obstacles => "Obstacles",
+        }
+    }
+}
+
+/// Browsing the stats: Left and Right switch pages and Ⓐ or Ⓑ leave.
+pub struct StatsBrowser {
+    page: StatsPage,
+}
+
+impl StatsBrowser {
+    pub fn new() -> Self {
+        Self {
+            page: StatsPage::Totals,
+        }
+    }
+
+    pub fn get_page(&self) -> StatsPage {
+        self.page
+    }
+
+    /// Handles a frame of input and returns whether the browser stays open.
+    pub fn update(&mut self, input: &Input) -> bool {
+        if input.pushed.contains(Buttons::A) || input.pushed.contains(Buttons::B) {
+            return false;
+        }
+        if input.pushed.contains(Buttons::LEFT) || input.pushed.contains(Buttons::RIGHT) {
+            // with two pages, both ways lead to the other one
+            self.page = match self.page {
+                StatsPage::Totals => StatsPage::Obstacles,
+                StatsPage::Obstacles => StatsPage::Totals,
+            };
+        }
+        true
+    }
+}
+
+impl Default for StatsBrowser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::{save::Save, GameState};
+
+    /// Jumps over cacti and low birds, and ducks under high birds.
+    fn autopilot(world: &World) -> Input {
+        let dino = world.dino.get_bounds();
+        let Some(obstacle) = world.obstacles.iter().next() else {
+            return Input::default();
+        };
+        let distance = obstacle.get_bounds().x - dino.right();
+        let reach = world.ground.get_velocity() * 0.2;
+        if distance > reach || obstacle.get_bounds().right() < dino.x {
+            return Input::default();
+        }
+        if obstacle.get_kind() == ObstacleKind::Bird {
+            Input {
+                current: Buttons::B,
+                pushed: Buttons::NONE,
+            }
+        } else {
+            Input::push(Buttons::A)
+        }
+    }
+
+    /// Thirty seconds on autopilot, then a run into the next obstacle.
+    fn record_run() -> (Stats, World) {
+        let mut world = World::new();
+        let mut stats = Stats::default();
+        world.start(7);
+        for _ in 0..30_000 / 33 {
+            world.update(autopilot(&world), 33);
+            stats.record(&world);
+        }
+        assert_eq!(stats.runs, 0);
+        while world.get_state() == GameState::Playing {
+            world.update(Input::default(), 33);
+            stats.record(&world);
+        }
+        (stats, world)
+    }
+
+    #[test]
+    fn record_counts_a_run() {
+        let (stats, world) = record_run();
+        assert!(stats.cleared.iter().sum::<u32>() > 0);
+        assert!(stats.jumps > 0 && stats.ducks > 0);
+        assert_eq!(stats.runs, 1);
+        assert_eq!(stats.deaths.iter().sum::<u32>(), 1);
+        assert_eq!(stats.longest_run_ms, world.get_elapsed_ms());
+        assert_eq!(stats.play_time_ms, u64::from(world.get_elapsed_ms()));
+        assert_eq!(
+            stats.distance,
+            (world.get_distance() / PIXELS_PER_METER) as u64
+        );
+        assert_eq!(
+            stats.total_score,
+            world.scoreboard.get_score().max(0) as u64
+        );
+    }
+
+    #[test]
+    fn save_entries_round_trip() {
+        let (stats, _) = record_run();
+        let mut parsed = Stats::default();
+        for line in stats.to_string().lines() {
+            parsed
+                .parse_entry(line.strip_prefix("stat").unwrap())
+                .unwrap();
+        }
+        assert_eq!(parsed, stats);
+        let save = Save {
+            stats: stats.clone(),
+            ..Save::default()
+        };
+        assert_eq!(Save::parse(&save.to_string()).unwrap().stats, stats);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_entries() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.parse_entry(" runs"), Err("missing field"));
+        assert_eq!(stats.parse_entry(" runs -1"), Err("invalid stat"));
+        assert_eq!(stats.parse_entry(" coins 3"), Err("unknown stat"));
+        assert_eq!(
+            stats.parse_entry(" deaths volcano 1"),
+            Err("unknown obstacle")
+        );
+        assert_eq!(stats, Stats::default());
+    }
+
+    #[test]
+    fn average_score() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.get_average_score(), 0);
+        stats.runs = 3;
+        stats.total_score = 1000;
+        assert_eq!(stats.get_average_score(), 333);
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(format_duration(59_999), "0m 59s");
+        assert_eq!(format_duration(754_000), "12m 34s");
+        assert_eq!(format_duration(3_723_000), "1h 02m");
+        assert_eq!(format_distance(9_999), "9999 m");
+        assert_eq!(format_distance(12_345), "12.3 km");
+    }
+
+    #[test]
+    fn browser_switches_pages() {
+        let mut browser = StatsBrowser::new();
+        assert!(browser.update(&Input::push(Buttons::RIGHT)));
+        assert_eq!(browser.get_page(), StatsPage::Obstacles);
+        assert!(browser.update(&Input::push(Buttons::LEFT)));
+        assert_eq!(browser.get_page(), StatsPage::Totals);
+        assert!(!browser.update(&Input::push(Buttons::B)));
+    }
+}
@@ -0,0 +1,218 @@
+//! Theme packs.
+//!
+//! A pack is a folder under `themes/` in the game's data folder with a
+//! `theme.txt` manifest. The manifest maps bundled asset names to files in
+//! the pack and may give obstacles new sizes and hitboxes, and the dino new
+//! hitboxes:
+//!
+//! ```text
+//! name Desert night
+//! image ground sand
+//! image cactus/cactus-big-1 cacti/saguaro
+//! sound jump sounds/boing
+//! obstacle cactus-big-1 25 50 0,0,25,17 4,17,17,33
+//! dino ducking 18,17,59,15 28,32,28,15
+//! ```
+//!
+//! `obstacle <kind> <width> <height> <x,y,w,h>...` is in screen pixels, like
+//! [`crate::hitbox`]; `bird` sets both bird kinds. `dino <standing|ducking>
+//! <x,y,w,h>...` sets the boxes of one pose, at least one. `hitbox-tool` derives both from
+//! the images. Lines starting with `#` are comments. Assets the manifest doesn't mention fall back to the bundled
+//! ones.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    dino::DinoShape,
+    math::{Rect, Size},
+    obstacle::{ObstacleKind, Shape},
+    replay::ParseError,
+    World,
+};
+
+pub const THEMES_DIR: &str = "themes";
+pub const MANIFEST: &str = "theme.txt";
+
+/// Images a pack can replace. Tables keep the bundled frame layout.
+pub const IMAGES: &[&str] = &[
+    "dino",
+    "dino-ghost",
+    "bird",
+    "cactus/cactus-small-1",
+    "cactus/cactus-small-2",
+    "cactus/cactus-small-3",
+    "cactus/cactus-big-1",
+    "cactus/cactus-big-2",
+    "cactus/cactus-big-3",
+    "ground",
+    "cloud",
+    "game-over",
+    "restart",
+    "heart",
+    "coin",
+    "power-up/shield",
+    "power-up/double-jump",
+    "power-up/slow-time",
+    "accessory/top-hat",
+    "accessory/crown",
+];
+
+pub const SOUNDS: &[&str] = &[
+    "jump",
+    "dead",
+    "achievement",
+    "coin",
+    "power-up/shield",
+    "power-up/double-jump",
+    "power-up/slow-time",
+];
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Theme {
+    name: String,
+    dir: String,
+    images: Vec<(&'static str, String)>,
+    sounds: Vec<(&'static str, String)>,
+    shapes: Vec<(ObstacleKind, Shape)>,
+    dino: Option<DinoShape>,
+}
+
+impl Theme {
+    /// Reads the manifest of the pack in `dir`. The name defaults to the
+    /// folder's name.
+    pub fn parse(dir: &str, text: &str) -> Result<Self, ParseError> {
+        let mut theme = Self {
+            name: dir.rsplit('/').next().unwrap_or(dir).to_string(),
+            dir: dir.to_string(),
+            ..Self::default()
+        };
+        let error = |line, message| ParseError { line, message };
+        for (line, text) in text.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+            let mut words = text.split_whitespace();
+            let Some(tag) = words.next().filter(|t| !t.starts_with('#')) else {
+                continue;
+            };
+            let mut next = || words.next().ok_or(error(line, "missing field"));
+            match tag {
+                "name" => {
+                    theme.name = text[tag.len()..].trim().to_string();
+                }
+                "image" => {
+                    let name = next()?;
+                    let name = *IMAGES
+                        .iter()
+                        .find(|n| **n == name)
+                        .ok_or(error(line, "unknown image"))?;
+                    theme.images.push((name, next()?.to_string()));
+                }
+                "sound" => {
+                    let name = next()?;
+                    let name = *SOUNDS
+                        .iter()
+                        .find(|n| **n == name)
+                        .ok_or(error(line, "unknown sound"))?;
+                    theme.sounds.push((name, next()?.to_string()));
+                }
+                "obstacle" => {
+                    let key = next()?;
+                    let kinds = match (key, ObstacleKind::from_key(key)) {
+                        ("bird", _) => vec![ObstacleKind::Bird, ObstacleKind::LowBird],
+                        (_, Some(kind)) => vec![kind],
+                        (_, None) => return Err(error(line, "unknown obstacle")),
+                    };
+                    let width = parse_f32(next()?).ok_or(error(line, "invalid size"))?;
+                    let height = parse_f32(next()?).ok_or(error(line, "invalid size"))?;
+                    let hitboxes = words
+                        .map(parse_rect)
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or(error(line, "invalid hitbox"))?;
+                    let shape = Shape {
+                        size: Size::new(width, height),
+                        hitboxes,
+                    };
+                    for kind in kinds {
+                        theme.shapes.push((kind, shape.clone()));
+                    }
+                }
+                "dino" => {
+                    let pose = next()?;
+                    let hitboxes = words
+                        .map(parse_rect)
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or(error(line, "invalid hitbox"))?;
+                    // a dino without boxes can't be hit
+                    if hitboxes.is_empty() {
+                        return Err(error(line, "missing hitbox"));
+                    }
+                    let shape = theme.dino.get_or_insert_with(DinoShape::default);
+                    match pose {
+                        "standing" => shape.standing = hitboxes,
+                        "ducking" => shape.ducking = hitboxes,
+                        _ => return Err(error(line, "unknown pose")),
+                    }
+                }
+                _ => return Err(error(line, "unknown entry")),
+            }
+        }
+        Ok(theme)
+    }
+
+    pub fn get_name(&self) -> &str {
+        if self.dir.is_empty() {
+            return "Default";
+        }
+        &self.name
+    }
+
+    /// The folder of the pack, empty for the bundled assets.
+    pub fn get_dir(&self) -> &str {
+        &self.dir
+    }
+
+    /// Where to load the bundled image `name` from.
+    pub fn image_path(&self, name: &str) -> String {
+        Self::resolve(&self.dir, &self.images, name)
+    }
+
+    /// Where to load the bundled sound `name` from.
+    pub fn sound_path(&self, name: &str) -> String {
+        Self::resolve(&self.dir, &self.sounds, name)
+    }
+
+    fn resolve(dir: &str, entries: &[(&'static str, String)], name: &str) -> String {
+        match entries.iter().rev().find(|(n, _)| *n == name) {
+            Some((_, path)) => format!("{}/{}", dir, path),
+            None => name.to_string(),
+        }
+    }
+
+    /// Sets the obstacle and dino shapes of the pack, and the bundled ones
+    /// for those it doesn't change.
+    pub fn apply(&self, world: &mut World) {
+        world.obstacles.reset_shapes();
+        for (kind, shape) in &self.shapes {
+            world.obstacles.set_shape(*kind, shape.clone());
+        }
+        world.dino.set_shape(self.dino.clone().unwrap_or_default());
+    }
+}
+
+pub(crate) fn parse_f32(s: &str) -> Option<f32> {
+    s.parse().ok().filter(|v: &f32| v.is_finite() && *v >= 0.0)
+}
+
+pub(crate) fn parse_rect(s: &str) -> Option<Rect> {
+    let mut values = s.split(',').map(parse_f32);
+    let rect = Rect::new(
+        values.next()??,
+        values.next()??,
+        values.next()??,
+        values.next()??,
+    );
+    values.next().is_none().then_some(rect)
+}
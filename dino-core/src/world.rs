@@ -0,0 +1,470 @@
+#[cfg(feature = "cheats")]
+use alloc::{format, string::String};
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    args,
+    coin::Coins,
+    dino::{Dino, DinoState},
+    ground::Ground,
+    input::{Buttons, Input},
+    lives::Lives,
+    obstacle::{ObstacleKind, Obstacles},
+    power_up::{PowerUpKind, PowerUps},
+    rng::Rng,
+    scoreboard::Scoreboard,
+    settings::Difficulty,
+    shop::Perks,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum GameState {
+    Ready,
+    Playing,
+    Dead,
+}
+
+/// The longest single update. Longer frames are run as several, see
+/// [`World::advance`], so a stalled frame can't carry the dino through an
+/// obstacle.
+pub const MAX_FRAME_MS: u32 = 50;
+
+/// Things that happened during the last [`World::update`] or
+/// [`World::advance`]. Frontends use them to play sounds and persist records.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Event {
+    Jumped,
+    Ducked,
+    /// Lost a life, but not the last one.
+    Hurt(ObstacleKind),
+    Died(ObstacleKind),
+    Achievement(i32),
+    ObstacleSpawned(ObstacleKind),
+    /// The obstacle went past the dino without hitting it.
+    ObstacleCleared(ObstacleKind),
+    PowerUpCollected(PowerUpKind),
+    PowerUpExpired(PowerUpKind),
+    /// The shield absorbed a hit and is gone.
+    ShieldBroken(ObstacleKind),
+    CoinCollected,
+}
+
+pub struct World {
+    state: GameState,
+    seed: u64,
+    rng: Rng,
+    tick: u32,
+    elapsed_ms: u32,
+    distance: f32,
+    top_speed: f32,
+    jumps: u32,
+    death_tick: Option<u32>,
+    perks: Perks,
+    difficulty: Difficulty,
+    events: Vec<Event>,
+    #[cfg(feature = "cheats")]
+    invincible: bool,
+    pub ground: Ground,
+    pub dino: Dino,
+    pub lives: Lives,
+    pub obstacles: Obstacles,
+    pub power_ups: PowerUps,
+    pub coins: Coins,
+    pub scoreboard: Scoreboard,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self {
+            state: GameState::Ready,
+            seed: 0,
+            rng: Rng::new(0),
+            tick: 0,
+            elapsed_ms: 0,
+            distance: 0.0,
+            top_speed: 0.0,
+            jumps: 0,
+            death_tick: None,
+            perks: Perks::NONE,
+            difficulty: Difficulty::Normal,
+            events: vec![],
+            #[cfg(feature = "cheats")]
+            invincible: false,
+            ground: Ground::new(),
+            dino: Dino::new(),
+            lives: Lives::default(),
+            obstacles: Obstacles::new(),
+            power_ups: PowerUps::new(),
+            coins: Coins::new(),
+            scoreboard: Scoreboard::new(),
+        }
+    }
+
+    pub fn get_state(&self) -> GameState {
+        self.state
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Number of updates since the run started.
+    pub fn get_tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Time played since the run started, in milliseconds.
+    pub fn get_elapsed_ms(&self) -> u32 {
+        self.elapsed_ms
+    }
+
+    /// Pixels the ground moved since the run started.
+    pub fn get_distance(&self) -> f32 {
+        self.distance
+    }
+
+    /// The ground's fastest speed in the run, in pixels per second.
+    pub fn get_top_speed(&self) -> f32 {
+        self.top_speed
+    }
+
+    /// Jumps in the run, double jumps included.
+    pub fn get_jumps(&self) -> u32 {
+        self.jumps
+    }
+
+    pub fn get_death_tick(&self) -> Option<u32> {
+        self.death_tick
+    }
+
+    pub fn get_perks(&self) -> Perks {
+        self.perks
+    }
+
+    /// Perks for the runs to come, applied by [`World::start`].
+    pub fn set_perks(&mut self, perks: Perks) {
+        self.perks = perks;
+    }
+
+    pub fn get_difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// The difficulty of the runs to come, applied by [`World::start`].
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Resets the world and starts a new run. Everything random in the run is
+    /// derived from `seed`.
+    pub fn start(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = Rng::new(seed);
+        self.tick = 0;
+        self.elapsed_ms = 0;
+        self.distance = 0.0;
+        self.top_speed = 0.0;
+        self.jumps = 0;
+        self.death_tick = None;
+        self.events.clear();
+        self.ground
+            .set_speed_scale(self.difficulty.get_speed_scale());
+        self.ground.reset();
+        self.dino.reset();
+        self.lives.reset();
+        self.obstacles.reset();
+        self.power_ups.reset();
+        self.coins.reset();
+        self.scoreboard.reset();
+        if self.perks.contains(Perks::EXTRA_LIFE) {
+            self.lives.grant(1);
+        }
+        if self.perks.contains(Perks::SHIELD) {
+            self.power_ups.activate(PowerUpKind::Shield);
+        }
+        self.state = GameState::Playing;
+    }
+
+    pub fn update(&mut self, input: Input, delta_ms: u32) {
+        self.events.clear();
+        self.step(input, delta_ms);
+    }
+
+    /// Runs a frame of `delta_ms` as updates of at most [`MAX_FRAME_MS`], and
+    /// calls `on_update` after each one of a run, e.g. to record it. Buttons
+    /// pushed this frame only count in the first update. The events of all of
+    /// them are kept.
+    pub fn advance(
+        &mut self,
+        mut input: Input,
+        delta_ms: u32,
+        mut on_update: impl FnMut(Input, u32, &World),
+    ) {
+        self.events.clear();
+        let mut remaining = delta_ms;
+        loop {
+            let was_playing = self.state == GameState::Playing;
+            let step_ms = remaining.min(MAX_FRAME_MS);
+            self.step(input, step_ms);
+            if was_playing {
+                on_update(input, step_ms, self);
+            }
+            remaining -= step_ms;
+            if remaining == 0 || self.state != GameState::Playing {
+                break;
+            }
+            input.pushed = Buttons::NONE;
+        }
+    }
+
+    fn step(&mut self, input: Input, delta_ms: u32) {
+        let delta = delta_ms as f32 / 1000.0;
+        // slow-time only slows the world down, the score keeps counting
+        let world_delta = delta * self.power_ups.get_time_scale();
+        let was_playing = self.state == GameState::Playing;
+        if was_playing {
+            self.distance += self.ground.get_velocity() * world_delta;
+            self.ground.update(world_delta);
+            self.top_speed = self.top_speed.max(self.ground.get_velocity());
+            self.lives.update(delta);
+        }
+        self.update_dino(&input, world_delta);
+        let spawned = self.obstacles.update(
+            self.state,
+            self.ground.get_velocity(),
+            world_delta,
+            &mut self.rng,
+            &mut self.events,
+        );
+        if let Some(obstacle) = spawned {
+            self.power_ups.spawn_over(obstacle, &mut self.rng);
+            self.coins.spawn_over(obstacle, &mut self.rng);
+        }
+        self.coins
+            .update(self.state, self.ground.get_velocity(), world_delta);
+        self.power_ups.update(
+            self.state,
+            self.ground.get_velocity(),
+            delta,
+            world_delta,
+            &mut self.events,
+        );
+        self.scoreboard.update(self.state, delta, &mut self.events);
+        if was_playing {
+            self.tick += 1;
+            self.elapsed_ms += delta_ms;
+        }
+    }
+
+    fn update_dino(&mut self, input: &Input, delta: f32) {
+        let old_state = self.dino.update_state(self.state, input);
+        let state = self.dino.get_state();
+        if self.state != GameState::Playing {
+            return;
+        }
+        if (old_state != DinoState::Jump && state == DinoState::Jump)
+            || old_state == DinoState::Idle
+        {
+            self.jumps += 1;
+            self.events.push(Event::Jumped);
+        }
+        if old_state != DinoState::Duck && state == DinoState::Duck {
+            self.events.push(Event::Ducked);
+        }
+        if old_state == DinoState::Jump
+            && input.pushed.contains(Buttons::A)
+            && self.power_ups.is_active(PowerUpKind::DoubleJump)
+            && self.dino.can_double_jump()
+        {
+            self.dino.double_jump();
+            self.jumps += 1;
+            self.events.push(Event::Jumped);
+        }
+        let goal = self.dino.step(old_state, delta);
+        let hit = self
+            .obstacles
+            .check_collision(self.dino.hitboxes(), &goal)
+            .map(|o| (o.get_id(), o.get_kind()));
+        // it isn't cleared, even if nothing happens
+        let hit = hit.map(|(id, kind)| {
+            self.obstacles.mark_hit(id);
+            kind
+        });
+        match hit {
+            Some(_) if self.lives.is_invulnerable() || self.is_invincible() => {}
+            Some(kind) if self.power_ups.is_active(PowerUpKind::Shield) => {
+                self.power_ups.consume(PowerUpKind::Shield);
+                self.lives.make_invulnerable(args::INVULNERABLE_SECS);
+                self.events.push(Event::ShieldBroken(kind));
+            }
+            Some(kind) if self.lives.hit() => {
+                self.dino.hurt();
+                self.events.push(Event::Hurt(kind));
+            }
+            Some(kind) => {
+                self.state = GameState::Dead;
+                self.death_tick = Some(self.tick);
+                self.events.push(Event::Died(kind));
+                return;
+            }
+            None => {}
+        }
+        self.dino.move_to(goal);
+        self.coins
+            .collect(&self.dino.get_bounds(), &mut self.events);
+        let collected = self
+            .power_ups
+            .collect(self.dino.hitboxes(), &self.dino.get_bounds());
+        for kind in collected {
+            self.power_ups.activate(kind);
+            self.events.push(Event::PowerUpCollected(kind));
+        }
+    }
+
+    #[cfg(not(feature = "cheats"))]
+    fn is_invincible(&self) -> bool {
+        false
+    }
+
+    /// A hash of everything that influences the rest of the run. Replays store
+    /// it periodically so a verifier can tell where a re-simulation diverges.
+    pub fn checksum(&self) -> u32 {
+        let mut hash = Fnv::new();
+        hash.write(self.state as u32);
+        hash.write(self.tick);
+        hash.write(self.dino.get_state() as u32);
+        hash.write(self.dino.get_bounds().y.to_bits());
+        hash.write(self.dino.get_vertical_velocity().to_bits());
+        hash.write(self.lives.get_remaining());
+        hash.write(self.lives.get_invulnerable_time().to_bits());
+        hash.write(self.ground.get_offset().to_bits());
+        hash.write(self.ground.get_velocity().to_bits());
+        for obstacle in self.obstacles.iter() {
+            hash.write(obstacle.get_id());
+            hash.write(obstacle.get_kind() as u32);
+            hash.write(obstacle.get_bounds().x.to_bits());
+        }
+        for power_up in self.power_ups.iter() {
+            hash.write(power_up.get_id());
+            hash.write(power_up.get_kind() as u32);
+            hash.write(power_up.get_bounds().x.to_bits());
+        }
+        for (kind, time_left) in self.power_ups.active() {
+            hash.write(kind as u32);
+            hash.write(time_left.to_bits());
+        }
+        for coin in self.coins.iter() {
+            hash.write(coin.get_id());
+            hash.write(coin.get_bounds().x.to_bits());
+        }
+        hash.write(self.coins.get_collected());
+        hash.write(self.scoreboard.get_time().to_bits());
+        hash.finish()
+    }
+}
+
+/// Controls for the game's developer cheat console.
+#[cfg(feature = "cheats")]
+impl World {
+    pub fn is_invincible(&self) -> bool {
+        self.invincible
+    }
+
+    /// Obstacles pass through the dino, and it keeps its lives.
+    pub fn set_invincible(&mut self, invincible: bool) {
+        self.invincible = invincible;
+    }
+
+    /// Spawns an obstacle at the right edge of the screen right away.
+    pub fn spawn_obstacle(&mut self, kind: ObstacleKind) {
+        self.obstacles.spawn(kind, &mut self.events);
+    }
+
+    /// A readable summary of the world, one line per part.
+    pub fn dump(&self) -> String {
+        let dino = &self.dino;
+        let bounds = dino.get_bounds();
+        let mut text = format!(
+            "state {:?}, tick {}, seed {:016x}\n\
+             score {}, lives {}/{}, invincible {}\n\
+             dino {:?} at {},{} vy {}\n\
+             ground velocity {}, offset {}\n",
+            self.state,
+            self.tick,
+            self.seed,
+            self.scoreboard.get_score(),
+            self.lives.get_remaining(),
+            self.lives.get_max(),
+            self.invincible,
+            dino.get_state(),
+            bounds.x,
+            bounds.y,
+            dino.get_vertical_velocity(),
+            self.ground.get_velocity(),
+            self.ground.get_offset(),
+        );
+        for obstacle in self.obstacles.iter() {
+            let bounds = obstacle.get_bounds();
+            text += &format!(
+                "obstacle {} {} at {},{}\n",
+                obstacle.get_id(),
+                obstacle.get_kind().get_key(),
+                bounds.x,
+                bounds.y
+            );
+        }
+        for power_up in self.power_ups.iter() {
+            let bounds = power_up.get_bounds();
+            text += &format!(
+                "power-up {} {} at {},{}\n",
+                power_up.get_id(),
+                power_up.get_kind().get_name(),
+                bounds.x,
+                bounds.y
+            );
+        }
+        for (kind, time_left) in self.power_ups.active() {
+            text += &format!("active {} {}s\n", kind.get_name(), time_left);
+        }
+        text += &format!(
+            "coins {} on screen, {} collected",
+            self.coins.iter().count(),
+            self.coins.get_collected()
+        );
+        text
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 32-bit FNV-1a, for checksums that have to match across platforms.
+pub(crate) struct Fnv(u32);
+
+impl Fnv {
+    pub(crate) fn new() -> Self {
+        Self(0x811c9dc5)
+    }
+
+    pub(crate) fn write(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u32;
+            self.0 = self.0.wrapping_mul(0x01000193);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u32 {
+        self.0
+    }
+}
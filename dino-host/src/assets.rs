@@ -0,0 +1,142 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use dino_core::{
+    obstacle::ObstacleKind,
+    power_up::PowerUpKind,
+    skin::{Accessory, SkinTable, CLASSIC_TABLE, GHOST_TABLE},
+    theme::{Theme, MANIFEST},
+};
+
+use crate::Bitmap;
+
+/// The game's images, loaded from the same `assets/` folder the `.pdx` is
+/// built from and scaled to their on-screen size.
+pub struct Assets {
+    pub dino_tables: Vec<(&'static str, Vec<Bitmap>)>,
+    pub accessories: Vec<(Accessory, Bitmap)>,
+    pub bird: Vec<Bitmap>,
+    pub cacti: Vec<(ObstacleKind, Bitmap)>,
+    pub ground: Bitmap,
+    pub cloud: Bitmap,
+    pub game_over: Bitmap,
+    pub restart: Bitmap,
+    pub heart: Bitmap,
+    pub coin: Bitmap,
+    pub power_ups: Vec<(PowerUpKind, Bitmap)>,
+}
+
+impl Assets {
+    pub fn default_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets")
+    }
+
+    pub fn load(dir: impl AsRef<Path>) -> io::Result<Self> {
+        Self::load_with_theme(dir, &Theme::default())
+    }
+
+    /// Loads the images `theme` replaces from its pack, and the others from
+    /// `dir`. Images missing from the pack fall back to the bundled ones.
+    pub fn load_with_theme(dir: impl AsRef<Path>, theme: &Theme) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        let open_file = |name: &str, suffix: &str| {
+            let path = dir.join(format!("{}{}.png", name, suffix));
+            Bitmap::open(&path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+        };
+        // tables are named `<name>-table-<width>-<height>`
+        let open_with_suffix = |name: &str, suffix: &str| {
+            open_file(&theme.image_path(name), suffix).or_else(|_| open_file(name, suffix))
+        };
+        let open = |name: &str| open_with_suffix(name, "");
+        let cactus = |kind, name: &str| open(name).map(|b| (kind, b.scaled(0.5)));
+        let power_up = |kind: PowerUpKind| open(kind.get_path()).map(|b| (kind, b.scaled(0.5)));
+        let dino_table = |table: &SkinTable| {
+            let suffix = format!("-table-{}-{}", table.width, table.height);
+            let frames = open_with_suffix(table.path, &suffix)?.split(table.count as u32);
+            Ok::<_, io::Error>((table.path, frames.iter().map(|b| b.scaled(0.5)).collect()))
+        };
+        let accessory = |a: Accessory| open(a.get_path()).map(|b| (a, b.scaled(0.5)));
+        Ok(Self {
+            dino_tables: vec![dino_table(&CLASSIC_TABLE)?, dino_table(&GHOST_TABLE)?],
+            accessories: vec![accessory(Accessory::TopHat)?, accessory(Accessory::Crown)?],
+            bird: open_with_suffix("bird", "-table-92-68")?
+                .split(2)
+                .iter()
+                .map(|b| b.scaled(0.5))
+                .collect(),
+            cacti: vec![
+                cactus(ObstacleKind::CactusSmall1, "cactus/cactus-small-1")?,
+                cactus(ObstacleKind::CactusSmall2, "cactus/cactus-small-2")?,
+                cactus(ObstacleKind::CactusSmall3, "cactus/cactus-small-3")?,
+                cactus(ObstacleKind::CactusBig1, "cactus/cactus-big-1")?,
+                cactus(ObstacleKind::CactusBig2, "cactus/cactus-big-2")?,
+                cactus(ObstacleKind::CactusBig3, "cactus/cactus-big-3")?,
+            ],
+            ground: open("ground")?,
+            cloud: open("cloud")?,
+            game_over: open("game-over")?.scaled(0.5),
+            restart: open("restart")?.scaled(0.5),
+            heart: open("heart")?.scaled(0.5),
+            coin: open("coin")?.scaled(0.5),
+            power_ups: vec![
+                power_up(PowerUpKind::Shield)?,
+                power_up(PowerUpKind::DoubleJump)?,
+                power_up(PowerUpKind::SlowTime)?,
+            ],
+        })
+    }
+
+    /// The image of an obstacle. Birds are animated, cacti ignore `frame`.
+    pub fn obstacle(&self, kind: ObstacleKind, frame: usize) -> &Bitmap {
+        if kind.is_bird() {
+            return &self.bird[frame % self.bird.len()];
+        }
+        self.cacti
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, b)| b)
+            .unwrap()
+    }
+
+    /// Reads the manifest of the theme pack in `dir`.
+    pub fn load_theme(dir: impl AsRef<Path>) -> io::Result<Theme> {
+        let dir = dir.as_ref();
+        let path = dir.join(MANIFEST);
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        Theme::parse(&dir.to_string_lossy(), &text).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })
+    }
+
+    /// A frame of a dino skin's table.
+    pub fn dino(&self, table: &SkinTable, frame: usize) -> &Bitmap {
+        self.dino_tables
+            .iter()
+            .find(|(path, _)| *path == table.path)
+            .map(|(_, frames)| &frames[frame])
+            .unwrap()
+    }
+
+    pub fn accessory(&self, accessory: Accessory) -> &Bitmap {
+        self.accessories
+            .iter()
+            .find(|(a, _)| *a == accessory)
+            .map(|(_, b)| b)
+            .unwrap()
+    }
+
+    pub fn power_up(&self, kind: PowerUpKind) -> &Bitmap {
+        self.power_ups
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, b)| b)
+            .unwrap()
+    }
+}
@@ -0,0 +1,210 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use dino_core::math::Rect;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Color {
+    Black,
+    White,
+    Clear,
+}
+
+/// A 1-bit image with a transparency mask, like a Playdate `LCDBitmap`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Bitmap {
+    width: u32,
+    height: u32,
+    pixels: Vec<Color>,
+}
+
+impl Bitmap {
+    pub fn new(width: u32, height: u32, color: Color) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color; (width * height) as usize],
+        }
+    }
+
+    /// Loads a PNG the way the Playdate compiler converts it: transparent
+    /// pixels become clear, everything else black or white by luminance.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut decoder = png::Decoder::new(File::open(path)?);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf)?;
+        let channels = info.color_type.samples();
+        let pixels = buf[..info.buffer_size()]
+            .chunks(channels)
+            .map(|p| {
+                let (luma, alpha) = match p {
+                    [l] => (*l as u32, 255),
+                    [l, a] => (*l as u32, *a),
+                    [r, g, b] => ((*r as u32 * 3 + *g as u32 * 6 + *b as u32) / 10, 255),
+                    [r, g, b, a] => ((*r as u32 * 3 + *g as u32 * 6 + *b as u32) / 10, *a),
+                    _ => unreachable!(),
+                };
+                if alpha < 128 {
+                    Color::Clear
+                } else if luma < 128 {
+                    Color::Black
+                } else {
+                    Color::White
+                }
+            })
+            .collect();
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    /// Splits a horizontal strip of `count` frames, like `BitmapTable::open`.
+    pub fn split(&self, count: u32) -> Vec<Bitmap> {
+        let width = self.width / count;
+        (0..count)
+            .map(|i| self.crop(i * width, 0, width, self.height))
+            .collect()
+    }
+
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Bitmap {
+        let mut out = Bitmap::new(width, height, Color::Clear);
+        for dy in 0..height {
+            for dx in 0..width {
+                out.set(dx as i32, dy as i32, self.get(x + dx, y + dy));
+            }
+        }
+        out
+    }
+
+    /// Nearest-neighbour scaling, like `draw_scaled_bitmap`.
+    pub fn scaled(&self, scale: f32) -> Bitmap {
+        let width = (self.width as f32 * scale) as u32;
+        let height = (self.height as f32 * scale) as u32;
+        let mut out = Bitmap::new(width, height, Color::Clear);
+        for y in 0..height {
+            for x in 0..width {
+                let sx = (x as f32 / scale) as u32;
+                let sy = (y as f32 / scale) as u32;
+                out.set(x as i32, y as i32, self.get(sx, sy));
+            }
+        }
+        out
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> Color {
+        if x >= self.width || y >= self.height {
+            return Color::Clear;
+        }
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    pub fn set(&mut self, x: i32, y: i32, color: Color) {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return;
+        }
+        self.pixels[(y as u32 * self.width + x as u32) as usize] = color;
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        self.pixels.fill(color);
+    }
+
+    /// Draws `bitmap` with its top-left corner at `(x, y)`. Clear pixels are
+    /// skipped.
+    pub fn draw(&mut self, bitmap: &Bitmap, x: i32, y: i32) {
+        for sy in 0..bitmap.height {
+            for sx in 0..bitmap.width {
+                let color = bitmap.get(sx, sy);
+                if color != Color::Clear {
+                    self.set(x + sx as i32, y + sy as i32, color);
+                }
+            }
+        }
+    }
+
+    pub fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let x0 = rect.x as i32;
+        let y0 = rect.y as i32;
+        for y in y0..y0 + rect.height as i32 {
+            for x in x0..x0 + rect.width as i32 {
+                self.set(x, y, color);
+            }
+        }
+    }
+
+    /// Swaps black and white, like `display.set_inverted`.
+    pub fn invert(&mut self) {
+        for p in &mut self.pixels {
+            *p = match p {
+                Color::Black => Color::White,
+                Color::White => Color::Black,
+                Color::Clear => Color::Clear,
+            };
+        }
+    }
+
+    /// Whether the pixel shows up black on the screen. Clear pixels show the
+    /// white background.
+    pub fn is_black(&self, x: u32, y: u32) -> bool {
+        self.get(x, y) == Color::Black
+    }
+
+    /// Packs the pixels into rows of 1-bit samples, most significant bit
+    /// first. `black` is the bit written for black (and clear is white).
+    fn pack_rows(&self, black: bool) -> Vec<u8> {
+        let stride = self.width.div_ceil(8) as usize;
+        let mut data = vec![0; stride * self.height as usize];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.is_black(x, y) == black {
+                    data[y as usize * stride + x as usize / 8] |= 0x80 >> (x % 8);
+                }
+            }
+        }
+        data
+    }
+
+    /// Writes a binary PBM (P4) image.
+    pub fn write_pbm(&self, mut writer: impl Write) -> io::Result<()> {
+        write!(writer, "P4\n{} {}\n", self.width, self.height)?;
+        writer.write_all(&self.pack_rows(true))
+    }
+
+    /// Writes a 1-bit grayscale PNG. Clear pixels come out white, as they
+    /// do on the screen.
+    pub fn write_png(&self, writer: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::One);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pack_rows(false))?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Saves the image as PBM if `path` ends in `.pbm`, and as PNG otherwise.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let writer = BufWriter::new(File::create(path)?);
+        if path.extension().is_some_and(|e| e == "pbm") {
+            self.write_pbm(writer)
+        } else {
+            self.write_png(writer)
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Derives collision boxes from the sprite images, for `hitbox-tool` and for
+//! the check that [`dino_core::hitbox`] still matches the bundled sprites.
+//!
+//! Every sprite is scaled to its on-screen size and covered, top to bottom,
+//! by bands of rows whose opaque pixels start and end within `tolerance`
+//! pixels of the band's first row. Frames of an animation share one set of
+//! boxes that covers all of them.
+
+use std::{io, path::Path};
+
+use dino_core::{math::Rect, obstacle::ObstacleKind, skin::CLASSIC_TABLE};
+
+use crate::{Bitmap, Color};
+
+/// The tolerance the bundled boxes are derived with.
+pub const DEFAULT_TOLERANCE: u32 = 3;
+
+/// A sprite and where its boxes go.
+pub struct Sprite {
+    /// The theme manifest entry, like `dino standing` or
+    /// `obstacle cactus-small-1 17 35`.
+    pub entry: String,
+    /// The constant in [`dino_core::hitbox`], like `CACTUS_SMALL_1`.
+    pub constant: String,
+    /// Frames at screen scale, all the same size.
+    pub frames: Vec<Bitmap>,
+}
+
+impl Sprite {
+    pub fn is_opaque(&self, x: u32, y: u32) -> bool {
+        self.frames.iter().any(|f| f.get(x, y) != Color::Clear)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.frames[0].width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.frames[0].height()
+    }
+}
+
+/// The first and last opaque column of every row.
+fn row_extents(sprite: &Sprite) -> Vec<Option<(u32, u32)>> {
+    (0..sprite.height())
+        .map(|y| {
+            let mut opaque = (0..sprite.width()).filter(|x| sprite.is_opaque(*x, y));
+            let first = opaque.next()?;
+            Some((first, opaque.next_back().unwrap_or(first)))
+        })
+        .collect()
+}
+
+/// First row, first row's extent and union of the extents of a band.
+type Band = (u32, (u32, u32), (u32, u32));
+
+/// Covers the opaque pixels with bands of rows.
+pub fn cover(sprite: &Sprite, tolerance: u32) -> Vec<Rect> {
+    let mut band: Option<Band> = None;
+    let mut boxes = vec![];
+    let close = |band: Band, end: u32, boxes: &mut Vec<Rect>| {
+        let (top, _, (left, right)) = band;
+        boxes.push(Rect::new(
+            left as f32,
+            top as f32,
+            (right - left + 1) as f32,
+            (end - top) as f32,
+        ));
+    };
+    for (y, extent) in row_extents(sprite).into_iter().enumerate() {
+        let y = y as u32;
+        band = match (band, extent) {
+            (Some((top, first, union)), Some((left, right)))
+                if left.abs_diff(first.0) <= tolerance && right.abs_diff(first.1) <= tolerance =>
+            {
+                Some((top, first, (union.0.min(left), union.1.max(right))))
+            }
+            (band, extent) => {
+                if let Some(band) = band {
+                    close(band, y, &mut boxes);
+                }
+                extent.map(|e| (y, e, e))
+            }
+        };
+    }
+    if let Some(band) = band {
+        close(band, sprite.height(), &mut boxes);
+    }
+    boxes
+}
+
+/// The dino's two poses and every obstacle, in the order of
+/// [`dino_core::hitbox`]. Both birds share one sprite.
+pub fn load_sprites(dir: impl AsRef<Path>) -> io::Result<Vec<Sprite>> {
+    let dir = dir.as_ref();
+    let open = |name: &str| {
+        let path = dir.join(format!("{name}.png"));
+        Bitmap::open(&path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))
+    };
+    let table = CLASSIC_TABLE;
+    let dino = open(&format!(
+        "{}-table-{}-{}",
+        table.path, table.width, table.height
+    ))?
+    .split(table.count as u32);
+    let dino_frames = |frames: &[&[usize]]| {
+        let mut frames: Vec<usize> = frames.concat();
+        frames.sort();
+        frames.dedup();
+        frames.iter().map(|i| dino[*i].scaled(0.5)).collect()
+    };
+    let map = table.frames;
+    let mut sprites = vec![
+        Sprite {
+            entry: "dino standing".into(),
+            constant: "DINO_STANDING".into(),
+            frames: dino_frames(&[map.idle.frames, map.jump.frames, map.run.frames]),
+        },
+        Sprite {
+            entry: "dino ducking".into(),
+            constant: "DINO_DUCKING".into(),
+            frames: dino_frames(&[map.duck.frames]),
+        },
+    ];
+    let bird: Vec<Bitmap> = open("bird-table-92-68")?
+        .split(2)
+        .iter()
+        .map(|b| b.scaled(0.5))
+        .collect();
+    for kind in ObstacleKind::ALL {
+        let (key, frames) = match kind {
+            ObstacleKind::Bird => ("bird", bird.clone()),
+            ObstacleKind::LowBird => continue,
+            _ => (
+                kind.get_key(),
+                vec![open(&format!("cactus/{}", kind.get_key()))?.scaled(0.5)],
+            ),
+        };
+        let (width, height) = (frames[0].width(), frames[0].height());
+        sprites.push(Sprite {
+            entry: format!("obstacle {key} {width} {height}"),
+            constant: key.to_uppercase().replace('-', "_"),
+            frames,
+        });
+    }
+    Ok(sprites)
+}
+
+#[cfg(test)]
+mod tests {
+    use dino_core::dino::DinoShape;
+
+    use super::*;
+    use crate::Assets;
+
+    #[test]
+    fn bundled_hitboxes_match_the_sprites() {
+        let shape = DinoShape::default();
+        let mut bundled = vec![shape.standing, shape.ducking];
+        bundled.extend(
+            ObstacleKind::ALL
+                .into_iter()
+                .filter(|k| *k != ObstacleKind::LowBird)
+                .map(|k| k.default_shape().hitboxes),
+        );
+        let sprites = load_sprites(Assets::default_dir()).unwrap();
+        assert_eq!(sprites.len(), bundled.len());
+        for (sprite, boxes) in sprites.iter().zip(bundled) {
+            assert_eq!(
+                cover(sprite, DEFAULT_TOLERANCE),
+                boxes,
+                "{} is out of date, regenerate dino-core/src/hitbox.rs with \
+                 `cargo run -p hitbox-tool -- --rust`",
+                sprite.constant
+            );
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Runs Dino on a workstation.
+//!
+//! The gameplay comes from `dino-core`, exactly as on the device. This crate
+//! only replaces the Playdate's sprites and frame buffer with a software
+//! renderer, so frontends like the terminal build can show the game.
+
+pub mod assets;
+pub mod bitmap;
+pub mod hitbox;
+pub mod scene;
+pub mod text;
+
+pub use assets::Assets;
+pub use bitmap::{Bitmap, Color};
+pub use scene::Scene;
@@ -0,0 +1,549 @@
+use dino_core::{
+    args,
+    dino::DinoState,
+    display::{DISPLAY_HEIGHT, DISPLAY_WIDTH},
+    ground::Ground,
+    layout,
+    math::Rect,
+    obstacle::ObstacleKind,
+    profile::ProfileMenu,
+    rng::Rng,
+    scores::{HighScores, InitialsEntry, Mode, ScoreBrowser},
+    settings::{OptionRow, Options, Settings},
+    shop::{Item, Shop, Wallet},
+    skin::{Skin, Wardrobe},
+    stats::{Stats, StatsBrowser, StatsPage},
+    GameState, World,
+};
+
+use crate::{text, Assets, Bitmap, Color};
+
+struct Animation {
+    frames: &'static [usize],
+    frame_time: f32,
+    current_frame: usize,
+    current_time: f32,
+}
+
+impl Animation {
+    fn new(frames: &'static [usize], frame_time: f32) -> Self {
+        Self {
+            frames,
+            frame_time,
+            current_frame: 0,
+            current_time: 0.0,
+        }
+    }
+
+    fn for_dino(skin: Skin, state: DinoState) -> Self {
+        let animation = skin.get_table().frames.get(state);
+        Self::new(animation.frames, animation.frame_time)
+    }
+
+    fn update(&mut self, delta: f32) {
+        self.current_time += delta;
+        if self.current_time >= self.frame_time {
+            self.current_frame += 1;
+            if self.current_frame >= self.frames.len() {
+                self.current_frame = 0;
+            }
+            self.current_time = 0.0;
+        }
+    }
+
+    fn frame(&self) -> usize {
+        self.frames[self.current_frame]
+    }
+}
+
+struct MessageBox {
+    text: String,
+    center: (f32, f32),
+    elapsed: f32,
+    visible: bool,
+}
+
+impl MessageBox {
+    fn new(text: impl Into<String>, center: (f32, f32)) -> Self {
+        Self {
+            text: text.into(),
+            center,
+            elapsed: 0.0,
+            visible: false,
+        }
+    }
+
+    fn update(&mut self, delta: f32, visible: bool) {
+        if !visible {
+            self.visible = false;
+            return;
+        }
+        self.elapsed += delta;
+        self.visible = (self.elapsed as i32) & 1 == 0;
+    }
+
+    fn draw(&self, target: &mut Bitmap) {
+        if !self.visible {
+            return;
+        }
+        let width = text::get_text_width(&self.text) as f32;
+        let height = text::get_height() as f32;
+        let (x, y) = layout::centered(self.center, width, height);
+        target.fill_rect(Rect::new(x, y, width, height), Color::White);
+        text::draw_text(target, &self.text, x as i32, y as i32);
+    }
+}
+
+/// The host counterpart of the device's sprites: it follows a [`World`] and
+/// draws what the Playdate would show for it.
+pub struct Scene {
+    assets: Assets,
+    rng: Rng,
+    clouds: Vec<Rect>,
+    skin: Skin,
+    dino_state: DinoState,
+    dino_animation: Animation,
+    bird_animation: Animation,
+    last_obstacle_id: Option<u32>,
+    mask: (f32, f32),
+    start_message: MessageBox,
+    shop_message: MessageBox,
+    high_scores_message: MessageBox,
+    profile_message: Option<MessageBox>,
+    skin_message: Option<MessageBox>,
+    restart_message: MessageBox,
+    last_invert_time: f32,
+    inverted: bool,
+}
+
+impl Scene {
+    pub fn new(assets: Assets, seed: u64) -> Self {
+        Self {
+            assets,
+            rng: Rng::new(seed),
+            clouds: vec![],
+            skin: Skin::Classic,
+            dino_state: DinoState::Idle,
+            dino_animation: Animation::for_dino(Skin::Classic, DinoState::Idle),
+            bird_animation: Animation::new(&[0, 1], 0.1),
+            last_obstacle_id: None,
+            mask: (layout::MASK_LEFT_X, layout::MASK_RIGHT_X),
+            start_message: MessageBox::new(layout::START_MESSAGE, layout::START_MESSAGE_CENTER),
+            shop_message: MessageBox::new(layout::SHOP_MESSAGE, layout::SHOP_MESSAGE_CENTER),
+            high_scores_message: MessageBox::new(
+                layout::HIGH_SCORES_MESSAGE,
+                layout::HIGH_SCORES_MESSAGE_CENTER,
+            ),
+            profile_message: None,
+            skin_message: None,
+            restart_message: MessageBox::new(
+                layout::RESTART_MESSAGE,
+                layout::RESTART_MESSAGE_CENTER,
+            ),
+            last_invert_time: 0.0,
+            inverted: false,
+        }
+    }
+
+    /// Call right after [`World::start`].
+    pub fn reset(&mut self) {
+        self.clouds.clear();
+        self.dino_state = DinoState::Idle;
+        self.dino_animation = Animation::for_dino(self.skin, DinoState::Idle);
+        self.last_obstacle_id = None;
+        self.last_invert_time = 0.0;
+    }
+
+    /// Switches to the wardrobe's skin. The picker is shown between runs
+    /// once more than one skin is unlocked.
+    pub fn set_wardrobe(&mut self, wardrobe: &Wardrobe) {
+        self.skin = wardrobe.get_selected();
+        self.dino_animation = Animation::for_dino(self.skin, self.dino_state);
+        self.skin_message = (wardrobe.unlocked().count() > 1)
+            .then(|| MessageBox::new(layout::skin_text(self.skin), layout::SKIN_MESSAGE_CENTER));
+    }
+
+    /// Shows the profile in use between runs.
+    pub fn set_profile(&mut self, name: &str) {
+        self.profile_message = Some(MessageBox::new(
+            layout::profile_text(name),
+            layout::PROFILE_MESSAGE_CENTER,
+        ));
+    }
+
+    /// Starts the next run at night, for the night start cosmetic.
+    pub fn start_at_night(&mut self) {
+        self.inverted = true;
+    }
+
+    /// Whether the display is inverted for the night cycle.
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    pub fn update(&mut self, world: &World, delta: f32) {
+        let playing = world.get_state() == GameState::Playing;
+        // day and night
+        if playing {
+            self.last_invert_time += delta;
+            if self.last_invert_time > args::DAY_NIGHT_CYCLE_SECS as f32 {
+                self.inverted = !self.inverted;
+                self.last_invert_time = 0.0;
+            }
+        }
+        self.update_clouds(world, delta);
+        // dino
+        let state = world.dino.get_state();
+        if state != self.dino_state {
+            self.dino_state = state;
+            self.dino_animation = Animation::for_dino(self.skin, state);
+        }
+        self.dino_animation.update(delta);
+        // obstacles
+        let obstacle_id = world.obstacles.iter().next().map(|o| o.get_id());
+        if obstacle_id != self.last_obstacle_id {
+            self.last_obstacle_id = obstacle_id;
+            self.bird_animation = Animation::new(&[0, 1], 0.1);
+        } else if playing {
+            self.bird_animation.update(delta);
+        }
+        // reveal the main scene
+        if playing {
+            if self.mask.0 + layout::MASK_WIDTH > 0.0 {
+                self.mask.0 -= layout::MASK_SPEED * delta;
+            }
+            if self.mask.1 < DISPLAY_WIDTH as f32 {
+                self.mask.1 += layout::MASK_SPEED * delta;
+            }
+        }
+        self.start_message
+            .update(delta, world.get_state() == GameState::Ready);
+        self.shop_message
+            .update(delta, world.get_state() == GameState::Ready);
+        self.high_scores_message
+            .update(delta, world.get_state() == GameState::Ready);
+        if let Some(message) = &mut self.profile_message {
+            message.update(delta, world.get_state() == GameState::Ready);
+        }
+        if let Some(message) = &mut self.skin_message {
+            message.update(delta, world.get_state() != GameState::Playing);
+        }
+        self.restart_message
+            .update(delta, world.get_state() == GameState::Dead);
+    }
+
+    fn update_clouds(&mut self, world: &World, delta: f32) {
+        if world.get_state() == GameState::Playing {
+            let velocity = world.ground.get_velocity() * 0.3;
+            for cloud in &mut self.clouds {
+                cloud.x -= velocity * delta;
+            }
+        }
+        self.clouds.retain(|c| c.right() >= 0.0);
+        if !self.clouds.iter().any(|c| c.right() > DISPLAY_WIDTH as f32) {
+            let x =
+                DISPLAY_WIDTH as f32 + self.rng.gen_f32(0.0..DISPLAY_WIDTH as f32 - 46.0 - 32.0);
+            let y = self.rng.gen_f32(32.0..DISPLAY_HEIGHT as f32 / 2.0);
+            self.clouds.push(Rect::new(x, y, 46.0, 14.0));
+        }
+    }
+
+    /// Draws the frame into `target`, which must be the size of the display.
+    /// The night inversion is not applied, see [`Scene::is_inverted`].
+    pub fn draw(&self, world: &World, record: i32, target: &mut Bitmap) {
+        target.clear(Color::White);
+        // ground and clouds
+        let ground_y = DISPLAY_HEIGHT as i32 - Ground::HEIGHT as i32;
+        let offset = world.ground.get_offset() as i32;
+        target.draw(&self.assets.ground, offset, ground_y);
+        target.draw(&self.assets.ground, offset + Ground::WIDTH as i32, ground_y);
+        for cloud in &self.clouds {
+            target.draw(&self.assets.cloud, cloud.x as i32, cloud.y as i32);
+        }
+        // dino and obstacles
+        if world.lives.is_blink_visible() {
+            let bounds = world.dino.get_bounds();
+            let table = self.skin.get_table();
+            let frame = self.dino_animation.frame();
+            target.draw(
+                self.assets.dino(table, frame),
+                bounds.x as i32,
+                bounds.y as i32,
+            );
+            if let Some(accessory) = self.skin.get_accessory() {
+                let (x, y) = accessory.get_position(table.anchors[frame]);
+                let image = self.assets.accessory(accessory);
+                target.draw(image, (bounds.x + x) as i32, (bounds.y + y) as i32);
+            }
+        }
+        for obstacle in world.obstacles.iter() {
+            let bounds = obstacle.get_bounds();
+            let image = self
+                .assets
+                .obstacle(obstacle.get_kind(), self.bird_animation.frame());
+            target.draw(image, bounds.x as i32, bounds.y as i32);
+        }
+        for coin in world.coins.iter() {
+            let bounds = coin.get_bounds();
+            target.draw(&self.assets.coin, bounds.x as i32, bounds.y as i32);
+        }
+        for power_up in world.power_ups.iter() {
+            let bounds = power_up.get_bounds();
+            let image = self.assets.power_up(power_up.get_kind());
+            target.draw(image, bounds.x as i32, bounds.y as i32);
+        }
+        // mask
+        let height = DISPLAY_HEIGHT as f32;
+        target.fill_rect(
+            Rect::new(self.mask.0, 0.0, layout::MASK_WIDTH, height),
+            Color::White,
+        );
+        target.fill_rect(
+            Rect::new(self.mask.1, 0.0, layout::MASK_WIDTH, height),
+            Color::White,
+        );
+        // scoreboard
+        let score = world.scoreboard.get_score();
+        let text = layout::score_text(record, score);
+        let (x, y) = layout::score_position(text::get_text_width(&text));
+        text::draw_text(target, &text, x, y);
+        if world.lives.get_max() > 1 {
+            for i in 0..world.lives.get_remaining() {
+                let (x, y) = layout::life_icon_position(x, i);
+                target.draw(&self.assets.heart, x, y);
+            }
+        }
+        let coins = world.coins.get_collected();
+        if coins > 0 {
+            let text = layout::coin_text(coins);
+            let (icon, pos) = layout::coin_counter_position(text::get_text_width(&text));
+            target.draw(&self.assets.coin, icon.0, icon.1);
+            text::draw_text(target, &text, pos.0, pos.1);
+        }
+        for (i, (kind, time_left)) in world.power_ups.active().enumerate() {
+            let text = layout::power_up_text(kind, time_left);
+            let (x, y) = layout::power_up_position(text::get_text_width(&text), i as u32);
+            text::draw_text(target, &text, x, y);
+        }
+        // restart panel
+        if world.get_state() == GameState::Dead {
+            let bounds = layout::GAME_OVER;
+            target.draw(&self.assets.game_over, bounds.x as i32, bounds.y as i32);
+            let bounds = layout::RESTART_ICON;
+            target.draw(&self.assets.restart, bounds.x as i32, bounds.y as i32);
+        }
+        self.start_message.draw(target);
+        self.shop_message.draw(target);
+        self.high_scores_message.draw(target);
+        if let Some(message) = &self.profile_message {
+            message.draw(target);
+        }
+        if let Some(message) = &self.skin_message {
+            message.draw(target);
+        }
+        self.restart_message.draw(target);
+    }
+
+    /// Draws the shop over the whole screen.
+    pub fn draw_shop(&self, shop: &Shop, wallet: &Wallet, target: &mut Bitmap) {
+        target.clear(Color::White);
+        let centered = |target: &mut Bitmap, text: &str, y: i32| {
+            let x = layout::centered_x(text::get_text_width(text));
+            text::draw_text(target, text, x, y);
+        };
+        centered(target, "SHOP", layout::SHOP_TITLE_Y);
+        centered(
+            target,
+            &layout::shop_coins_text(wallet),
+            layout::SHOP_COINS_Y,
+        );
+        for (i, item) in Item::ALL.iter().enumerate() {
+            let y = layout::shop_row_y(i);
+            let selected = *item == shop.get_selected();
+            let name = layout::shop_item_text(*item, wallet, selected);
+            text::draw_text(target, &name, layout::SHOP_LEFT_X, y);
+            let price = layout::shop_price_text(*item);
+            let x = layout::SHOP_RIGHT_X - text::get_text_width(&price) as i32;
+            text::draw_text(target, &price, x, y);
+        }
+        centered(target, shop.get_message(), layout::SHOP_MESSAGE_Y);
+        centered(target, layout::SHOP_HINT, layout::SHOP_HINT_Y);
+    }
+
+    /// Draws the options screen over the whole screen.
+    pub fn draw_options(&self, options: &Options, settings: &Settings, target: &mut Bitmap) {
+        target.clear(Color::White);
+        let centered = |target: &mut Bitmap, text: &str, y: i32| {
+            let x = layout::centered_x(text::get_text_width(text));
+            text::draw_text(target, text, x, y);
+        };
+        centered(target, "OPTIONS", layout::OPTIONS_TITLE_Y);
+        for (i, row) in OptionRow::ALL.iter().enumerate() {
+            let y = layout::options_row_y(i);
+            let selected = *row == options.get_selected();
+            let name = layout::options_row_text(*row, selected);
+            text::draw_text(target, &name, layout::OPTIONS_LEFT_X, y);
+            let value = settings.get_value_text(*row);
+            let x = layout::OPTIONS_RIGHT_X - text::get_text_width(&value) as i32;
+            text::draw_text(target, &value, x, y);
+        }
+        centered(target, layout::OPTIONS_HINT, layout::OPTIONS_HINT_Y);
+    }
+
+    /// Draws a high score table over the whole screen.
+    pub fn draw_high_scores(
+        &self,
+        browser: &ScoreBrowser,
+        high_scores: &HighScores,
+        target: &mut Bitmap,
+    ) {
+        target.clear(Color::White);
+        let centered = |target: &mut Bitmap, text: &str, y: i32| {
+            let x = layout::centered_x(text::get_text_width(text));
+            text::draw_text(target, text, x, y);
+        };
+        centered(target, "HIGH SCORES", layout::SCORES_TITLE_Y);
+        let mode = browser.get_mode();
+        centered(
+            target,
+            &layout::scores_mode_text(mode),
+            layout::SCORES_MODE_Y,
+        );
+        let entries = high_scores.get(mode);
+        if entries.is_empty() {
+            centered(target, layout::SCORES_EMPTY, layout::SCORES_ROWS_Y);
+        }
+        for (rank, entry) in entries.iter().enumerate() {
+            let y = layout::scores_row_y(rank);
+            let highlighted = browser.get_highlight() == Some(rank);
+            let [rank, initials, score, date] = layout::scores_row_texts(rank, entry, highlighted);
+            let x = layout::SCORES_RANK_RIGHT_X - text::get_text_width(&rank) as i32;
+            text::draw_text(target, &rank, x, y);
+            text::draw_text(target, &initials, layout::SCORES_INITIALS_X, y);
+            let x = layout::SCORES_SCORE_RIGHT_X - text::get_text_width(&score) as i32;
+            text::draw_text(target, &score, x, y);
+            text::draw_text(target, &date, layout::SCORES_DATE_X, y);
+        }
+        centered(target, layout::SCORES_HINT, layout::SCORES_HINT_Y);
+    }
+
+    /// Draws the lifetime stats over the whole screen.
+    pub fn draw_stats(&self, browser: &StatsBrowser, stats: &Stats, target: &mut Bitmap) {
+        target.clear(Color::White);
+        let centered = |target: &mut Bitmap, text: &str, y: i32| {
+            let x = layout::centered_x(text::get_text_width(text));
+            text::draw_text(target, text, x, y);
+        };
+        let right = |target: &mut Bitmap, text: &str, right_x: i32, y: i32| {
+            text::draw_text(target, text, right_x - text::get_text_width(text) as i32, y);
+        };
+        centered(target, layout::STATS_TITLE, layout::STATS_TITLE_Y);
+        let page = browser.get_page();
+        centered(target, &layout::stats_page_text(page), layout::STATS_PAGE_Y);
+        match page {
+            StatsPage::Totals => {
+                for (i, [label, value]) in layout::stats_total_rows(stats).iter().enumerate() {
+                    let y = layout::stats_row_y(i);
+                    text::draw_text(target, label, layout::STATS_LEFT_X, y);
+                    right(target, value, layout::STATS_RIGHT_X, y);
+                }
+            }
+            StatsPage::Obstacles => {
+                let header = layout::STATS_OBSTACLES_HEADER.map(String::from);
+                let rows = ObstacleKind::ALL
+                    .iter()
+                    .map(|kind| layout::stats_obstacle_row(*kind, stats));
+                for (i, [name, cleared, deaths]) in Some(header).into_iter().chain(rows).enumerate()
+                {
+                    let y = layout::stats_row_y(i);
+                    text::draw_text(target, &name, layout::STATS_LEFT_X, y);
+                    right(target, &cleared, layout::STATS_MIDDLE_RIGHT_X, y);
+                    right(target, &deaths, layout::STATS_RIGHT_X, y);
+                }
+            }
+        }
+        centered(target, layout::STATS_HINT, layout::STATS_HINT_Y);
+    }
+
+    /// Draws the initials entry for a new high score over the whole screen.
+    pub fn draw_initials(
+        &self,
+        entry: &InitialsEntry,
+        score: i32,
+        mode: Mode,
+        target: &mut Bitmap,
+    ) {
+        let text = layout::initials_score_text(score, mode);
+        Self::draw_letters(layout::INITIALS_TITLE, &text, entry, target);
+    }
+
+    /// Draws the profile switcher over the whole screen. `current` is the
+    /// index of the profile in use.
+    pub fn draw_profiles(
+        &self,
+        menu: &ProfileMenu,
+        names: &[String],
+        current: usize,
+        target: &mut Bitmap,
+    ) {
+        if let Some(entry) = menu.get_naming() {
+            let (title, text) = (layout::PROFILES_NAMING_TITLE, layout::PROFILES_NAMING_TEXT);
+            Self::draw_letters(title, text, entry, target);
+            return;
+        }
+        target.clear(Color::White);
+        let centered = |target: &mut Bitmap, text: &str, y: i32| {
+            let x = layout::centered_x(text::get_text_width(text));
+            text::draw_text(target, text, x, y);
+        };
+        centered(target, layout::PROFILES_TITLE, layout::PROFILES_TITLE_Y);
+        for (i, name) in names.iter().enumerate() {
+            let selected = i == menu.get_selected();
+            let row = layout::profiles_row_text(name, selected, i == current);
+            text::draw_text(
+                target,
+                &row,
+                layout::PROFILES_LEFT_X,
+                layout::profiles_row_y(i),
+            );
+        }
+        if ProfileMenu::can_create(names) {
+            let selected = menu.get_selected() == names.len();
+            let row = layout::profiles_row_text(layout::PROFILES_NEW_ROW, selected, false);
+            let y = layout::profiles_row_y(names.len());
+            text::draw_text(target, &row, layout::PROFILES_LEFT_X, y);
+        }
+        centered(target, layout::PROFILES_HINT, layout::PROFILES_HINT_Y);
+    }
+
+    /// Draws a title, a line of text and the letters of `entry` with a
+    /// cursor under the one being picked.
+    fn draw_letters(title: &str, text: &str, entry: &InitialsEntry, target: &mut Bitmap) {
+        target.clear(Color::White);
+        let centered_on = |target: &mut Bitmap, text: &str, center_x: i32, y: i32| {
+            let x = center_x - text::get_text_width(text) as i32 / 2;
+            text::draw_text(target, text, x, y);
+        };
+        let center_x = layout::CENTER_X as i32;
+        centered_on(target, title, center_x, layout::INITIALS_TITLE_Y);
+        centered_on(target, text, center_x, layout::INITIALS_SCORE_Y);
+        for (i, letter) in entry.get_letters().iter().enumerate() {
+            let x = layout::initials_letter_center_x(i);
+            let letter = char::from(*letter).to_string();
+            centered_on(target, &letter, x, layout::INITIALS_LETTERS_Y);
+        }
+        let x = layout::initials_letter_center_x(entry.get_cursor());
+        centered_on(
+            target,
+            layout::INITIALS_CURSOR,
+            x,
+            layout::INITIALS_CURSOR_Y,
+        );
+        centered_on(
+            target,
+            layout::INITIALS_HINT,
+            center_x,
+            layout::INITIALS_HINT_Y,
+        );
+    }
+}
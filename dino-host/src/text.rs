@@ -0,0 +1,61 @@
+use std::convert::Infallible;
+
+use embedded_graphics::{
+    mono_font::{ascii::FONT_7X13_BOLD, MonoFont, MonoTextStyle},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{Baseline, Text},
+};
+
+use crate::{Bitmap, Color};
+
+/// Stand-in for the system font (`Roobert-10-Bold`) used on the device.
+pub const FONT: MonoFont = FONT_7X13_BOLD;
+
+/// The device font has a glyph for the A button; the ASCII font doesn't.
+fn to_ascii(text: &str) -> String {
+    text.replace('Ⓐ', "(A)").replace('Ⓑ', "(B)")
+}
+
+pub fn get_text_width(text: &str) -> u32 {
+    let chars = to_ascii(text).chars().count() as u32;
+    chars * (FONT.character_size.width + FONT.character_spacing)
+}
+
+pub fn get_height() -> u32 {
+    FONT.character_size.height
+}
+
+/// Draws black text with its top-left corner at `(x, y)`.
+pub fn draw_text(target: &mut Bitmap, text: &str, x: i32, y: i32) {
+    let style = MonoTextStyle::new(&FONT, BinaryColor::On);
+    let text = to_ascii(text);
+    Text::with_baseline(&text, Point::new(x, y), style, Baseline::Top)
+        .draw(target)
+        .unwrap();
+}
+
+impl OriginDimensions for Bitmap {
+    fn size(&self) -> Size {
+        Size::new(self.width(), self.height())
+    }
+}
+
+impl DrawTarget for Bitmap {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let color = match color {
+                BinaryColor::On => Color::Black,
+                BinaryColor::Off => Color::White,
+            };
+            self.set(point.x, point.y, color);
+        }
+        Ok(())
+    }
+}
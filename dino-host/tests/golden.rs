@@ -0,0 +1,360 @@
+//! Renders the main screens and compares them with the checked-in images in
+//! `tests/golden`. Run with `UPDATE_GOLDEN=1` to regenerate them after an
+//! intended change, and review the new images before committing them.
+//!
+//! The images come from the host renderer only. The device draws with its
+//! own sprites and shares nothing with it but `dino_core::layout` and the
+//! simulation, so these tests catch regressions in the gameplay, the layout
+//! and the host's drawing. A change to how the device draws a screen has to
+//! be checked in the simulator.
+
+use std::path::PathBuf;
+
+use dino_core::{
+    dino::DinoState,
+    input::{Buttons, Input},
+    math::Rect,
+    obstacle::ObstacleKind,
+    profile::ProfileMenu,
+    scores::{Entry, HighScores, InitialsEntry, Mode, ScoreBrowser},
+    settings::{Difficulty, Options, Settings},
+    shop::{Item, Shop, Wallet},
+    skin::{Skin, Wardrobe},
+    stats::{Stats, StatsBrowser},
+    Event, GameState, World,
+};
+use dino_host::{Assets, Bitmap, Color, Scene};
+
+const SEED: u64 = 7;
+const FRAME_MS: u32 = 33;
+
+struct Game {
+    world: World,
+    scene: Scene,
+}
+
+impl Game {
+    fn new() -> Self {
+        Self::with_assets(Assets::load(Assets::default_dir()).unwrap())
+    }
+
+    fn with_assets(assets: Assets) -> Self {
+        let mut game = Self {
+            world: World::new(),
+            scene: Scene::new(assets, SEED),
+        };
+        game.step(Input::default());
+        game
+    }
+
+    fn with_lives(lives: u32) -> Self {
+        let mut game = Self::new();
+        game.world.lives.set_max(lives);
+        game
+    }
+
+    fn start(&mut self) {
+        self.world.start(SEED);
+        self.scene.reset();
+    }
+
+    fn step(&mut self, input: Input) {
+        self.world.update(input, FRAME_MS);
+        self.scene.update(&self.world, FRAME_MS as f32 / 1000.0);
+    }
+
+    /// Jumps over cacti and low birds, and ducks under high birds.
+    fn autopilot(&self) -> Input {
+        let dino = self.world.dino.get_bounds();
+        let Some(obstacle) = self.world.obstacles.iter().next() else {
+            return Input::default();
+        };
+        let distance = obstacle.get_bounds().x - dino.right();
+        let reach = self.world.ground.get_velocity() * 0.2;
+        if distance > reach || obstacle.get_bounds().right() < dino.x {
+            return Input::default();
+        }
+        if obstacle.get_kind() == ObstacleKind::Bird {
+            Input {
+                current: Buttons::B,
+                pushed: Buttons::NONE,
+            }
+        } else {
+            Input::push(Buttons::A)
+        }
+    }
+
+    fn run_for(&mut self, ms: u32, autopilot: bool) {
+        for _ in 0..ms / FRAME_MS {
+            let input = if autopilot {
+                self.autopilot()
+            } else {
+                Input::default()
+            };
+            self.step(input);
+        }
+    }
+
+    fn render(&self) -> Bitmap {
+        let mut frame = Bitmap::new(400, 240, Color::White);
+        self.scene.draw(&self.world, 1234, &mut frame);
+        if self.scene.is_inverted() {
+            frame.invert();
+        }
+        frame
+    }
+}
+
+fn check(name: &str, frame: &Bitmap) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let path = dir.join(format!("{}.png", name));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        frame.save(&path).unwrap();
+        return;
+    }
+    let golden =
+        Bitmap::open(&path).unwrap_or_else(|e| panic!("cannot open {}: {}", path.display(), e));
+    if &golden != frame {
+        let actual = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{}.png", name));
+        frame.save(&actual).unwrap();
+        panic!(
+            "{} differs from {}, the rendered frame is at {}",
+            name,
+            path.display(),
+            actual.display()
+        );
+    }
+}
+
+#[test]
+fn ready() {
+    let game = Game::new();
+    assert_eq!(game.world.get_state(), GameState::Ready);
+    check("ready", &game.render());
+}
+
+#[test]
+fn playing() {
+    let mut game = Game::new();
+    game.start();
+    game.run_for(3000, true);
+    assert_eq!(game.world.get_state(), GameState::Playing);
+    assert!(!game.scene.is_inverted());
+    check("playing", &game.render());
+}
+
+#[test]
+fn dead() {
+    let mut game = Game::new();
+    game.start();
+    game.run_for(10_000, false);
+    assert_eq!(game.world.get_state(), GameState::Dead);
+    check("dead", &game.render());
+}
+
+#[test]
+fn night() {
+    let mut game = Game::new();
+    game.start();
+    game.run_for(32_000, true);
+    assert_eq!(game.world.get_state(), GameState::Playing);
+    assert!(game.scene.is_inverted());
+    check("night", &game.render());
+}
+
+#[test]
+fn hurt() {
+    let mut game = Game::with_lives(3);
+    game.start();
+    while !game
+        .world
+        .events()
+        .iter()
+        .any(|e| matches!(e, Event::Hurt(_)))
+    {
+        game.step(Input::default());
+    }
+    game.run_for(200, false);
+    assert_eq!(game.world.get_state(), GameState::Playing);
+    assert_eq!(game.world.lives.get_remaining(), 2);
+    assert_eq!(game.world.dino.get_state(), DinoState::Hurt);
+    check("hurt", &game.render());
+}
+
+#[test]
+fn power_up() {
+    let mut game = Game::new();
+    game.start();
+    while !game
+        .world
+        .events()
+        .iter()
+        .any(|e| matches!(e, Event::PowerUpCollected(_)))
+    {
+        assert_eq!(game.world.get_state(), GameState::Playing);
+        let input = game.autopilot();
+        game.step(input);
+    }
+    game.run_for(500, true);
+    assert_eq!(game.world.power_ups.active().count(), 1);
+    check("power-up", &game.render());
+}
+
+#[test]
+fn shop() {
+    let game = Game::new();
+    let mut wallet = Wallet::parse("coins 120\nperk extra-life 2\n");
+    let mut shop = Shop::new();
+    shop.update(&Input::push(Buttons::DOWN), &mut wallet);
+    shop.update(&Input::push(Buttons::A), &mut wallet);
+    shop.update(&Input::push(Buttons::DOWN), &mut wallet);
+    shop.update(&Input::push(Buttons::A), &mut wallet);
+    assert_eq!(wallet.get_coins(), 90);
+    assert_eq!(wallet.get_owned(Item::StartShield), 1);
+    assert_eq!(shop.get_message(), "Not enough coins");
+    let mut frame = Bitmap::new(400, 240, Color::White);
+    game.scene.draw_shop(&shop, &wallet, &mut frame);
+    check("shop", &frame);
+}
+
+#[test]
+fn options() {
+    let game = Game::new();
+    let mut settings = Settings::default();
+    let mut options = Options::new();
+    for buttons in [
+        Buttons::DOWN,
+        Buttons::LEFT,
+        Buttons::DOWN,
+        Buttons::A,
+        Buttons::DOWN,
+    ] {
+        options.update(&Input::push(buttons), &mut settings);
+    }
+    let mut frame = Bitmap::new(400, 240, Color::White);
+    game.scene.draw_options(&options, &settings, &mut frame);
+    check("options", &frame);
+}
+
+#[test]
+fn initials() {
+    let game = Game::new();
+    let mut entry = InitialsEntry::new(*b"AAA");
+    entry.update(&Input::push(Buttons::DOWN), 0);
+    entry.update(&Input::push(Buttons::A), 0);
+    entry.update(&Input::push(Buttons::NONE), 2);
+    let mode = Mode {
+        difficulty: Difficulty::Normal,
+        lives: 3,
+    };
+    let mut frame = Bitmap::new(400, 240, Color::White);
+    game.scene.draw_initials(&entry, 1234, mode, &mut frame);
+    check("initials", &frame);
+}
+
+#[test]
+fn high_scores() {
+    let game = Game::new();
+    let mode = Mode {
+        difficulty: Difficulty::Normal,
+        lives: 3,
+    };
+    let mut high_scores = HighScores::default();
+    for (i, score) in [420, 1234, 77, 980, 1234, 3015].into_iter().enumerate() {
+        let entry = Entry {
+            score,
+            initials: [b'A' + i as u8; 3],
+            // 2026-10-19 and the days before
+            date: 845_683_200 - i as u32 * 86_400,
+            seed: SEED,
+            duration_ms: score as u32 * 50,
+        };
+        high_scores.insert(mode, entry);
+    }
+    let browser = ScoreBrowser::new(mode, Some(2));
+    let mut frame = Bitmap::new(400, 240, Color::White);
+    game.scene
+        .draw_high_scores(&browser, &high_scores, &mut frame);
+    check("high-scores", &frame);
+}
+
+#[test]
+fn stats() {
+    let mut game = Game::new();
+    let mut stats = Stats::default();
+    game.start();
+    for _ in 0..30_000 / FRAME_MS {
+        let input = game.autopilot();
+        game.step(input);
+        stats.record(&game.world);
+    }
+    // run into the next obstacle
+    while game.world.get_state() == GameState::Playing {
+        game.step(Input::default());
+        stats.record(&game.world);
+    }
+    let mut browser = StatsBrowser::new();
+    let mut frame = Bitmap::new(400, 240, Color::White);
+    game.scene.draw_stats(&browser, &stats, &mut frame);
+    check("stats", &frame);
+    browser.update(&Input::push(Buttons::RIGHT));
+    game.scene.draw_stats(&browser, &stats, &mut frame);
+    check("stats-obstacles", &frame);
+}
+
+#[test]
+fn profiles() {
+    let mut game = Game::new();
+    game.scene.set_profile("ANN");
+    game.step(Input::default());
+    check("ready-profile", &game.render());
+    let names = ["P1", "ANN", "BOB"].map(String::from);
+    let mut menu = ProfileMenu::new(1);
+    menu.update(&Input::push(Buttons::DOWN), 0, &names);
+    menu.update(&Input::push(Buttons::DOWN), 0, &names);
+    let mut frame = Bitmap::new(400, 240, Color::White);
+    game.scene.draw_profiles(&menu, &names, 1, &mut frame);
+    check("profiles", &frame);
+}
+
+#[test]
+fn skin() {
+    let mut wardrobe = Wardrobe::new();
+    wardrobe.unlock_for(500);
+    wardrobe.select(Skin::TopHat);
+    let mut game = Game::new();
+    game.scene.set_wardrobe(&wardrobe);
+    game.start();
+    game.run_for(2000, true);
+    assert_eq!(game.world.get_state(), GameState::Playing);
+    check("skin", &game.render());
+}
+
+#[test]
+fn skin_picker() {
+    let mut wardrobe = Wardrobe::new();
+    wardrobe.unlock_for(1000);
+    wardrobe.cycle(-1);
+    assert_eq!(wardrobe.get_selected(), Skin::Ghost);
+    let mut game = Game::new();
+    game.scene.set_wardrobe(&wardrobe);
+    game.step(Input::default());
+    check("skin-picker", &game.render());
+}
+
+#[test]
+fn theme() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/theme");
+    let theme = Assets::load_theme(&dir).unwrap();
+    assert_eq!(theme.get_name(), "Night sky");
+    let assets = Assets::load_with_theme(Assets::default_dir(), &theme).unwrap();
+    let mut game = Game::with_assets(assets);
+    theme.apply(&mut game.world);
+    let shape = game.world.obstacles.get_shape(ObstacleKind::CactusSmall1);
+    assert_eq!(shape.hitboxes, [Rect::new(0.0, 0.0, 17.0, 35.0)]);
+    game.start();
+    game.run_for(3000, true);
+    game.run_for(10_000, false);
+    assert_eq!(game.world.get_state(), GameState::Dead);
+    check("theme", &game.render());
+}
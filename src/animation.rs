@@ -3,36 +3,38 @@ use core::cell::RefCell;
 use alloc::{
     borrow::ToOwned, boxed::Box, collections::BTreeMap, string::String, sync::Arc, vec::Vec,
 };
-use playdate_rs::{graphics::BitmapTable, sprite::Sprite, PLAYDATE};
+use playdate_rs::{
+    graphics::{Bitmap, BitmapFlip, Color},
+    sprite::Sprite,
+    PLAYDATE,
+};
+
+use crate::assets::TableId;
 
 pub trait Animation {
     fn update(&self, delta: f32);
     fn reset(&self);
+    /// The table frame being shown.
+    fn get_frame(&self) -> usize;
 }
 
+/// Plays frames of a table that [`crate::assets`] has already scaled.
 pub struct BitmapAnimation {
-    table: Arc<BitmapTable>,
+    table: Arc<Vec<Bitmap>>,
     frames: Vec<usize>,
     frame_time: f32,
     current_frame: RefCell<usize>,
     current_time: RefCell<f32>,
-    scale: f32,
 }
 
 impl BitmapAnimation {
-    pub fn new(
-        table: Arc<BitmapTable>,
-        frames: impl AsRef<[usize]>,
-        frame_time: f32,
-        scale: f32,
-    ) -> Self {
+    pub fn new(table: Arc<Vec<Bitmap>>, frames: impl AsRef<[usize]>, frame_time: f32) -> Self {
         Self {
             table,
             frames: frames.as_ref().to_vec(),
             frame_time,
             current_frame: RefCell::new(0),
             current_time: RefCell::new(0.0),
-            scale,
         }
     }
 
@@ -54,16 +56,20 @@ impl Animation for BitmapAnimation {
             }
             *current_time = 0.0;
         }
-        PLAYDATE.graphics.draw_scaled_bitmap(
-            self.table.get(self.frames[*current_frame]).unwrap(),
+        PLAYDATE.graphics.draw_bitmap(
+            &self.table[self.frames[*current_frame]],
             vec2!(0, 0),
-            vec2!(self.scale, self.scale),
+            BitmapFlip::Unflipped,
         );
     }
 
     fn reset(&self) {
         self.reset();
     }
+
+    fn get_frame(&self) -> usize {
+        self.frames[*self.current_frame.borrow()]
+    }
 }
 
 pub trait AnimationState: PartialEq + Clone + Ord {
@@ -73,7 +79,7 @@ pub trait AnimationState: PartialEq + Clone + Ord {
 }
 
 pub struct AnimationStateMachine<S: AnimationState> {
-    bitmap_tables: BTreeMap<String, Arc<BitmapTable>>,
+    bitmap_tables: BTreeMap<String, Arc<Vec<Bitmap>>>,
     animations: BTreeMap<S, Box<dyn Animation>>,
     current_state: RefCell<S>,
 }
@@ -87,15 +93,8 @@ impl<S: AnimationState> AnimationStateMachine<S> {
         }
     }
 
-    pub fn add_bitmap_table(
-        &mut self,
-        name: impl AsRef<str>,
-        path: impl AsRef<str>,
-        count: usize,
-        width: u32,
-        height: u32,
-    ) -> Arc<BitmapTable> {
-        let table = Arc::new(BitmapTable::open(count, width, height, path).unwrap());
+    pub fn add_bitmap_table(&mut self, name: impl AsRef<str>, id: TableId) -> Arc<Vec<Bitmap>> {
+        let table = crate::assets::table(id);
         self.bitmap_tables
             .insert(name.as_ref().to_owned(), table.clone());
         table
@@ -105,7 +104,10 @@ impl<S: AnimationState> AnimationStateMachine<S> {
         self.animations.insert(state, Box::new(anim));
     }
 
+    /// Draws the current frame into the sprite's image, and marks the sprite
+    /// dirty when the frame changed.
     pub fn update(&self, sprite: &Sprite, delta: f32, payload: &S::Payload) {
+        let frame = self.get_current_frame();
         let bitmap = sprite.get_image().unwrap();
         let mut current_state = self.current_state.borrow_mut();
         let next_state = current_state
@@ -117,18 +119,21 @@ impl<S: AnimationState> AnimationStateMachine<S> {
             *current_state = next_state;
         }
         PLAYDATE.graphics.push_context(bitmap);
-        PLAYDATE.graphics.clear(crate::sprite_bg_color());
+        PLAYDATE.graphics.clear(Color::Clear);
         animation.update(delta);
         PLAYDATE.graphics.pop_context();
+        if animation.get_frame() != frame {
+            sprite.mark_dirty();
+        }
     }
 
-    pub fn get_current_state(&self) -> S {
-        self.current_state.borrow().clone()
+    pub fn get_current_frame(&self) -> usize {
+        self.animations[&*self.current_state.borrow()].get_frame()
     }
 
     pub fn reset(&self) {
         *self.current_state.borrow_mut() = S::INITIAL;
-        for (_, animation) in self.animations.iter() {
+        for animation in self.animations.values() {
             animation.reset();
         }
     }
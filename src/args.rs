@@ -1,6 +0,0 @@
-pub const GRAVITY: f32 = 1000.0;
-pub const JUMP_VELOCITY: f32 = -500.0;
-pub const MOVE_ACCELERATION: f32 = 6.0;
-pub const INITIAL_MOVE_VELOCITY: f32 = 250.0;
-pub const MAX_MOVE_VELOCITY: f32 = 400.0;
-pub const DAY_NIGHT_CYCLE_SECS: usize = 30;
@@ -0,0 +1,341 @@
+//! Every image, table, sound and font the views use is loaded here once and
+//! shared, so spawning a sprite only copies a ready bitmap.
+//!
+//! Assets are asked for by the IDs `build.rs` generates. The bundled images
+//! are already at their on-screen size; a theme's replacements are scaled
+//! when they are first loaded. An asset that can't be loaded is replaced by
+//! a black placeholder and the error is kept, so the game tells which file
+//! is missing or corrupt instead of crashing.
+
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::fmt;
+#[cfg(feature = "audio")]
+use core::sync::atomic::{AtomicU32, Ordering};
+#[cfg(feature = "audio")]
+use playdate_rs::sound::FilePlayer;
+use playdate_rs::{
+    graphics::{Bitmap, BitmapTable, Color, Font},
+    sprite::Sprite,
+    PLAYDATE,
+};
+use spin::{Lazy, Mutex};
+
+/// Used when the font the game asks for can't be loaded.
+const FALLBACK_FONT: &str = "/System/Fonts/Asheville-Sans-14-Bold.pft";
+
+#[derive(Clone, Debug)]
+pub struct AssetError {
+    kind: &'static str,
+    name: String,
+    reason: String,
+}
+
+impl AssetError {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot load {} \"{}\": {}",
+            self.kind, self.name, self.reason
+        )
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    images: BTreeMap<String, Arc<Bitmap>>,
+    tables: BTreeMap<String, Arc<Vec<Bitmap>>>,
+    sounds: BTreeMap<String, Arc<Sound>>,
+    fonts: BTreeMap<String, Arc<Font>>,
+    errors: Vec<AssetError>,
+}
+
+impl Registry {
+    fn fail(&mut self, kind: &'static str, name: &str, reason: impl fmt::Debug) -> AssetError {
+        let error = AssetError {
+            kind,
+            name: name.to_string(),
+            reason: format!("{:?}", reason),
+        };
+        error!("{}", error);
+        self.errors.push(error.clone());
+        error
+    }
+}
+
+static REGISTRY: Lazy<Mutex<Registry>> = Lazy::new(|| Mutex::new(Registry::default()));
+
+/// Drops every cached asset, so the next requests load them from the current
+/// theme. Views still holding a handle keep theirs alive.
+pub fn clear() {
+    *REGISTRY.lock() = Registry::default();
+}
+
+/// The errors of the assets that failed to load, oldest first.
+pub fn errors() -> Vec<AssetError> {
+    REGISTRY.lock().errors.clone()
+}
+
+/// Typed handles of the files in `assets/`, generated by `build.rs`.
+#[allow(dead_code)]
+pub mod ids {
+    include!(concat!(env!("OUT_DIR"), "/asset_ids.rs"));
+}
+
+/// An image, drawn at `width`×`height`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ImageId {
+    /// The bundled name, the one themes replace.
+    pub name: &'static str,
+    /// The bundled file, already scaled by the build script.
+    pub path: &'static str,
+    pub width: u32,
+    pub height: u32,
+    /// How much a theme's replacement, at the original size, is scaled.
+    pub scale: f32,
+}
+
+/// A bitmap table of `count` frames, drawn at `width`×`height`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TableId {
+    pub name: &'static str,
+    pub path: &'static str,
+    pub count: usize,
+    pub width: u32,
+    pub height: u32,
+    pub scale: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SoundId {
+    pub name: &'static str,
+}
+
+/// A sound effect. Builds without the `audio` feature load no sounds and
+/// play nothing.
+pub struct Sound {
+    #[cfg(feature = "audio")]
+    player: FilePlayer,
+}
+
+/// The volume every sound plays at, as the bits of an `f32` from 0 to 1.
+#[cfg(feature = "audio")]
+static VOLUME: AtomicU32 = AtomicU32::new(0x3f80_0000);
+
+/// Sets the volume of the sounds played from now on.
+#[allow(unused_variables)]
+pub fn set_volume(volume: f32) {
+    #[cfg(feature = "audio")]
+    VOLUME.store(volume.to_bits(), Ordering::Relaxed);
+}
+
+impl Sound {
+    pub fn play(&self) {
+        #[cfg(feature = "audio")]
+        {
+            let volume = f32::from_bits(VOLUME.load(Ordering::Relaxed));
+            self.player.set_volume(volume, volume);
+            self.player.play(1);
+        }
+    }
+}
+
+/// The path of the theme's replacement for `name`, if it has one.
+fn themed_image(name: &str) -> Option<String> {
+    Some(crate::theme::image_path(name)).filter(|p| p != name)
+}
+
+pub fn image(id: ImageId) -> Arc<Bitmap> {
+    if let Some(image) = REGISTRY.lock().images.get(id.name) {
+        return image.clone();
+    }
+    let themed = themed_image(id.name).and_then(|p| Bitmap::open(p).ok());
+    let image = match themed {
+        Some(image) => scaled(&image, id.scale),
+        None => Bitmap::open(id.path).unwrap_or_else(|e| {
+            REGISTRY.lock().fail("image", id.name, e);
+            placeholder(id.width, id.height)
+        }),
+    };
+    let image = Arc::new(image);
+    REGISTRY
+        .lock()
+        .images
+        .insert(id.name.to_string(), image.clone());
+    image
+}
+
+/// Shows `image` on `sprite` by drawing the shared handle, so the sprite
+/// doesn't keep a copy of its own.
+pub fn show_image(sprite: &Sprite, image: Arc<Bitmap>) {
+    let size = image.get_bitmap_data().size;
+    sprite.set_size(size.width as f32, size.height as f32);
+    sprite.set_draw_function(move |sprite, bounds, _| {
+        let position = vec2!(bounds.x as i32, bounds.y as i32);
+        PLAYDATE
+            .graphics
+            .draw_bitmap(&*image, position, sprite.get_image_flip());
+    });
+    sprite.mark_dirty();
+}
+
+/// The frames of a bitmap table.
+pub fn table(id: TableId) -> Arc<Vec<Bitmap>> {
+    if let Some(table) = REGISTRY.lock().tables.get(id.name) {
+        return table.clone();
+    }
+    let (count, width, height) = (id.count, id.width, id.height);
+    let themed = themed_image(id.name).and_then(|p| {
+        let scale = |v: u32| (v as f32 / id.scale) as u32;
+        BitmapTable::open(count, scale(width), scale(height), p).ok()
+    });
+    let frames: Vec<Bitmap> = match themed {
+        Some(table) => (0..count)
+            .map(|i| match table.get(i) {
+                Some(frame) => scaled(&frame, id.scale),
+                None => placeholder(width, height),
+            })
+            .collect(),
+        None => match BitmapTable::open(count, width, height, id.path) {
+            Ok(table) => (0..count)
+                .map(|i| (*table.get(i).unwrap()).clone())
+                .collect(),
+            Err(e) => {
+                REGISTRY.lock().fail("table", id.name, e);
+                (0..count).map(|_| placeholder(width, height)).collect()
+            }
+        },
+    };
+    let frames = Arc::new(frames);
+    REGISTRY
+        .lock()
+        .tables
+        .insert(id.name.to_string(), frames.clone());
+    frames
+}
+
+/// A sound. A sound that fails to load plays silence.
+pub fn sound(id: SoundId) -> Arc<Sound> {
+    if let Some(sound) = REGISTRY.lock().sounds.get(id.name) {
+        return sound.clone();
+    }
+    let sound = Arc::new(load_sound(id));
+    REGISTRY
+        .lock()
+        .sounds
+        .insert(id.name.to_string(), sound.clone());
+    sound
+}
+
+#[cfg(feature = "audio")]
+fn load_sound(id: SoundId) -> Sound {
+    let path = crate::theme::sound_path(id.name);
+    let player = match FilePlayer::open(&path).or_else(|_| FilePlayer::open(id.name)) {
+        Ok(player) => player,
+        Err(e) => {
+            REGISTRY.lock().fail("sound", id.name, e);
+            FilePlayer::new()
+        }
+    };
+    Sound { player }
+}
+
+#[cfg(not(feature = "audio"))]
+fn load_sound(_id: SoundId) -> Sound {
+    Sound {}
+}
+
+/// A font from the system or the game's folder, or [`FALLBACK_FONT`] when it
+/// can't be loaded. Fails only if neither can.
+pub fn font(path: &str) -> Result<Arc<Font>, AssetError> {
+    if let Some(font) = REGISTRY.lock().fonts.get(path) {
+        return Ok(font.clone());
+    }
+    let font = match PLAYDATE.graphics.load_font(path) {
+        Ok(font) => font,
+        Err(e) => {
+            REGISTRY.lock().fail("font", path, e);
+            PLAYDATE
+                .graphics
+                .load_font(FALLBACK_FONT)
+                .map_err(|e| REGISTRY.lock().fail("font", FALLBACK_FONT, e))?
+        }
+    };
+    let font = Arc::new(font);
+    REGISTRY.lock().fonts.insert(path.to_string(), font.clone());
+    Ok(font)
+}
+
+fn scaled(image: &Bitmap, scale: f32) -> Bitmap {
+    let size = image.get_bitmap_data().size;
+    let bitmap = Bitmap::new(
+        size!(
+            (size.width as f32 * scale) as u32,
+            (size.height as f32 * scale) as u32
+        ),
+        Color::Clear,
+    );
+    PLAYDATE.graphics.push_context(&bitmap);
+    PLAYDATE
+        .graphics
+        .draw_scaled_bitmap(image, vec2!(0, 0), vec2!(scale, scale));
+    PLAYDATE.graphics.pop_context();
+    bitmap
+}
+
+fn placeholder(width: u32, height: u32) -> Bitmap {
+    Bitmap::new(size!(width, height), Color::Black)
+}
+
+/// What the cache holds, for the memory log.
+pub struct MemoryReport {
+    pub images: usize,
+    pub frames: usize,
+    pub sounds: usize,
+    pub fonts: usize,
+    /// Pixel and mask data of the cached images and frames.
+    pub bitmap_bytes: usize,
+}
+
+pub fn memory_report() -> MemoryReport {
+    let registry = REGISTRY.lock();
+    let bytes = |bitmap: &Bitmap| {
+        let data = bitmap.get_bitmap_data();
+        let planes = if data.mask.is_null() { 1 } else { 2 };
+        (data.rowbytes * data.size.height) as usize * planes
+    };
+    let frames = registry.tables.values().flat_map(|t| t.iter());
+    MemoryReport {
+        images: registry.images.len(),
+        frames: registry.tables.values().map(|t| t.len()).sum(),
+        sounds: registry.sounds.len(),
+        fonts: registry.fonts.len(),
+        bitmap_bytes: registry.images.values().map(|b| bytes(b)).sum::<usize>()
+            + frames.map(bytes).sum::<usize>(),
+    }
+}
+
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "assets: {} images, {} frames ({} KiB), {} sounds, {} fonts",
+            self.images,
+            self.frames,
+            self.bitmap_bytes / 1024,
+            self.sounds,
+            self.fonts
+        )
+    }
+}
@@ -1,31 +1,39 @@
-use alloc::{vec, vec::Vec};
+use alloc::{sync::Arc, vec, vec::Vec};
 use playdate_rs::{
     display::{DISPLAY_HEIGHT, DISPLAY_WIDTH},
-    graphics::{Bitmap, BitmapFlip},
+    graphics::Bitmap,
     rand::Rng,
     sprite::Sprite,
     App, PLAYDATE,
 };
 
-use crate::{DinoGame, GameState};
+use crate::{
+    assets::ids,
+    layer::Layer,
+    pool::{self, Pool, Pooled},
+    DinoGame, GameState,
+};
 
 pub struct BGItem {
     sprite: Sprite,
 }
 
 impl BGItem {
-    pub fn new() -> Self {
+    pub fn new(image: &Arc<Bitmap>) -> Self {
         // Create cloud image
+        let cloud = pool::new_sprite(Some(image));
+        Layer::Background.apply(&cloud);
+        Self { sprite: cloud }
+    }
+
+    /// Shows the cloud at a random spot right of the screen.
+    fn spawn(&mut self) {
         let pos_x = DISPLAY_WIDTH as f32
             + playdate_rs::util::rand::rng().gen_range(0.0..=DISPLAY_WIDTH as f32 - 46.0 - 32.0);
         let pos_y = playdate_rs::util::rand::rng().gen_range(32.0..=DISPLAY_HEIGHT as f32 / 2.0);
-        let cloud = Sprite::new();
-        let bitmap = Bitmap::open("cloud").unwrap();
-        cloud.set_image(bitmap, BitmapFlip::Unflipped);
-        cloud.set_z_index(-100);
-        cloud.set_bounds(rect!(x: pos_x, y: pos_y, w: 46.0, h: 14.0));
-        PLAYDATE.sprite.add_sprite(&cloud);
-        Self { sprite: cloud }
+        self.sprite
+            .set_bounds(rect!(x: pos_x, y: pos_y, w: 46.0, h: 14.0));
+        self.sprite.set_visible(true);
     }
 
     pub fn update(&mut self, delta: f32) {
@@ -33,12 +41,18 @@ impl BGItem {
             return;
         }
         let pos = self.sprite.get_position();
-        let velocity = DinoGame::get().ground.get_velocity() * 0.3;
+        let velocity = DinoGame::get().world.ground.get_velocity() * 0.3;
         self.sprite
             .move_to(vec2!(x: pos.x - velocity * delta, y: pos.y));
     }
 }
 
+impl Pooled for BGItem {
+    fn get_sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+}
+
 impl Drop for BGItem {
     fn drop(&mut self) {
         PLAYDATE.sprite.remove_sprite(&self.sprite);
@@ -46,16 +60,22 @@ impl Drop for BGItem {
 }
 
 pub struct BGItems {
+    cloud: Arc<Bitmap>,
     items: Vec<BGItem>,
+    pool: Pool<BGItem>,
 }
 
 impl BGItems {
     pub fn new() -> Self {
-        Self { items: vec![] }
+        Self {
+            cloud: crate::assets::image(ids::image::CLOUD),
+            items: vec![],
+            pool: Pool::new(),
+        }
     }
 
     pub fn reset(&mut self) {
-        self.items.clear();
+        self.pool.reclaim(&mut self.items, |_| false);
     }
 
     pub fn update(&mut self, delta: f32) {
@@ -63,8 +83,8 @@ impl BGItems {
         for obstacle in &mut self.items {
             obstacle.update(delta);
         }
-        // remove items that are off screen
-        self.items.retain(|x| {
+        // hide items that are off screen
+        self.pool.reclaim(&mut self.items, |x| {
             let rect = x.sprite.get_bounds();
             rect.x + rect.width >= 0.0
         });
@@ -75,7 +95,12 @@ impl BGItems {
             x_right > DISPLAY_WIDTH as f32
         });
         if !has_hidden_items {
-            self.items.push(BGItem::new());
+            let mut item = self
+                .pool
+                .take(|_| true)
+                .unwrap_or_else(|| BGItem::new(&self.cloud));
+            item.spawn();
+            self.items.push(item);
         }
     }
 }
@@ -0,0 +1,261 @@
+//! The developer cheat console, compiled in with the `cheats` feature.
+//!
+//! Commands come from a hidden menu, opened by turning the crank a full turn
+//! backwards, or from `cheats.txt` in the game's data folder, which is read
+//! and deleted once a second. playdate-rs doesn't bind the simulator's
+//! serial message callback, so in the simulator write the commands to that
+//! file instead, one per line:
+//!
+//! ```text
+//! invincible on|off
+//! speed <pixels per second>
+//! spawn <obstacle kind, e.g. cactus-big-1>
+//! score <score>
+//! night on|off
+//! dump
+//! ```
+
+use alloc::{collections::VecDeque, format, string::String, vec::Vec};
+use core::fmt;
+use dino_core::{
+    input::{Buttons, Input},
+    obstacle::ObstacleKind,
+    World,
+};
+use playdate_rs::{fs::File, graphics::Color, sys::FileOptions, PLAYDATE};
+
+use crate::{overdraw, set_font};
+
+const COMMAND_FILE: &str = "cheats.txt";
+const POLL_INTERVAL_MS: usize = 1000;
+/// How far the crank has to turn backwards to open the menu.
+const OPEN_ANGLE: f32 = 360.0;
+const SPEED_STEP: f32 = 25.0;
+const SCORE_STEP: i32 = 500;
+const MENU_TOP: i32 = 60;
+const MENU_LEFT: i32 = 100;
+const MENU_WIDTH: i32 = 200;
+const LINE_HEIGHT: i32 = 16;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Command {
+    Invincible(bool),
+    Speed(f32),
+    Spawn(ObstacleKind),
+    Score(i32),
+    Night(bool),
+    Dump,
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let arg = words.next().unwrap_or_default();
+        let switch = || match arg {
+            "on" => Ok(true),
+            "off" => Ok(false),
+            _ => Err(format!("expected on or off: {}", line)),
+        };
+        let number = || {
+            arg.parse()
+                .map_err(|_| format!("expected a number: {}", line))
+        };
+        match name {
+            "invincible" => switch().map(Command::Invincible),
+            "speed" => number().map(Command::Speed),
+            "spawn" => ObstacleKind::from_key(arg)
+                .map(Command::Spawn)
+                .ok_or(format!("unknown obstacle kind: {}", arg)),
+            "score" => number().map(|score: f32| Command::Score(score as i32)),
+            "night" => switch().map(Command::Night),
+            "dump" => Ok(Command::Dump),
+            _ => Err(format!("unknown command: {}", line)),
+        }
+    }
+
+    /// Whether the command changes the run, so it can't be replayed.
+    pub fn is_cheating(&self) -> bool {
+        !matches!(self, Command::Night(_) | Command::Dump)
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let switch = |on: bool| if on { "on" } else { "off" };
+        match self {
+            Command::Invincible(on) => write!(f, "invincible {}", switch(*on)),
+            Command::Speed(speed) => write!(f, "speed {}", speed),
+            Command::Spawn(kind) => write!(f, "spawn {}", kind.get_key()),
+            Command::Score(score) => write!(f, "score {}", score),
+            Command::Night(on) => write!(f, "night {}", switch(*on)),
+            Command::Dump => write!(f, "dump"),
+        }
+    }
+}
+
+/// The hidden menu, one command per row. Left and Right change the value of
+/// the selected row, Ⓐ runs it and Ⓑ closes the menu.
+struct Menu {
+    rows: Vec<Command>,
+    selected: usize,
+}
+
+impl Menu {
+    fn new(world: &World, night: bool) -> Self {
+        Self {
+            rows: Vec::from([
+                Command::Invincible(world.is_invincible()),
+                Command::Speed(world.ground.get_velocity()),
+                Command::Spawn(ObstacleKind::ALL[0]),
+                Command::Score(world.scoreboard.get_score()),
+                Command::Night(night),
+                Command::Dump,
+            ]),
+            selected: 0,
+        }
+    }
+
+    fn adjust(&mut self, step: i32) {
+        let row = &mut self.rows[self.selected];
+        *row = match *row {
+            Command::Invincible(on) => Command::Invincible(!on),
+            Command::Speed(speed) => Command::Speed((speed + step as f32 * SPEED_STEP).max(0.0)),
+            Command::Spawn(kind) => {
+                let kinds = ObstacleKind::ALL;
+                let index = kinds.iter().position(|k| *k == kind).unwrap() as i32;
+                Command::Spawn(kinds[(index + step).rem_euclid(kinds.len() as i32) as usize])
+            }
+            Command::Score(score) => Command::Score((score + step * SCORE_STEP).max(0)),
+            Command::Night(on) => Command::Night(!on),
+            Command::Dump => Command::Dump,
+        };
+    }
+
+    fn draw(&self) {
+        let height = (self.rows.len() as i32 + 1) * LINE_HEIGHT;
+        let frame = rect!(x: MENU_LEFT, y: MENU_TOP, w: MENU_WIDTH, h: height);
+        PLAYDATE.graphics.fill_rect(frame, Color::White);
+        PLAYDATE.graphics.draw_rect(frame, Color::Black);
+        // erased the frame after the menu closes
+        overdraw::add(frame);
+        set_font();
+        let x = MENU_LEFT + 8;
+        PLAYDATE
+            .graphics
+            .draw_text("Cheats", vec2!(x, MENU_TOP + 2));
+        for (i, row) in self.rows.iter().enumerate() {
+            let marker = if i == self.selected { ">" } else { " " };
+            let y = MENU_TOP + 2 + (i as i32 + 1) * LINE_HEIGHT;
+            PLAYDATE
+                .graphics
+                .draw_text(format!("{} {}", marker, row), vec2!(x, y));
+        }
+    }
+}
+
+pub struct Cheats {
+    menu: Option<Menu>,
+    commands: VecDeque<Command>,
+    /// Degrees the crank turned backwards in a row.
+    crank: f32,
+    next_poll_ms: usize,
+}
+
+impl Cheats {
+    pub fn new() -> Self {
+        Self {
+            menu: None,
+            commands: VecDeque::new(),
+            crank: 0.0,
+            next_poll_ms: 0,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.menu.is_some()
+    }
+
+    /// Lets the menu take the input while it is open, and queues the
+    /// commands from the menu and the command file.
+    pub fn update(&mut self, input: &Input, world: &World, night: bool) {
+        self.poll_file();
+        let change = PLAYDATE.system.get_crank_change();
+        self.crank = if change < 0.0 {
+            self.crank - change
+        } else {
+            0.0
+        };
+        if self.crank >= OPEN_ANGLE && self.menu.is_none() {
+            self.crank = 0.0;
+            self.menu = Some(Menu::new(world, night));
+            return;
+        }
+        let Some(menu) = &mut self.menu else {
+            return;
+        };
+        let pushed = input.pushed;
+        if pushed.contains(Buttons::B) {
+            self.menu = None;
+        } else if pushed.contains(Buttons::UP) {
+            menu.selected = (menu.selected + menu.rows.len() - 1) % menu.rows.len();
+        } else if pushed.contains(Buttons::DOWN) {
+            menu.selected = (menu.selected + 1) % menu.rows.len();
+        } else if pushed.contains(Buttons::LEFT) {
+            menu.adjust(-1);
+        } else if pushed.contains(Buttons::RIGHT) {
+            menu.adjust(1);
+        } else if pushed.contains(Buttons::A) {
+            self.commands.push_back(menu.rows[menu.selected]);
+        }
+    }
+
+    fn poll_file(&mut self) {
+        let now = PLAYDATE.system.get_current_time_milliseconds();
+        if now < self.next_poll_ms {
+            return;
+        }
+        self.next_poll_ms = now + POLL_INTERVAL_MS;
+        let Ok(mut file) = File::open(COMMAND_FILE, FileOptions::kFileReadData) else {
+            return;
+        };
+        let text = file.read_to_string().unwrap_or_default();
+        drop(file);
+        let _ = PLAYDATE.file.unlink(COMMAND_FILE, false);
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            match Command::parse(line) {
+                Ok(command) => self.commands.push_back(command),
+                Err(e) => warn!("{}", e),
+            }
+        }
+    }
+
+    pub fn next_command(&mut self) -> Option<Command> {
+        self.commands.pop_front()
+    }
+
+    /// Draws the menu straight onto the frame, after the sprites.
+    pub fn draw(&self) {
+        if let Some(menu) = &self.menu {
+            menu.draw();
+        }
+    }
+}
+
+/// Runs a command on the world. Night mode is the caller's, since the
+/// display isn't part of the world.
+pub fn run(command: Command, world: &mut World) {
+    info!("{}", command);
+    match command {
+        Command::Invincible(on) => world.set_invincible(on),
+        Command::Speed(speed) => world.ground.set_velocity(speed),
+        Command::Spawn(kind) => world.spawn_obstacle(kind),
+        Command::Score(score) => world.scoreboard.set_score(score),
+        Command::Night(_) => {}
+        Command::Dump => {
+            for line in world.dump().lines() {
+                info!("{}", line);
+            }
+        }
+    }
+}
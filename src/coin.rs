@@ -0,0 +1,94 @@
+use alloc::{sync::Arc, vec, vec::Vec};
+use dino_core::{Event, World};
+use playdate_rs::{graphics::Bitmap, sprite::Sprite, PLAYDATE};
+
+use crate::{
+    assets::{ids, Sound},
+    pool::{self, Pool, Pooled},
+};
+
+/// The coin image at its in-game size, also used by the scoreboard.
+pub fn coin_bitmap() -> Arc<Bitmap> {
+    crate::assets::image(ids::image::COIN)
+}
+
+struct Coin {
+    id: u32,
+    sprite: Sprite,
+}
+
+impl Coin {
+    fn new(image: &Arc<Bitmap>) -> Self {
+        Self {
+            id: 0,
+            sprite: pool::new_sprite(Some(image)),
+        }
+    }
+
+    fn spawn(&mut self, coin: &dino_core::coin::Coin) {
+        self.id = coin.get_id();
+        self.sprite.set_bounds(crate::to_rect(coin.get_bounds()));
+        self.sprite.set_visible(true);
+    }
+}
+
+impl Pooled for Coin {
+    fn get_sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+}
+
+impl Drop for Coin {
+    fn drop(&mut self) {
+        PLAYDATE.sprite.remove_sprite(&self.sprite);
+    }
+}
+
+pub struct Coins {
+    image: Arc<Bitmap>,
+    sound: Arc<Sound>,
+    coins: Vec<Coin>,
+    pool: Pool<Coin>,
+}
+
+impl Coins {
+    pub fn new() -> Self {
+        Self {
+            image: coin_bitmap(),
+            sound: crate::assets::sound(ids::sound::COIN),
+            coins: vec![],
+            pool: Pool::new(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.pool.reclaim(&mut self.coins, |_| false);
+    }
+
+    pub fn update(&mut self, world: &World) {
+        // hide coins that were collected or scrolled off
+        self.pool.reclaim(&mut self.coins, |c| {
+            world.coins.iter().any(|x| x.get_id() == c.id)
+        });
+        for x in world.coins.iter() {
+            match self.coins.iter_mut().find(|c| c.id == x.get_id()) {
+                Some(coin) => coin.sprite.set_bounds(crate::to_rect(x.get_bounds())),
+                None => {
+                    let mut coin = self
+                        .pool
+                        .take(|_| true)
+                        .unwrap_or_else(|| Coin::new(&self.image));
+                    coin.spawn(x);
+                    self.coins.push(coin);
+                }
+            }
+        }
+        if world
+            .events()
+            .iter()
+            .any(|e| matches!(e, Event::CoinCollected))
+        {
+            self.sound.play();
+        }
+    }
+}
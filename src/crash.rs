@@ -0,0 +1,185 @@
+//! The crash screen and crash report.
+//!
+//! A panic writes `crash.txt` to the data folder, with the version, what the
+//! run was doing and the last log lines.
+//!
+//! On the device this runs in the panic handler, see [`crate::entry`], which
+//! then draws the message and location over the game and stops. The
+//! simulator links `std`, which owns the panic handler there, so the report
+//! is written from a panic hook instead. With `panic = "abort"` the
+//! simulator quits as soon as the hook returns, so it shows no crash screen,
+//! only the message in its console.
+
+use alloc::{format, string::String};
+use core::{
+    fmt,
+    panic::Location,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use dino_core::{dino::DinoState, GameState, World};
+use playdate_rs::{
+    fs::{File, Write},
+    println,
+    sys::FileOptions,
+};
+use spin::Mutex;
+
+#[cfg(not(all(target_arch = "arm", target_os = "none")))]
+extern crate std;
+
+const REPORT_FILE: &str = "crash.txt";
+
+/// What the run was doing, for the report.
+#[derive(Clone, Copy)]
+struct Snapshot {
+    state: GameState,
+    seed: u64,
+    tick: u32,
+    score: i32,
+    lives: u32,
+    speed: f32,
+    dino: DinoState,
+    obstacles: usize,
+}
+
+static SNAPSHOT: Mutex<Option<Snapshot>> = Mutex::new(None);
+/// Set by the first panic, so a panic while reporting doesn't report again.
+static CRASHED: AtomicBool = AtomicBool::new(false);
+
+/// Keeps what `world` is doing for the report. Called every frame.
+pub fn set_snapshot(world: &World) {
+    *SNAPSHOT.lock() = Some(Snapshot {
+        state: world.get_state(),
+        seed: world.get_seed(),
+        tick: world.get_tick(),
+        score: world.scoreboard.get_score(),
+        lives: world.lives.get_remaining(),
+        speed: world.ground.get_velocity(),
+        dino: world.dino.get_state(),
+        obstacles: world.obstacles.iter().count(),
+    });
+}
+
+/// Reports panics from the panic hook in the simulator. The device's panic
+/// handler calls [`report`] itself.
+pub fn install_hook() {
+    #[cfg(not(all(target_arch = "arm", target_os = "none")))]
+    std::panic::set_hook(alloc::boxed::Box::new(|info| {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+        report(&message, info.location());
+    }));
+}
+
+/// Writes the report, and on the device shows the crash screen.
+pub fn report(message: &dyn fmt::Display, location: Option<&Location>) {
+    if CRASHED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    let message = format!("{}", message);
+    let location = match location {
+        Some(location) => format!("{}:{}", location.file(), location.line()),
+        None => String::from("unknown location"),
+    };
+    // the logger may be what panicked, so this skips it
+    println!("panicked at {}: {}", location, message);
+    write_report(&message, &location);
+    #[cfg(all(target_arch = "arm", target_os = "none"))]
+    screen::draw(&message, &location);
+}
+
+fn write_report(message: &str, location: &str) {
+    let mut text = format!(
+        "dino {} crashed\npanicked at {}: {}\n\n",
+        env!("CARGO_PKG_VERSION"),
+        location,
+        message
+    );
+    // the lock is held if the panic came from `set_snapshot`
+    match SNAPSHOT.try_lock().and_then(|s| *s) {
+        Some(s) => text.push_str(&format!(
+            "state {:?}\nseed {}\ntick {}\nscore {}\nlives {}\nspeed {:.1}\ndino {:?}\nobstacles {}\n",
+            s.state, s.seed, s.tick, s.score, s.lives, s.speed, s.dino, s.obstacles
+        )),
+        None => text.push_str("no game state\n"),
+    }
+    text.push_str("\nrecent log:\n");
+    for line in crate::log::recent() {
+        text.push_str(&line);
+        text.push('\n');
+    }
+    if let Ok(mut file) = File::open(REPORT_FILE, FileOptions::kFileWrite) {
+        let _ = file.write_all(text.as_bytes()).and_then(|_| file.flush());
+    }
+}
+
+/// The crash screen, which only the device gets to show.
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+mod screen {
+    use alloc::{format, string::String, vec::Vec};
+    use playdate_rs::{
+        display::DISPLAY_WIDTH,
+        graphics::{Color, Font},
+        PLAYDATE,
+    };
+
+    use super::REPORT_FILE;
+
+    /// A system font, in case the panic came from loading the game's.
+    const FONT_PATH: &str = "/System/Fonts/Asheville-Sans-14-Bold.pft";
+    const MARGIN: i32 = 10;
+    const LINE_HEIGHT: i32 = 18;
+
+    /// Splits `text` into lines that fit the screen.
+    fn wrap(font: Option<&Font>, text: &str) -> Vec<String> {
+        let max_width = DISPLAY_WIDTH as i32 - 2 * MARGIN;
+        let fits = |line: &str| match font {
+            Some(font) => font.get_text_width(line, 0) as i32 <= max_width,
+            None => line.len() <= 40,
+        };
+        let mut lines: Vec<String> = Vec::new();
+        for word in text.split_whitespace() {
+            match lines.last_mut() {
+                Some(line) if fits(&format!("{} {}", line, word)) => {
+                    line.push(' ');
+                    line.push_str(word);
+                }
+                _ => lines.push(String::from(word)),
+            }
+        }
+        lines
+    }
+
+    /// Draws straight onto the screen and shows it, since no more frames will
+    /// come.
+    pub fn draw(message: &str, location: &str) {
+        let font = PLAYDATE.graphics.load_font(FONT_PATH).ok();
+        if let Some(font) = &font {
+            PLAYDATE.graphics.set_font(font);
+        }
+        PLAYDATE.display.set_inverted(false);
+        PLAYDATE.graphics.set_draw_offset(vec2!(0, 0));
+        PLAYDATE.graphics.clear_clip_rect();
+        PLAYDATE.graphics.clear(Color::White);
+        let mut lines = Vec::from([String::from("Dino crashed"), String::new()]);
+        lines.extend(wrap(font.as_ref(), message));
+        lines.extend(wrap(font.as_ref(), &format!("at {}", location)));
+        lines.push(String::new());
+        lines.extend(wrap(
+            font.as_ref(),
+            &format!(
+                "The details are in {} in the game's data folder.",
+                REPORT_FILE
+            ),
+        ));
+        for (i, line) in lines.iter().enumerate() {
+            let y = MARGIN + i as i32 * LINE_HEIGHT;
+            PLAYDATE.graphics.draw_text(line, vec2!(MARGIN, y));
+        }
+        PLAYDATE.graphics.display();
+    }
+}
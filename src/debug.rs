@@ -0,0 +1,71 @@
+//! The debug overlay, offered under "Overlay" with the `debug-overlay`
+//! feature. It outlines the bounds of the dino, obstacles, coins and
+//! power-ups, inverts their hitboxes, and prints the dino's physics on a
+//! white panel. Everything is drawn over the sprites, see [`crate::overdraw`].
+
+use alloc::format;
+use dino_core::{math::Rect, World};
+use playdate_rs::{graphics::Color, PLAYDATE};
+
+use crate::{font_height, overdraw, set_font, text_width};
+
+/// Below the FPS counter.
+const TEXT_POSITION: (i32, i32) = (2, 16);
+/// Around the text, so the panel doesn't hug it.
+const TEXT_PADDING: i32 = 2;
+
+fn to_screen(rect: Rect) -> playdate_rs::math::Rect<i32> {
+    rect!(x: rect.x as i32, y: rect.y as i32, w: rect.width as i32, h: rect.height as i32)
+}
+
+/// Outlines `bounds` and inverts the `hitboxes` inside it.
+fn draw_shape(bounds: Rect, hitboxes: &[Rect]) {
+    PLAYDATE.graphics.draw_rect(to_screen(bounds), Color::Black);
+    overdraw::add(to_screen(bounds));
+    for hitbox in hitboxes {
+        let rect = Rect::new(
+            bounds.x + hitbox.x,
+            bounds.y + hitbox.y,
+            hitbox.width,
+            hitbox.height,
+        );
+        PLAYDATE.graphics.fill_rect(to_screen(rect), Color::XOR);
+        overdraw::add(to_screen(rect));
+    }
+}
+
+/// Draws straight onto the frame, after the sprites.
+pub fn draw(world: &World) {
+    let dino = &world.dino;
+    draw_shape(dino.get_bounds(), dino.hitboxes());
+    for obstacle in world.obstacles.iter() {
+        let shape = world.obstacles.get_shape(obstacle.get_kind());
+        draw_shape(obstacle.get_bounds(), &shape.hitboxes);
+    }
+    // coins and power-ups are collected anywhere within their bounds
+    let whole = |bounds: Rect| [Rect::new(0.0, 0.0, bounds.width, bounds.height)];
+    for coin in world.coins.iter() {
+        draw_shape(coin.get_bounds(), &whole(coin.get_bounds()));
+    }
+    for power_up in world.power_ups.iter() {
+        draw_shape(power_up.get_bounds(), &whole(power_up.get_bounds()));
+    }
+    let text = format!(
+        "{:?}  vy {:.1}  ground {:.1}",
+        dino.get_state(),
+        dino.get_vertical_velocity(),
+        world.ground.get_velocity()
+    );
+    let panel = rect!(
+        x: TEXT_POSITION.0 - TEXT_PADDING,
+        y: TEXT_POSITION.1 - TEXT_PADDING,
+        w: text_width(&text) as i32 + 2 * TEXT_PADDING,
+        h: font_height() as i32 + 2 * TEXT_PADDING,
+    );
+    PLAYDATE.graphics.fill_rect(panel, Color::White);
+    overdraw::add(panel);
+    set_font();
+    PLAYDATE
+        .graphics
+        .draw_text(text, vec2!(TEXT_POSITION.0, TEXT_POSITION.1));
+}
@@ -0,0 +1,50 @@
+//! The entry points `#[app]` would generate, with a panic handler that shows
+//! the crash screen instead of only logging the message.
+
+use playdate_rs::system::SystemEvent;
+
+use crate::DinoGame;
+
+#[no_mangle]
+#[allow(non_snake_case)]
+unsafe extern "C" fn eventHandler(pd: *mut ::core::ffi::c_void, event: SystemEvent, arg: u32) {
+    playdate_rs::__playdate_handle_event::<DinoGame>(pd, event, arg);
+}
+
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+#[panic_handler]
+fn panic_handler(info: &core::panic::PanicInfo) -> ! {
+    crate::crash::report(&info.message(), info.location());
+    // `system.error` would replace the crash screen, so this keeps it up
+    // until the device is restarted
+    #[allow(clippy::empty_loop)]
+    loop {}
+}
+
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+#[no_mangle]
+pub extern "C" fn _sbrk() {}
+
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+#[no_mangle]
+extern "C" fn _exit() {}
+
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+#[no_mangle]
+extern "C" fn _kill() {}
+
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+#[no_mangle]
+extern "C" fn _getpid() {}
+
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+#[no_mangle]
+extern "C" fn __exidx_start() {
+    unimplemented!();
+}
+
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+#[no_mangle]
+extern "C" fn __exidx_end() {
+    unimplemented!();
+}
@@ -1,83 +1,43 @@
-use core::cell::RefCell;
+use dino_core::World;
+use playdate_rs::{display::DISPLAY_HEIGHT, sprite::Sprite, PLAYDATE};
 
-use playdate_rs::{
-    display::DISPLAY_HEIGHT,
-    graphics::{Bitmap, BitmapFlip},
-    sprite::Sprite,
-    PLAYDATE,
-};
-
-use crate::{DinoGame, GameState};
+use crate::layer::Layer;
 
 pub struct Ground {
-    pub ground_sprites: (Sprite, Sprite),
-    horizontal_velocity: RefCell<f32>,
+    ground_sprites: (Sprite, Sprite),
 }
 
 impl Ground {
-    const HEIGHT: f32 = 64.0;
-    pub const COLLIDE_HEIGHT: f32 = 64.0 - 18.0;
+    const WIDTH: f32 = dino_core::ground::Ground::WIDTH;
+    const HEIGHT: f32 = dino_core::ground::Ground::HEIGHT;
 
     pub fn new() -> Self {
-        let ground = Sprite::new();
-        let bitmap = Bitmap::open("ground").unwrap();
-        ground.set_image(bitmap, BitmapFlip::Unflipped);
-        ground.set_collide_rect(rect!(x: 0.0, y: 18.0, w: 2400.0, h: Self::HEIGHT));
-        ground.collisions_enabled();
-        ground.set_z_index(-100);
-        let ground2 = ground.clone();
-        ground2.set_collide_rect(rect!(x: 0.0, y: 18.0, w: 2400.0, h: Self::HEIGHT));
-        ground2.set_z_index(-100);
-        PLAYDATE.sprite.add_sprite(&ground);
-        PLAYDATE.sprite.add_sprite(&ground2);
-        let mut ground = Self {
-            ground_sprites: (ground, ground2),
-            horizontal_velocity: RefCell::new(0.0),
+        let bitmap = crate::assets::image(crate::assets::ids::image::GROUND);
+        let new_sprite = || {
+            let sprite = Sprite::new();
+            crate::assets::show_image(&sprite, bitmap.clone());
+            Layer::Background.apply(&sprite);
+            PLAYDATE.sprite.add_sprite(&sprite);
+            sprite
+        };
+        let ground = Self {
+            ground_sprites: (new_sprite(), new_sprite()),
         };
-        ground.reset();
+        ground.move_to(0.0);
         ground
     }
 
-    pub fn reset(&mut self) {
+    fn move_to(&self, x: f32) {
         let y = DISPLAY_HEIGHT as f32 - Self::HEIGHT;
         self.ground_sprites
             .0
-            .set_bounds(rect!(x: 0.0, y: y, w: 2400.0, h: 24.0));
+            .set_bounds(rect!(x: x, y: y, w: Self::WIDTH, h: 24.0));
         self.ground_sprites
             .1
-            .set_bounds(rect!(x: 2400.0, y: y, w: 2400.0, h: 24.0));
-        *self.horizontal_velocity.borrow_mut() = crate::args::INITIAL_MOVE_VELOCITY;
-    }
-
-    pub fn sprite_is_ground(&self, s: &Sprite) -> bool {
-        s == &self.ground_sprites.0 || s == &self.ground_sprites.1
-    }
-
-    pub fn get_velocity(&self) -> f32 {
-        *self.horizontal_velocity.borrow()
+            .set_bounds(rect!(x: x + Self::WIDTH, y: y, w: Self::WIDTH, h: 24.0));
     }
 
-    pub fn update(&mut self, delta: f32) {
-        if DinoGame::get_game_state() != GameState::Playing {
-            return;
-        }
-        // move sprites
-        let mut velocity = self.horizontal_velocity.borrow_mut();
-        let step = *velocity * delta;
-        self.ground_sprites.0.move_by(vec2![-step, 0.0]);
-        self.ground_sprites.1.move_by(vec2![-step, 0.0]);
-        // change ground sprites order
-        let pos1 = self.ground_sprites.1.get_position();
-        if pos1.x <= 0.0 {
-            self.ground_sprites
-                .0
-                .move_to(vec2![pos1.x + 2400.0, pos1.y]);
-            core::mem::swap(&mut self.ground_sprites.0, &mut self.ground_sprites.1);
-        }
-        // update velocity
-        *velocity += crate::args::MOVE_ACCELERATION * delta;
-        if *velocity > crate::args::MAX_MOVE_VELOCITY {
-            *velocity = crate::args::MAX_MOVE_VELOCITY;
-        }
+    pub fn update(&mut self, world: &World) {
+        self.move_to(world.ground.get_offset());
     }
 }
@@ -0,0 +1,238 @@
+use alloc::string::ToString;
+use dino_core::{
+    input::Input,
+    layout,
+    scores::{
+        Entry, HighScores, InitialsEntry, InitialsUpdate, Mode, ScoreBrowser, ScoreBrowserUpdate,
+        INITIALS_LEN,
+    },
+};
+use playdate_rs::{
+    display::{DISPLAY_HEIGHT, DISPLAY_WIDTH},
+    graphics::{Bitmap, BitmapFlip, Color},
+    sprite::Sprite,
+    PLAYDATE,
+};
+
+use crate::{layer::Layer, set_font, text_width};
+
+/// How far the crank turns to move to the next letter.
+const CRANK_STEP_DEGREES: f32 = 30.0;
+
+/// A hidden full-screen sprite for a screen to draw on.
+pub fn screen_sprite() -> Sprite {
+    let bitmap = Bitmap::new(size!(DISPLAY_WIDTH as _, DISPLAY_HEIGHT as _), Color::White);
+    let sprite = Sprite::new();
+    sprite.set_image(bitmap, BitmapFlip::Unflipped);
+    Layer::Screen.apply(&sprite);
+    sprite.set_bounds(rect!(x: 0.0, y: 0.0, w: DISPLAY_WIDTH as _, h: DISPLAY_HEIGHT as _));
+    sprite.set_visible(false);
+    PLAYDATE.sprite.add_sprite(&sprite);
+    sprite
+}
+
+pub fn draw_centered(text: &str, center_x: i32, y: i32) {
+    let x = center_x - text_width(text) as i32 / 2;
+    PLAYDATE.graphics.draw_text(text, vec2!(x, y));
+}
+
+/// Draws a title, a line of text and the letters of `entry` with a cursor
+/// under the one being picked, into the pushed context.
+pub fn draw_letters(title: &str, text: &str, entry: &InitialsEntry) {
+    let center_x = layout::CENTER_X as i32;
+    PLAYDATE.graphics.clear(Color::White);
+    set_font();
+    draw_centered(title, center_x, layout::INITIALS_TITLE_Y);
+    draw_centered(text, center_x, layout::INITIALS_SCORE_Y);
+    for (i, letter) in entry.get_letters().iter().enumerate() {
+        let x = layout::initials_letter_center_x(i);
+        let letter = char::from(*letter).to_string();
+        draw_centered(&letter, x, layout::INITIALS_LETTERS_Y);
+    }
+    let x = layout::initials_letter_center_x(entry.get_cursor());
+    draw_centered(layout::INITIALS_CURSOR, x, layout::INITIALS_CURSOR_Y);
+    draw_centered(layout::INITIALS_HINT, center_x, layout::INITIALS_HINT_Y);
+}
+
+/// Turns the crank into letter steps. The cheat console reads the crank
+/// change, so this keeps track of the angle itself.
+pub struct Crank {
+    last_angle: f32,
+    /// Degrees turned towards the next letter.
+    turned: f32,
+}
+
+impl Crank {
+    pub fn new() -> Self {
+        Self {
+            last_angle: PLAYDATE.system.get_crank_angle(),
+            turned: 0.0,
+        }
+    }
+
+    /// The letters the crank moved past since the last call.
+    pub fn read_steps(&mut self) -> i32 {
+        let angle = PLAYDATE.system.get_crank_angle();
+        let change = (angle - self.last_angle + 540.0) % 360.0 - 180.0;
+        self.last_angle = angle;
+        self.turned += change;
+        let steps = (self.turned / CRANK_STEP_DEGREES) as i32;
+        self.turned -= steps as f32 * CRANK_STEP_DEGREES;
+        steps
+    }
+}
+
+/// A run that made it into a table, waiting for its initials.
+struct Pending {
+    initials: InitialsEntry,
+    mode: Mode,
+    entry: Entry,
+}
+
+/// The initials entry, opened when a run makes it into its table.
+pub struct InitialsScreen {
+    sprite: Sprite,
+    pending: Option<Pending>,
+    crank: Crank,
+}
+
+impl InitialsScreen {
+    pub fn new() -> Self {
+        Self {
+            sprite: screen_sprite(),
+            pending: None,
+            crank: Crank::new(),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Asks for the initials of `entry`, starting from `initials`.
+    pub fn open(&mut self, mode: Mode, entry: Entry, initials: [u8; INITIALS_LEN]) {
+        self.pending = Some(Pending {
+            initials: InitialsEntry::new(initials),
+            mode,
+            entry,
+        });
+        self.crank = Crank::new();
+        self.sprite.set_visible(true);
+        self.draw();
+    }
+
+    /// Lets the screen take the input. Returns the entry with its initials
+    /// once they are picked.
+    pub fn update(&mut self, input: &Input) -> Option<(Mode, Entry)> {
+        let crank_steps = self.crank.read_steps();
+        let pending = self.pending.as_mut()?;
+        match pending.initials.update(input, crank_steps) {
+            InitialsUpdate::Editing => {
+                self.draw();
+                None
+            }
+            InitialsUpdate::Done(initials) => {
+                self.sprite.set_visible(false);
+                let Pending {
+                    mode, mut entry, ..
+                } = self.pending.take()?;
+                entry.initials = initials;
+                Some((mode, entry))
+            }
+        }
+    }
+
+    fn draw(&mut self) {
+        let Some(pending) = &self.pending else {
+            return;
+        };
+        let bitmap = self.sprite.get_image().unwrap();
+        PLAYDATE.graphics.push_context(bitmap);
+        let score = layout::initials_score_text(pending.entry.score, pending.mode);
+        draw_letters(layout::INITIALS_TITLE, &score, &pending.initials);
+        PLAYDATE.graphics.pop_context();
+        self.sprite.mark_dirty();
+    }
+}
+
+/// The high score tables, opened with Up on the Ready screen and after
+/// entering initials.
+pub struct HighScoresScreen {
+    sprite: Sprite,
+    browser: Option<ScoreBrowser>,
+}
+
+impl HighScoresScreen {
+    pub fn new() -> Self {
+        Self {
+            sprite: screen_sprite(),
+            browser: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.browser.is_some()
+    }
+
+    pub fn open(&mut self, browser: ScoreBrowser, high_scores: &HighScores) {
+        self.browser = Some(browser);
+        self.sprite.set_visible(true);
+        self.draw(high_scores);
+    }
+
+    /// Lets the tables take the input. The caller opens the stats when
+    /// they are asked for.
+    pub fn update(&mut self, input: &Input, high_scores: &HighScores) -> ScoreBrowserUpdate {
+        let Some(browser) = &mut self.browser else {
+            return ScoreBrowserUpdate::Closed;
+        };
+        let update = browser.update(input);
+        if update == ScoreBrowserUpdate::Open {
+            self.draw(high_scores);
+        } else {
+            self.browser = None;
+            self.sprite.set_visible(false);
+        }
+        update
+    }
+
+    fn draw(&mut self, high_scores: &HighScores) {
+        let Some(browser) = &self.browser else {
+            return;
+        };
+        let center_x = layout::CENTER_X as i32;
+        let bitmap = self.sprite.get_image().unwrap();
+        PLAYDATE.graphics.push_context(bitmap);
+        PLAYDATE.graphics.clear(Color::White);
+        set_font();
+        draw_centered("HIGH SCORES", center_x, layout::SCORES_TITLE_Y);
+        let mode = browser.get_mode();
+        draw_centered(
+            &layout::scores_mode_text(mode),
+            center_x,
+            layout::SCORES_MODE_Y,
+        );
+        let entries = high_scores.get(mode);
+        if entries.is_empty() {
+            draw_centered(layout::SCORES_EMPTY, center_x, layout::SCORES_ROWS_Y);
+        }
+        for (rank, entry) in entries.iter().enumerate() {
+            let y = layout::scores_row_y(rank);
+            let highlighted = browser.get_highlight() == Some(rank);
+            let [rank, initials, score, date] = layout::scores_row_texts(rank, entry, highlighted);
+            let x = layout::SCORES_RANK_RIGHT_X - text_width(&rank) as i32;
+            PLAYDATE.graphics.draw_text(&rank, vec2!(x, y));
+            PLAYDATE
+                .graphics
+                .draw_text(&initials, vec2!(layout::SCORES_INITIALS_X, y));
+            let x = layout::SCORES_SCORE_RIGHT_X - text_width(&score) as i32;
+            PLAYDATE.graphics.draw_text(&score, vec2!(x, y));
+            PLAYDATE
+                .graphics
+                .draw_text(&date, vec2!(layout::SCORES_DATE_X, y));
+        }
+        draw_centered(layout::SCORES_HINT, center_x, layout::SCORES_HINT_Y);
+        PLAYDATE.graphics.pop_context();
+        self.sprite.mark_dirty();
+    }
+}
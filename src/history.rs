@@ -0,0 +1,22 @@
+use alloc::format;
+use dino_core::history::{RunRecord, HISTORY_FILE};
+use playdate_rs::{
+    fs::{File, Write},
+    sys::FileOptions,
+};
+
+use crate::profile;
+
+/// Adds a finished run to the profile's history, see
+/// [`dino_core::history`].
+pub fn append(record: &RunRecord) {
+    let line = format!("{}\n", record);
+    match File::open(profile::path(HISTORY_FILE), FileOptions::kFileAppend) {
+        Ok(mut file) => {
+            if let Err(e) = file.write_all(line.as_bytes()) {
+                error!("cannot write the history: {}", e);
+            }
+        }
+        Err(e) => error!("cannot open the history: {}", e),
+    }
+}
@@ -0,0 +1,29 @@
+use playdate_rs::sprite::Sprite;
+
+/// The depths sprites are drawn at, back to front.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(i16)]
+pub enum Layer {
+    /// The ground and the clouds.
+    Background = -100,
+    /// The dino, obstacles, coins and power-ups.
+    Scene = 0,
+    /// Worn on the dino's head.
+    Accessory = 1,
+    /// The curtain that slides open when a run starts.
+    Mask = 10000,
+    /// The scoreboard and the game over panel.
+    Hud = 10001,
+    /// Blinking messages.
+    Message = 20000,
+    /// Full-screen menus, like the shop and the high scores.
+    Screen = 30000,
+    /// Opened from the system menu, so it can cover the other screens.
+    Options = 30001,
+}
+
+impl Layer {
+    pub fn apply(self, sprite: &Sprite) {
+        sprite.set_z_index(self as i16);
+    }
+}
@@ -4,58 +4,189 @@ extern crate alloc;
 #[macro_use]
 extern crate playdate_rs;
 
+#[macro_use]
+mod log;
+
 mod animation;
-mod args;
+mod assets;
 mod bg_items;
+#[cfg(feature = "cheats")]
+mod cheats;
+mod coin;
+mod crash;
+#[cfg(feature = "debug-overlay")]
+mod debug;
 mod dino;
+mod entry;
 mod ground;
+mod high_scores;
+mod history;
+mod layer;
 mod mask;
 mod obstacle;
+mod options;
+mod overdraw;
+mod pool;
+mod power_up;
+mod profile;
+#[cfg(feature = "profiler")]
+mod profiler;
+mod replay;
+mod save;
 mod scoreboard;
+mod shop;
+mod skin;
+mod stats;
+mod theme;
 mod ui_layer;
 
-use core::cell::RefCell;
-
+use alloc::{format, string::String, sync::Arc, vec::Vec};
 use dino::Dino;
+use dino_core::history::RunRecord;
+use dino_core::input::{Buttons, Input};
+use dino_core::profile::ProfileMenuUpdate;
+use dino_core::save::Save;
+use dino_core::scores::{self, Entry, Mode, ScoreBrowser, ScoreBrowserUpdate};
+use dino_core::settings::{OptionsUpdate, Settings};
+use dino_core::shop::{Item, Wallet};
+use dino_core::skin::Wardrobe;
+use dino_core::theme::Theme;
+use dino_core::{Event, GameState, World};
 use ground::Ground;
+use high_scores::{HighScoresScreen, InitialsScreen};
 use mask::Mask;
 use obstacle::Obstacles;
-use playdate_rs::graphics::{Color, Font};
-use playdate_rs::system::{Buttons, MenuItem};
-use playdate_rs::{app, println, App, PLAYDATE};
+use options::OptionsScreen;
+use playdate_rs::display::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use playdate_rs::graphics::{Bitmap, Color, Font};
+use playdate_rs::math::Rect;
+use playdate_rs::rand::Rng;
+use playdate_rs::system::MenuItem;
+use playdate_rs::{App, PLAYDATE};
+use profile::ProfileScreen;
+use profiler::Profiler;
+use replay::Recorder;
+use shop::ShopScreen;
 use spin::Lazy;
+use stats::StatsScreen;
 
 use crate::scoreboard::Scoreboard;
 
-const SHOW_BOUNDING_BOX: bool = false;
+/// Where the system draws the FPS counter.
+const FPS_BOUNDS: Rect<i32> = Rect::new(0, 0, 30, 16);
+
+/// What the "Overlay" menu item draws over the game, in builds with the
+/// developer overlays.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Overlay {
+    Off,
+    #[cfg(feature = "profiler")]
+    Profiler,
+    /// Bounds, hitboxes and physics, see [`debug`].
+    #[cfg(feature = "debug-overlay")]
+    Debug,
+}
+
+impl Overlay {
+    const ALL: &'static [Overlay] = &[
+        Overlay::Off,
+        #[cfg(feature = "profiler")]
+        Overlay::Profiler,
+        #[cfg(feature = "debug-overlay")]
+        Overlay::Debug,
+    ];
+
+    fn get_name(self) -> &'static str {
+        match self {
+            Overlay::Off => "Off",
+            #[cfg(feature = "profiler")]
+            Overlay::Profiler => "Profiler",
+            #[cfg(feature = "debug-overlay")]
+            Overlay::Debug => "Debug",
+        }
+    }
+}
+
+/// Stands in for the profiler in builds without it, so the frame needs no
+/// `cfg` at every lap.
+#[cfg(not(feature = "profiler"))]
+mod profiler {
+    pub struct Profiler;
+
+    impl Profiler {
+        pub fn new() -> Self {
+            Profiler
+        }
+
+        pub fn start_frame(&mut self) {}
+
+        pub fn lap(&mut self, _name: &'static str) {}
+
+        pub fn end_frame(&mut self) {}
+
+        pub fn draw(&self) {}
+    }
+}
+
+fn to_rect(rect: dino_core::math::Rect) -> Rect<f32> {
+    rect!(x: rect.x, y: rect.y, w: rect.width, h: rect.height)
+}
 
-fn sprite_bg_color() -> Color {
-    if SHOW_BOUNDING_BOX {
-        Color::Black
-    } else {
-        Color::Clear
+fn read_input() -> Input {
+    let state = PLAYDATE.system.get_button_state();
+    Input {
+        current: Buttons::from_bits(state.current.bits()),
+        pushed: Buttons::from_bits(state.pushed.bits()),
     }
 }
 
-#[app]
 pub struct DinoGame {
+    world: World,
+    recorder: Recorder,
     dino: Dino,
     ground: Ground,
     obstacles: Obstacles,
+    power_ups: power_up::PowerUps,
+    coins: coin::Coins,
+    save: Save,
+    wallet: Wallet,
+    shop: ShopScreen,
+    options: OptionsScreen,
+    initials: InitialsScreen,
+    high_scores: HighScoresScreen,
+    stats: StatsScreen,
+    profiles: ProfileScreen,
+    wardrobe: Wardrobe,
     bg_items: bg_items::BGItems,
     mask: Mask,
     ui_layer: ui_layer::UILayer,
     scoreboard: Scoreboard,
-    state: RefCell<GameState>,
     last_invert_time_ms: usize,
     inverted: bool,
-    fps_menu: MenuItem,
-    _version_menu: MenuItem,
+    profiler: Profiler,
+    #[cfg(feature = "cheats")]
+    cheats: cheats::Cheats,
+    /// Only added when there is an overlay to pick.
+    overlay_menu: Option<MenuItem>,
+    _options_menu: MenuItem,
+    themes: Vec<Theme>,
+    theme_index: usize,
+    theme_menu: MenuItem,
+    _menu_image: Bitmap,
 }
 
 impl DinoGame {
     fn get_game_state() -> GameState {
-        *Self::get().state.borrow()
+        Self::get().world.get_state()
+    }
+
+    fn get_settings() -> &'static Settings {
+        &Self::get().save.settings
+    }
+
+    /// Whether the views should hold still instead of blinking.
+    fn is_flashing_reduced() -> bool {
+        Self::get_settings().reduce_flashing
     }
 
     fn is_playing(&self) -> bool {
@@ -69,83 +200,444 @@ impl DinoGame {
     }
 
     fn reset_and_start_game(&mut self) {
-        self.ground.reset();
+        let seed = playdate_rs::util::rand::rng().gen();
+        let lives = self.save.settings.lives;
+        self.world.lives.set_max(lives);
+        self.world.set_difficulty(self.save.settings.difficulty);
+        let perks = self.wallet.take_perks();
+        shop::save_wallet(&self.wallet);
+        self.world.set_perks(perks);
+        self.world.start(seed);
+        debug!("start run: seed {}, {} lives", seed, lives);
+        self.recorder
+            .start(seed, &self.world, perks, &self.save.settings.theme);
         self.dino.reset();
         self.bg_items.reset();
         self.obstacles.reset();
-        self.scoreboard.reset();
-        *self.state.borrow_mut() = GameState::Playing;
+        self.power_ups.reset();
+        self.coins.reset();
+        if self.wallet.is_unlocked(Item::NightStart) {
+            self.inverted = true;
+            PLAYDATE.display.set_inverted(true);
+        }
         self.last_invert_time_ms = PLAYDATE.system.get_current_time_milliseconds();
     }
+
+    /// Switches to the theme picked in the system menu, between runs.
+    fn update_theme(&mut self) {
+        let index = self.theme_menu.get_value() as usize;
+        if index == self.theme_index || self.is_playing() {
+            return;
+        }
+        self.theme_index = index;
+        let theme = &self.themes[index];
+        theme::set_current(theme);
+        self.save.settings.theme = String::from(theme.get_dir());
+        save::store(&self.save);
+        debug!("theme: {}", theme.get_dir());
+        theme.apply(&mut self.world);
+        // recreate every view with the new assets
+        PLAYDATE.sprite.remove_all_sprites();
+        assets::clear();
+        self.dino = Dino::new();
+        self.ground = Ground::new();
+        self.bg_items = bg_items::BGItems::new();
+        self.obstacles = Obstacles::new();
+        self.power_ups = power_up::PowerUps::new();
+        self.coins = coin::Coins::new();
+        self.shop = ShopScreen::new();
+        self.options = OptionsScreen::new();
+        self.initials = InitialsScreen::new();
+        self.high_scores = HighScoresScreen::new();
+        self.stats = StatsScreen::new();
+        let names = self.profiles.get_names().to_vec();
+        self.profiles = ProfileScreen::new(names);
+        self.mask = Mask::new();
+        self.ui_layer = ui_layer::UILayer::new();
+        self.scoreboard = Scoreboard::new(self.save.record);
+        self.apply_wardrobe();
+        self.ui_layer.set_profile(&profile::get_current());
+        self.ui_layer.set_asset_errors(&assets::errors());
+        info!("{}", assets::memory_report());
+    }
+
+    /// Switches to the profile `name` and loads its files. A new profile
+    /// starts with its name as the initials for high scores.
+    fn load_profile(&mut self, name: &str, is_new: bool) {
+        profile::switch(name);
+        let (mut save, save_recovery) = save::load();
+        if is_new {
+            if let Some(initials) = scores::parse_initials(name) {
+                save.initials = initials;
+            }
+            save::store(&save);
+        }
+        self.save = save;
+        self.wallet = shop::load_wallet();
+        self.wardrobe = skin::load_wardrobe();
+        self.scoreboard.set_record(self.save.record);
+        self.apply_settings();
+        self.apply_wardrobe();
+        // every profile has its own theme, which rebuilds the views
+        let theme_index = self
+            .themes
+            .iter()
+            .position(|t| t.get_dir() == self.save.settings.theme)
+            .unwrap_or(0);
+        self.theme_menu.set_value(theme_index as i32);
+        self.update_theme();
+        self.ui_layer.set_profile(name);
+        self.ui_layer.set_save_recovery(save_recovery);
+    }
+
+    /// Runs the cheat console, which takes the input while its menu is open.
+    #[cfg(feature = "cheats")]
+    fn update_cheats(&mut self, input: &mut Input) {
+        let was_open = self.cheats.is_open();
+        self.cheats.update(input, &self.world, self.inverted);
+        if was_open || self.cheats.is_open() {
+            *input = Input::default();
+        }
+        while let Some(command) = self.cheats.next_command() {
+            if command.is_cheating() {
+                self.recorder.disable();
+            }
+            if let cheats::Command::Night(on) = command {
+                self.inverted = on;
+                self.last_invert_time_ms = PLAYDATE.system.get_current_time_milliseconds();
+                PLAYDATE.display.set_inverted(on);
+            }
+            cheats::run(command, &mut self.world);
+        }
+    }
+
+    /// Applies the settings the views don't read every frame.
+    fn apply_settings(&mut self) {
+        assets::set_volume(self.save.settings.get_volume());
+        if self.save.settings.reduce_flashing && self.inverted {
+            self.inverted = false;
+            PLAYDATE.display.set_inverted(false);
+        }
+    }
+
+    fn apply_wardrobe(&mut self) {
+        self.dino.set_skin(self.wardrobe.get_selected());
+        self.ui_layer.set_wardrobe(&self.wardrobe);
+    }
+
+    /// Left and Right pick a skin between runs.
+    fn update_wardrobe(&mut self, input: &Input) {
+        let step = if input.pushed.contains(Buttons::LEFT) {
+            -1
+        } else if input.pushed.contains(Buttons::RIGHT) {
+            1
+        } else {
+            return;
+        };
+        self.wardrobe.cycle(step);
+        skin::save_wardrobe(&self.wardrobe);
+        self.apply_wardrobe();
+    }
 }
 
 impl App for DinoGame {
     fn new() -> Self {
-        println!("Hello, World!");
-        Self {
+        crash::install_hook();
+        info!("dino {}", env!("CARGO_PKG_VERSION"));
+        // the profile picks the folder the other files are loaded from
+        let profile_names = profile::load();
+        let (save, save_recovery) = save::load();
+        // load the theme before the views
+        let themes = theme::discover();
+        let theme_index = themes
+            .iter()
+            .position(|t| t.get_dir() == save.settings.theme)
+            .unwrap_or(0);
+        theme::set_current(&themes[theme_index]);
+        let mut world = World::new();
+        themes[theme_index].apply(&mut world);
+        let theme_names: Vec<&str> = themes.iter().map(|t| t.get_name()).collect();
+        let theme_menu = PLAYDATE
+            .system
+            .add_options_menu_item("Theme", &theme_names, || {});
+        theme_menu.set_value(theme_index as i32);
+        let overlay_names: Vec<&str> = Overlay::ALL.iter().map(|o| o.get_name()).collect();
+        let overlay_menu = (overlay_names.len() > 1).then(|| {
+            PLAYDATE
+                .system
+                .add_options_menu_item("Overlay", &overlay_names, || {})
+        });
+        let options_menu = PLAYDATE
+            .system
+            .add_menu_item("Options", options::request_open);
+        let menu_image = menu_image();
+        PLAYDATE.system.set_menu_image(&menu_image, 0);
+        // only redraw what the sprites mark dirty, over a white background
+        PLAYDATE.graphics.set_background_color(Color::White);
+        PLAYDATE.sprite.set_always_redraw(false);
+        let mut game = Self {
+            world,
+            recorder: Recorder::new(),
             dino: Dino::new(),
             ground: Ground::new(),
             bg_items: bg_items::BGItems::new(),
             obstacles: Obstacles::new(),
+            power_ups: power_up::PowerUps::new(),
+            coins: coin::Coins::new(),
+            wallet: shop::load_wallet(),
+            shop: ShopScreen::new(),
+            options: OptionsScreen::new(),
+            initials: InitialsScreen::new(),
+            high_scores: HighScoresScreen::new(),
+            stats: StatsScreen::new(),
+            profiles: ProfileScreen::new(profile_names),
+            wardrobe: skin::load_wardrobe(),
             mask: Mask::new(),
             ui_layer: ui_layer::UILayer::new(),
-            scoreboard: Scoreboard::new(),
-            state: RefCell::new(GameState::Ready),
-            fps_menu: PLAYDATE
-                .system
-                .add_checkmark_menu_item("Show FPS", true, || {}),
-            _version_menu: PLAYDATE
-                .system
-                .add_menu_item(format!("Version: {}", env!("CARGO_PKG_VERSION")), || {}),
+            scoreboard: Scoreboard::new(save.record),
+            save,
+            profiler: Profiler::new(),
+            #[cfg(feature = "cheats")]
+            cheats: cheats::Cheats::new(),
+            overlay_menu,
+            _options_menu: options_menu,
+            themes,
+            theme_index,
+            theme_menu,
+            _menu_image: menu_image,
             last_invert_time_ms: 0,
             inverted: false,
-        }
+        };
+        game.apply_settings();
+        game.apply_wardrobe();
+        game.ui_layer.set_asset_errors(&assets::errors());
+        game.ui_layer.set_save_recovery(save_recovery);
+        game.ui_layer.set_profile(&profile::get_current());
+        info!("{}", assets::memory_report());
+        game
     }
 
     fn update(&mut self, delta: f32) {
-        // Clear screen
-        PLAYDATE.graphics.clear(Color::Clear);
+        let overlay = self
+            .overlay_menu
+            .as_ref()
+            .map_or(Overlay::Off, |m| Overlay::ALL[m.get_value() as usize]);
+        #[cfg(feature = "profiler")]
+        self.profiler.set_enabled(overlay == Overlay::Profiler);
+        self.profiler.start_frame();
         // Update game state
-        let pushed = PLAYDATE.system.get_button_state().pushed;
-        if self.is_ready_or_dead() && pushed.contains(Buttons::A) {
+        let mut input = read_input();
+        #[cfg(feature = "cheats")]
+        self.update_cheats(&mut input);
+        self.options.open_if_requested(&self.save.settings);
+        if self.options.is_open() {
+            match self.options.update(&input, &mut self.save.settings) {
+                OptionsUpdate::Open => {}
+                OptionsUpdate::Changed => self.apply_settings(),
+                OptionsUpdate::Closed => save::store(&self.save),
+            }
+            input = Input::default();
+        } else if self.initials.is_open() {
+            if let Some((mode, entry)) = self.initials.update(&input) {
+                self.save.initials = entry.initials;
+                let rank = self.save.high_scores.insert(mode, entry);
+                save::store(&self.save);
+                let browser = ScoreBrowser::new(mode, rank);
+                self.high_scores.open(browser, &self.save.high_scores);
+            }
+            input = Input::default();
+        } else if self.high_scores.is_open() {
+            let update = self.high_scores.update(&input, &self.save.high_scores);
+            if update == ScoreBrowserUpdate::Stats {
+                self.stats.open(&self.save.stats);
+            }
+            input = Input::default();
+        } else if self.stats.is_open() {
+            self.stats.update(&input, &self.save.stats);
+            input = Input::default();
+        } else if self.profiles.is_open() {
+            match self.profiles.update(&input) {
+                ProfileMenuUpdate::Switch(index) => {
+                    let name = self.profiles.get_names()[index].clone();
+                    if name != profile::get_current() {
+                        self.load_profile(&name, false);
+                    }
+                }
+                ProfileMenuUpdate::Create(name) => self.load_profile(&name, true),
+                ProfileMenuUpdate::Open | ProfileMenuUpdate::Closed => {}
+            }
+            input = Input::default();
+        } else if self.shop.is_open() {
+            self.shop.update(&input, &mut self.wallet);
+            input = Input::default();
+        } else if DinoGame::get_game_state() == GameState::Ready
+            && input.pushed.contains(Buttons::B)
+        {
+            self.shop.open(&self.wallet);
+            input = Input::default();
+        } else if DinoGame::get_game_state() == GameState::Ready
+            && input.pushed.contains(Buttons::UP)
+        {
+            let browser = ScoreBrowser::new(Mode::of(&self.save.settings), None);
+            self.high_scores.open(browser, &self.save.high_scores);
+            input = Input::default();
+        } else if DinoGame::get_game_state() == GameState::Ready
+            && input.pushed.contains(Buttons::DOWN)
+        {
+            self.profiles.open();
+            input = Input::default();
+        } else if self.is_ready_or_dead() {
+            self.update_wardrobe(&input);
+            self.update_theme();
+        }
+        if self.is_ready_or_dead() && input.pushed.contains(Buttons::A) {
             self.reset_and_start_game();
+        } else if self.is_playing() {
+            input = self.save.settings.controls.map(input);
         }
         // Should invert the world?
-        if self.is_playing() {
+        if self.is_playing() && !self.save.settings.reduce_flashing {
             let current_time = PLAYDATE.system.get_current_time_milliseconds();
             let elapsed_ms = current_time - self.last_invert_time_ms;
-            if elapsed_ms > crate::args::DAY_NIGHT_CYCLE_SECS * 1000 {
+            if elapsed_ms > dino_core::args::DAY_NIGHT_CYCLE_SECS * 1000 {
                 self.inverted = !self.inverted;
                 self.last_invert_time_ms = current_time;
                 PLAYDATE.display.set_inverted(self.inverted);
             }
         }
-        // Update and draw sprites
-        self.ground.update(delta);
+        self.profiler.lap("input");
+        // Step the simulation and record it
+        let mut delta_ms = (delta * 1000.0 + 0.5) as u32;
+        // the options and the cheat menu pause the run
+        if self.options.is_open() {
+            delta_ms = 0;
+        }
+        #[cfg(feature = "cheats")]
+        if self.cheats.is_open() {
+            delta_ms = 0;
+        }
+        let was_playing = self.is_playing();
+        let recorder = &mut self.recorder;
+        self.world
+            .advance(input, delta_ms, |input, step_ms, world| {
+                recorder.record(input, step_ms, world)
+            });
+        crash::set_snapshot(&self.world);
+        if was_playing {
+            self.save.stats.record(&self.world);
+        }
+        self.profiler.lap("world");
+        for event in self.world.events() {
+            if let Event::Achievement(score) = event {
+                if !self.wardrobe.unlock_for(*score).is_empty() {
+                    skin::save_wardrobe(&self.wardrobe);
+                    self.ui_layer.set_wardrobe(&self.wardrobe);
+                }
+            }
+        }
+        let died = self.world.events().iter().find_map(|e| match e {
+            Event::Died(kind) => Some(*kind),
+            _ => None,
+        });
+        if let Some(obstacle) = died {
+            let score = self.world.scoreboard.get_score();
+            debug!("run over: score {}", score);
+            let date = PLAYDATE.system.get_seconds_since_epoch().0 as u32;
+            // runs changed by cheats would skew the balancing
+            if self.recorder.is_enabled() {
+                let profile = profile::get_current();
+                history::append(&RunRecord::of_run(&self.world, &profile, obstacle, date));
+            }
+            let new_record = score > self.save.record;
+            self.recorder.finish(new_record);
+            if new_record {
+                self.save.record = score;
+                self.scoreboard.set_record(score);
+            }
+            // the stats count every run
+            save::store(&self.save);
+            let mode = Mode::of_run(&self.world);
+            if self.save.high_scores.qualifies(mode, score) {
+                let entry = Entry {
+                    score,
+                    initials: self.save.initials,
+                    date,
+                    seed: self.world.get_seed(),
+                    duration_ms: self.world.get_elapsed_ms(),
+                };
+                self.initials.open(mode, entry, self.save.initials);
+            }
+            self.wallet.add_coins(self.world.coins.get_collected());
+            shop::save_wallet(&self.wallet);
+        }
+        // Update sprites and redraw the regions that changed
+        self.ground.update(&self.world);
+        self.profiler.lap("ground");
         self.bg_items.update(delta);
-        self.dino.update(delta);
-        self.obstacles.update(delta);
+        self.profiler.lap("bg_items");
+        self.dino.update(&self.world, delta);
+        self.profiler.lap("dino");
+        self.obstacles.update(&self.world, delta);
+        self.profiler.lap("obstacles");
+        self.power_ups.update(&self.world);
+        self.coins.update(&self.world);
+        self.profiler.lap("pickups");
         self.mask.update(delta);
+        self.profiler.lap("mask");
         self.ui_layer.update(delta);
-        self.scoreboard.update(delta);
+        self.profiler.lap("ui_layer");
+        self.scoreboard.update(&self.world);
+        self.profiler.lap("scoreboard");
+        overdraw::erase();
         PLAYDATE.sprite.draw_sprites();
-        // Draw FPS
-        if self.fps_menu.get_value() == 1 {
+        self.profiler.lap("draw_sprites");
+        self.profiler.end_frame();
+        // Draw the overlay
+        if self.save.settings.show_fps || overlay != Overlay::Off {
             PLAYDATE.system.draw_fps(vec2!(0, 0));
+            overdraw::add(FPS_BOUNDS);
+        }
+        self.profiler.draw();
+        #[cfg(feature = "debug-overlay")]
+        if overlay == Overlay::Debug {
+            debug::draw(&self.world);
         }
+        #[cfg(feature = "cheats")]
+        self.cheats.draw();
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
-enum GameState {
-    Ready,
-    Playing,
-    Dead,
-}
-
-static FONT: Lazy<Font> = Lazy::new(|| {
+/// Shown next to the system menu, which has no room left for a version item.
+fn menu_image() -> Bitmap {
+    let bitmap = Bitmap::new(size!(DISPLAY_WIDTH as _, DISPLAY_HEIGHT as _), Color::White);
+    PLAYDATE.graphics.push_context(&bitmap);
+    set_font();
+    let text = format!("Version: {}", env!("CARGO_PKG_VERSION"));
     PLAYDATE
         .graphics
-        .load_font("/System/Fonts/Roobert-10-Bold.pft")
-        .unwrap()
-});
+        .draw_text(text, vec2!(8, DISPLAY_HEIGHT as i32 - 24));
+    PLAYDATE.graphics.pop_context();
+    bitmap
+}
+
+static FONT: Lazy<Option<Arc<Font>>> =
+    Lazy::new(|| assets::font("/System/Fonts/Roobert-10-Bold.pft").ok());
+
+/// Switches to the game's font. Text keeps the current font if neither it
+/// nor the fallback could be loaded, which the asset errors already report.
+pub fn set_font() {
+    if let Some(font) = FONT.as_deref() {
+        PLAYDATE.graphics.set_font(font);
+    }
+}
+
+/// The width of `text` in the game's font, or 0 without one.
+pub fn text_width(text: impl AsRef<str>) -> u32 {
+    FONT.as_deref()
+        .map_or(0, |font| font.get_text_width(text, 0))
+}
+
+/// The line height of the game's font, or 0 without one.
+pub fn font_height() -> u8 {
+    FONT.as_deref().map_or(0, Font::get_height)
+}
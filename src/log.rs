@@ -0,0 +1,187 @@
+//! Logging with levels, filtered per module.
+//!
+//! Messages above [`MAX_LEVEL`] are compiled out, and builds with the
+//! `verbose-logging` feature keep them all. The rest are filtered at run
+//! time by `log-filter` in the game's data folder, see
+//! [`dino_core::log_filter`].
+//!
+//! Builds with the `log-file` feature also write the log to `log.txt` in the
+//! data folder, flushed line by line so it survives a crash. Every launch
+//! and every 32 KiB start a new file, and the two before it are kept as
+//! `log.1.txt` and `log.2.txt`.
+//!
+//! The last lines are also kept in memory for the crash report.
+
+use alloc::{collections::VecDeque, format, string::String, vec::Vec};
+use core::fmt;
+use dino_core::log_filter::Filter;
+pub use dino_core::log_filter::Level;
+#[cfg(feature = "log-file")]
+use playdate_rs::fs::Write;
+use playdate_rs::{fs::File, println, sys::FileOptions, PLAYDATE};
+use spin::{Lazy, Mutex};
+
+const FILTER_FILE: &str = "log-filter";
+/// Lines kept for the crash report.
+const RECENT_LINES: usize = 20;
+#[cfg(feature = "log-file")]
+const LOG_FILE: &str = "log.txt";
+/// Older log files kept next to the current one.
+#[cfg(feature = "log-file")]
+const KEPT_FILES: usize = 2;
+#[cfg(feature = "log-file")]
+const MAX_FILE_BYTES: usize = 32 * 1024;
+
+/// Messages above this level are compiled out.
+pub const MAX_LEVEL: Level = if cfg!(feature = "verbose-logging") {
+    Level::Trace
+} else {
+    Level::Info
+};
+
+/// An open file kept in [`LOGGER`], which has to be `Send`.
+#[cfg(feature = "log-file")]
+struct SendFile(File);
+
+// SAFETY: a `File` only holds the SDK's pointer to it, and the game runs on
+// the Playdate's single thread, so the file is never used from two threads.
+#[cfg(feature = "log-file")]
+unsafe impl Send for SendFile {}
+
+/// The current log file and what was written to it.
+#[cfg(feature = "log-file")]
+struct LogFile {
+    file: Option<SendFile>,
+    bytes: usize,
+}
+
+#[cfg(feature = "log-file")]
+impl LogFile {
+    fn new() -> Self {
+        let mut log_file = Self {
+            file: None,
+            bytes: 0,
+        };
+        log_file.rotate();
+        log_file
+    }
+
+    /// Shifts the kept files back by one and starts an empty `log.txt`.
+    fn rotate(&mut self) {
+        // close the file before renaming it
+        self.file = None;
+        let kept = |index: usize| format!("log.{}.txt", index);
+        for index in (1..KEPT_FILES).rev() {
+            let _ = PLAYDATE.file.rename(kept(index), kept(index + 1));
+        }
+        let _ = PLAYDATE.file.rename(LOG_FILE, kept(1));
+        self.file = File::open(LOG_FILE, FileOptions::kFileWrite)
+            .ok()
+            .map(SendFile);
+        self.bytes = 0;
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if self.bytes + line.len() + 1 > MAX_FILE_BYTES {
+            self.rotate();
+        }
+        let Some(SendFile(file)) = &mut self.file else {
+            return;
+        };
+        let _ = file
+            .write_all(line.as_bytes())
+            .and_then(|_| file.write_all(b"\n"))
+            .and_then(|_| file.flush());
+        self.bytes += line.len() + 1;
+    }
+}
+
+struct Logger {
+    filter: Filter,
+    recent: VecDeque<String>,
+    #[cfg(feature = "log-file")]
+    file: LogFile,
+}
+
+impl Logger {
+    fn new() -> Self {
+        let spec = File::open(FILTER_FILE, FileOptions::kFileReadData)
+            .and_then(|mut f| f.read_to_string())
+            .unwrap_or_default();
+        // the logger isn't up yet, so this goes straight to the console
+        let filter = Filter::parse(&spec, MAX_LEVEL).unwrap_or_else(|e| {
+            println!("{}: {}", FILTER_FILE, e);
+            Filter::new(MAX_LEVEL)
+        });
+        Self {
+            filter,
+            recent: VecDeque::new(),
+            #[cfg(feature = "log-file")]
+            file: LogFile::new(),
+        }
+    }
+}
+
+static LOGGER: Lazy<Mutex<Logger>> = Lazy::new(|| Mutex::new(Logger::new()));
+
+/// Logs `args` if the filter lets `level` through for `module`. Use the
+/// macros instead, which compile out the levels above [`MAX_LEVEL`].
+pub fn log(level: Level, module: &str, args: fmt::Arguments) {
+    // module paths are shown below the crate
+    let module = module.split_once("::").map_or(module, |(_, m)| m);
+    let mut logger = LOGGER.lock();
+    if level > logger.filter.get_level(module) {
+        return;
+    }
+    let line = format!(
+        "{} [{}] {}: {}",
+        PLAYDATE.system.get_current_time_milliseconds(),
+        level.get_name(),
+        module,
+        args
+    );
+    println!("{}", line);
+    #[cfg(feature = "log-file")]
+    logger.file.write_line(&line);
+    if logger.recent.len() == RECENT_LINES {
+        logger.recent.pop_front();
+    }
+    logger.recent.push_back(line);
+}
+
+/// The last lines logged, oldest first. Empty if the logger is busy, which
+/// it is when it panicked itself.
+pub fn recent() -> Vec<String> {
+    LOGGER
+        .try_lock()
+        .map(|logger| logger.recent.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $level <= $crate::log::MAX_LEVEL {
+            $crate::log::log($level, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+macro_rules! error {
+    ($($arg:tt)*) => { log!($crate::log::Level::Error, $($arg)*) };
+}
+
+macro_rules! warn {
+    ($($arg:tt)*) => { log!($crate::log::Level::Warn, $($arg)*) };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => { log!($crate::log::Level::Info, $($arg)*) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => { log!($crate::log::Level::Debug, $($arg)*) };
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => { log!($crate::log::Level::Trace, $($arg)*) };
+}
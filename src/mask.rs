@@ -6,9 +6,11 @@ use playdate_rs::{
     PLAYDATE,
 };
 
-use crate::{DinoGame, GameState};
+use dino_core::layout;
 
-const MASK_SIZE: Size<f32> = size!(DISPLAY_WIDTH as f32 - 80.0, DISPLAY_HEIGHT as f32);
+use crate::{layer::Layer, DinoGame, GameState};
+
+const MASK_SIZE: Size<f32> = size!(layout::MASK_WIDTH, DISPLAY_HEIGHT as f32);
 
 pub struct Mask {
     left_sprite: Sprite,
@@ -24,12 +26,14 @@ impl Mask {
         );
         let right_sprite = Sprite::new();
         right_sprite.set_image(bitmap, BitmapFlip::Unflipped);
-        right_sprite.set_z_index(10000);
-        right_sprite.set_bounds(rect!(x: 100.0, y: 0.0, w: MASK_SIZE.width, h: MASK_SIZE.height));
+        Layer::Mask.apply(&right_sprite);
+        right_sprite.set_bounds(
+            rect!(x: layout::MASK_RIGHT_X, y: 0.0, w: MASK_SIZE.width, h: MASK_SIZE.height),
+        );
         PLAYDATE.sprite.add_sprite(&right_sprite);
         let left_sprite = right_sprite.clone();
         left_sprite.set_bounds(
-            rect!(x: 20.0 - MASK_SIZE.width, y: 0.0, w: MASK_SIZE.width, h: MASK_SIZE.height),
+            rect!(x: layout::MASK_LEFT_X, y: 0.0, w: MASK_SIZE.width, h: MASK_SIZE.height),
         );
         PLAYDATE.sprite.add_sprite(&left_sprite);
         Self {
@@ -46,11 +50,13 @@ impl Mask {
         // reveal the main scene
         let pos = self.left_sprite.get_position();
         if pos.x + MASK_SIZE.width / 2.0 > 0.0 {
-            self.left_sprite.move_by(vec2!(x: -500.0 * delta, y: 0.0));
+            self.left_sprite
+                .move_by(vec2!(x: -layout::MASK_SPEED * delta, y: 0.0));
         }
         let pos = self.right_sprite.get_position();
         if pos.x - MASK_SIZE.width / 2.0 < DISPLAY_WIDTH as f32 {
-            self.right_sprite.move_by(vec2!(x: 500.0 * delta, y: 0.0));
+            self.right_sprite
+                .move_by(vec2!(x: layout::MASK_SPEED * delta, y: 0.0));
         }
     }
 }
@@ -1,151 +1,91 @@
 use alloc::{sync::Arc, vec, vec::Vec};
+use dino_core::{math::Rect, obstacle::ObstacleKind, World};
 use playdate_rs::{
-    display::{DISPLAY_HEIGHT, DISPLAY_WIDTH},
-    graphics::{Bitmap, BitmapFlip, BitmapTable, Color},
-    rand::Rng,
+    graphics::{Bitmap, Color},
     sprite::Sprite,
-    App, PLAYDATE,
+    PLAYDATE,
 };
 
 use crate::{
     animation::{Animation, BitmapAnimation},
-    ground::Ground,
+    assets::ids,
+    pool::{self, Pool, Pooled},
     DinoGame, GameState,
 };
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-enum ObstacleKind {
-    Bird,
-    LowBird,
-    CactusSmall1,
-    CactusSmall2,
-    CactusSmall3,
-    CactusBig1,
-    CactusBig2,
-    CactusBig3,
+/// The obstacle images at their on-screen size.
+pub struct ObstacleImages {
+    bird: Arc<Vec<Bitmap>>,
+    cacti: Vec<(ObstacleKind, Arc<Bitmap>)>,
 }
 
-impl ObstacleKind {
-    fn random() -> Self {
-        let kinds = [
-            ObstacleKind::Bird,
-            ObstacleKind::LowBird,
-            ObstacleKind::CactusSmall1,
-            ObstacleKind::CactusSmall2,
-            ObstacleKind::CactusSmall3,
-            ObstacleKind::CactusBig1,
-            ObstacleKind::CactusBig2,
-            ObstacleKind::CactusBig3,
-        ];
-        let i: usize = playdate_rs::util::rand::rng().gen_range(0..kinds.len());
-        kinds[i]
+impl ObstacleImages {
+    fn get_cactus(&self, kind: ObstacleKind) -> &Bitmap {
+        let (_, image) = self.cacti.iter().find(|(k, _)| *k == kind).unwrap();
+        image
     }
 }
 
-pub struct ObstacleImages {
-    bird: Arc<BitmapTable>,
-    cactus_small1: Bitmap,
-    cactus_small2: Bitmap,
-    cactus_small3: Bitmap,
-    cactus_big1: Bitmap,
-    cactus_big2: Bitmap,
-    cactus_big3: Bitmap,
-}
-
 pub struct Obstacle {
+    id: u32,
     kind: ObstacleKind,
-    anim: Option<BitmapAnimation>,
+    anim: BitmapAnimation,
     sprite: Sprite,
 }
 
 impl Obstacle {
-    pub fn new(images: &ObstacleImages) -> Self {
-        let mut me = Self {
-            kind: ObstacleKind::random(),
-            anim: None,
-            sprite: Sprite::new(),
-        };
-        me.gen_sprite(images);
-        me
+    fn new(images: &ObstacleImages) -> Self {
+        Self {
+            id: 0,
+            kind: ObstacleKind::Bird,
+            anim: BitmapAnimation::new(images.bird.clone(), [0, 1].as_ref(), 0.1),
+            sprite: pool::new_sprite(None),
+        }
     }
 
-    fn gen_sprite(&mut self, images: &ObstacleImages) {
-        let pos_x = DISPLAY_WIDTH as f32;
-        const BIRD_Y: f32 = 88.0;
-        println!("Create {:?}", self.kind);
-        match self.kind {
-            ObstacleKind::Bird | ObstacleKind::LowBird => {
-                let image = Bitmap::new(size!(46, 34), Color::Clear);
-                let pos_y = if self.kind == ObstacleKind::Bird {
-                    BIRD_Y + 34.0
-                } else {
-                    BIRD_Y + 60.0
-                };
-                self.sprite.set_image(image, BitmapFlip::Unflipped);
-                self.sprite
-                    .set_bounds(rect!(x: pos_x, y: pos_y, w: 46.0, h: 34.0));
-                self.sprite
-                    .set_collide_rect(rect!(x: 0.0, y: 0.0, w: 46.0, h: 34.0));
-                self.sprite.collisions_enabled();
-                PLAYDATE.sprite.add_sprite(&self.sprite);
-                self.anim = Some(BitmapAnimation::new(
-                    images.bird.clone(),
-                    [0, 1].as_ref(),
-                    0.1,
-                    0.5,
-                ));
-            }
-            _ => {
-                let original_image = match self.kind {
-                    ObstacleKind::CactusSmall1 => &images.cactus_small1,
-                    ObstacleKind::CactusSmall2 => &images.cactus_small2,
-                    ObstacleKind::CactusSmall3 => &images.cactus_small3,
-                    ObstacleKind::CactusBig1 => &images.cactus_big1,
-                    ObstacleKind::CactusBig2 => &images.cactus_big2,
-                    ObstacleKind::CactusBig3 => &images.cactus_big3,
-                    _ => unreachable!(),
-                };
-                let size = original_image.get_bitmap_data().size;
-                let image: Bitmap = Bitmap::new(
-                    size!(size.width as u32 / 2, size.height as u32 / 2),
-                    crate::sprite_bg_color(),
-                );
-                PLAYDATE.graphics.push_context(&image);
-                PLAYDATE
-                    .graphics
-                    .draw_scaled_bitmap(original_image, vec2!(0, 0), vec2!(0.5, 0.5));
-                PLAYDATE.graphics.pop_context();
-                self.sprite.set_image(image, BitmapFlip::Unflipped);
-                self.sprite
-                    .set_bounds(rect!(x: pos_x, y: DISPLAY_HEIGHT as f32 - Ground::COLLIDE_HEIGHT - size.height as f32 / 2.0, w: size.width as f32 / 2.0, h: size.height as f32 / 2.0));
-                self.sprite.set_collide_rect(
-                    rect!(x: 0.0, y: 0.0, w: size.width as f32 / 2.0, h: size.height as f32 / 2.0),
-                );
-                self.sprite.collisions_enabled();
-                PLAYDATE.sprite.add_sprite(&self.sprite);
-            }
+    /// Shows the sprite as `obstacle`.
+    fn spawn(&mut self, images: &ObstacleImages, obstacle: &dino_core::obstacle::Obstacle) {
+        self.id = obstacle.get_id();
+        self.kind = obstacle.get_kind();
+        trace!("spawn {:?}", self.kind);
+        let bounds = obstacle.get_bounds();
+        if self.kind.is_bird() {
+            // the animation draws the frames
+            pool::reskin(&self.sprite, bounds.width as _, bounds.height as _, None);
+            Animation::reset(&self.anim);
+        } else {
+            // the image is already scaled, just copy it onto the sprite's background
+            let image = images.get_cactus(self.kind);
+            let size = image.get_bitmap_data().size;
+            pool::reskin(&self.sprite, size.width as _, size.height as _, Some(image));
         }
+        self.sprite.set_bounds(crate::to_rect(bounds));
+        self.sprite.set_visible(true);
     }
 
-    pub fn update(&mut self, delta: f32) {
+    pub fn update(&mut self, bounds: Rect, delta: f32) {
+        self.sprite.set_bounds(crate::to_rect(bounds));
         if DinoGame::get_game_state() != GameState::Playing {
             return;
         }
-        let pos = self.sprite.get_position();
-        let velocity = DinoGame::get().ground.get_velocity();
-        self.sprite
-            .move_to(vec2!(x: pos.x - velocity * delta, y: pos.y));
-        if let Some(anim) = &self.anim {
+        if self.kind.is_bird() {
             PLAYDATE
                 .graphics
                 .push_context(self.sprite.get_image().unwrap());
-            PLAYDATE.graphics.clear(crate::sprite_bg_color());
-            anim.update(delta);
+            PLAYDATE.graphics.clear(Color::Clear);
+            self.anim.update(delta);
             PLAYDATE.graphics.pop_context();
+            self.sprite.mark_dirty();
         }
     }
 }
 
+impl Pooled for Obstacle {
+    fn get_sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+}
+
 impl Drop for Obstacle {
     fn drop(&mut self) {
         PLAYDATE.sprite.remove_sprite(&self.sprite);
@@ -155,42 +95,64 @@ impl Drop for Obstacle {
 pub struct Obstacles {
     images: ObstacleImages,
     obstacles: Vec<Obstacle>,
+    pool: Pool<Obstacle>,
 }
 
 impl Obstacles {
     pub fn new() -> Self {
         Self {
             images: ObstacleImages {
-                bird: Arc::new(BitmapTable::open(2, 92, 68, "bird").unwrap()),
-                cactus_small1: Bitmap::open("cactus/cactus-small-1").unwrap(),
-                cactus_small2: Bitmap::open("cactus/cactus-small-2").unwrap(),
-                cactus_small3: Bitmap::open("cactus/cactus-small-3").unwrap(),
-                cactus_big1: Bitmap::open("cactus/cactus-big-1").unwrap(),
-                cactus_big2: Bitmap::open("cactus/cactus-big-2").unwrap(),
-                cactus_big3: Bitmap::open("cactus/cactus-big-3").unwrap(),
+                bird: crate::assets::table(ids::table::BIRD),
+                cacti: [
+                    (
+                        ObstacleKind::CactusSmall1,
+                        ids::image::cactus::CACTUS_SMALL_1,
+                    ),
+                    (
+                        ObstacleKind::CactusSmall2,
+                        ids::image::cactus::CACTUS_SMALL_2,
+                    ),
+                    (
+                        ObstacleKind::CactusSmall3,
+                        ids::image::cactus::CACTUS_SMALL_3,
+                    ),
+                    (ObstacleKind::CactusBig1, ids::image::cactus::CACTUS_BIG_1),
+                    (ObstacleKind::CactusBig2, ids::image::cactus::CACTUS_BIG_2),
+                    (ObstacleKind::CactusBig3, ids::image::cactus::CACTUS_BIG_3),
+                ]
+                .into_iter()
+                .map(|(kind, id)| (kind, crate::assets::image(id)))
+                .collect(),
             },
             obstacles: vec![],
+            pool: Pool::new(),
         }
     }
 
     pub fn reset(&mut self) {
-        self.obstacles.clear();
+        self.pool.reclaim(&mut self.obstacles, |_| false);
     }
 
-    pub fn update(&mut self, delta: f32) {
-        // Update obstacles
-        for obstacle in &mut self.obstacles {
-            obstacle.update(delta);
-        }
-        // remove obstacles that are off screen
-        self.obstacles.retain(|obstacle| {
-            let rect = obstacle.sprite.get_bounds();
-            // println!("update3 {:?}", rect);
-            rect.x + rect.width >= 0.0
+    pub fn update(&mut self, world: &World, delta: f32) {
+        // hide obstacles that are gone from the world
+        self.pool.reclaim(&mut self.obstacles, |obstacle| {
+            world.obstacles.iter().any(|x| x.get_id() == obstacle.id)
         });
-        // Add new obstacles
-        if self.obstacles.is_empty() {
-            self.obstacles.push(Obstacle::new(&self.images));
+        for x in world.obstacles.iter() {
+            match self.obstacles.iter_mut().find(|o| o.id == x.get_id()) {
+                // Update obstacles
+                Some(obstacle) => obstacle.update(x.get_bounds(), delta),
+                // Add new obstacles, reusing a hidden one of the same size
+                None => {
+                    let kind = x.get_kind();
+                    let mut obstacle = self
+                        .pool
+                        .take(|o| o.kind == kind || o.kind.is_bird() && kind.is_bird())
+                        .unwrap_or_else(|| Obstacle::new(&self.images));
+                    obstacle.spawn(&self.images, x);
+                    self.obstacles.push(obstacle);
+                }
+            }
         }
     }
 }
@@ -0,0 +1,102 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+use dino_core::{
+    input::Input,
+    layout,
+    settings::{OptionRow, Options, OptionsUpdate, Settings},
+};
+use playdate_rs::{
+    display::{DISPLAY_HEIGHT, DISPLAY_WIDTH},
+    graphics::{Bitmap, BitmapFlip, Color},
+    sprite::Sprite,
+    PLAYDATE,
+};
+
+use crate::{layer::Layer, set_font, text_width};
+
+/// Set by the "Options" system menu item, which can't reach the game.
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// The callback of the "Options" system menu item.
+pub fn request_open() {
+    REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// The full-screen options, opened from the system menu.
+pub struct OptionsScreen {
+    sprite: Sprite,
+    options: Option<Options>,
+}
+
+impl OptionsScreen {
+    pub fn new() -> Self {
+        let bitmap = Bitmap::new(size!(DISPLAY_WIDTH as _, DISPLAY_HEIGHT as _), Color::White);
+        let sprite = Sprite::new();
+        sprite.set_image(bitmap, BitmapFlip::Unflipped);
+        Layer::Options.apply(&sprite);
+        sprite.set_bounds(rect!(x: 0.0, y: 0.0, w: DISPLAY_WIDTH as _, h: DISPLAY_HEIGHT as _));
+        sprite.set_visible(false);
+        PLAYDATE.sprite.add_sprite(&sprite);
+        Self {
+            sprite,
+            options: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.options.is_some()
+    }
+
+    /// Opens the screen if the system menu asked for it.
+    pub fn open_if_requested(&mut self, settings: &Settings) {
+        if REQUESTED.swap(false, Ordering::Relaxed) && !self.is_open() {
+            self.options = Some(Options::new());
+            self.sprite.set_visible(true);
+            self.draw(settings);
+        }
+    }
+
+    /// Lets the options take the input. The caller applies changed settings
+    /// and saves them once the screen is closed.
+    pub fn update(&mut self, input: &Input, settings: &mut Settings) -> OptionsUpdate {
+        let Some(options) = &mut self.options else {
+            return OptionsUpdate::Closed;
+        };
+        let update = options.update(input, settings);
+        if update == OptionsUpdate::Closed {
+            self.options = None;
+            self.sprite.set_visible(false);
+        } else {
+            self.draw(settings);
+        }
+        update
+    }
+
+    fn draw(&mut self, settings: &Settings) {
+        let Some(options) = &self.options else {
+            return;
+        };
+        let centered = |text: &str, y: i32| {
+            let x = layout::centered_x(text_width(text));
+            PLAYDATE.graphics.draw_text(text, vec2!(x, y));
+        };
+        let bitmap = self.sprite.get_image().unwrap();
+        PLAYDATE.graphics.push_context(bitmap);
+        PLAYDATE.graphics.clear(Color::White);
+        set_font();
+        centered("OPTIONS", layout::OPTIONS_TITLE_Y);
+        for (i, row) in OptionRow::ALL.iter().enumerate() {
+            let y = layout::options_row_y(i);
+            let selected = *row == options.get_selected();
+            let name = layout::options_row_text(*row, selected);
+            PLAYDATE
+                .graphics
+                .draw_text(&name, vec2!(layout::OPTIONS_LEFT_X, y));
+            let value = settings.get_value_text(*row);
+            let x = layout::OPTIONS_RIGHT_X - text_width(&value) as i32;
+            PLAYDATE.graphics.draw_text(&value, vec2!(x, y));
+        }
+        centered(layout::OPTIONS_HINT, layout::OPTIONS_HINT_Y);
+        PLAYDATE.graphics.pop_context();
+        self.sprite.mark_dirty();
+    }
+}
@@ -0,0 +1,34 @@
+//! Drawing straight onto the frame, after the sprites.
+//!
+//! Sprites only redraw the regions marked dirty, so nothing would erase what
+//! is drawn over them, like the FPS counter and the developer overlays.
+//! Whatever draws over the sprites notes its rects here, and [`erase`] marks
+//! them dirty on the next frame: the sprites beneath paint over them, and
+//! what is still shown is drawn again on top.
+
+use alloc::vec::Vec;
+use playdate_rs::{
+    math::{Rect, SideOffsets},
+    PLAYDATE,
+};
+use spin::Mutex;
+
+static DRAWN: Mutex<Vec<Rect<i32>>> = Mutex::new(Vec::new());
+
+/// Notes that `rect` was drawn over the sprites this frame.
+pub fn add(rect: Rect<i32>) {
+    DRAWN.lock().push(rect);
+}
+
+/// Marks the rects drawn last frame dirty. Called before the sprites are
+/// drawn.
+pub fn erase() {
+    for rect in DRAWN.lock().drain(..) {
+        PLAYDATE.sprite.add_dirty_rect(SideOffsets::new(
+            rect.x,
+            rect.x + rect.width,
+            rect.y,
+            rect.y + rect.height,
+        ));
+    }
+}
@@ -0,0 +1,102 @@
+//! Free lists for the sprites that come and go during a run. A view that
+//! scrolls off is hidden and kept, and the next spawn re-skins it, so once
+//! the pools are warm gameplay creates no sprites or bitmaps.
+
+use alloc::{sync::Arc, vec, vec::Vec};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use playdate_rs::{
+    graphics::{Bitmap, BitmapFlip, Color},
+    sprite::Sprite,
+    PLAYDATE,
+};
+
+use crate::layer::Layer;
+
+/// Sprites and bitmaps the pools had to create since launch.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// How many sprites and bitmaps the pools created. It stops growing once
+/// every pool holds enough views for what is on screen.
+#[cfg(feature = "profiler")]
+pub fn get_allocations() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+fn count_allocation() {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A hidden sprite in the display list, for a pooled view to show later.
+/// Views that always look the same get their `image` here, shared with the
+/// asset cache.
+pub fn new_sprite(image: Option<&Arc<Bitmap>>) -> Sprite {
+    count_allocation();
+    let sprite = Sprite::new();
+    Layer::Scene.apply(&sprite);
+    if let Some(image) = image {
+        crate::assets::show_image(&sprite, image.clone());
+    }
+    sprite.set_visible(false);
+    PLAYDATE.sprite.add_sprite(&sprite);
+    sprite
+}
+
+/// Gives the sprite `image`, or a blank `width`×`height` bitmap to draw on
+/// when there is no image. The sprite's bitmap is drawn over and kept if it
+/// already has that size.
+pub fn reskin(sprite: &Sprite, width: u32, height: u32, image: Option<&Bitmap>) {
+    let fits = sprite.get_image().is_some_and(|bitmap| {
+        let size = bitmap.get_bitmap_data().size;
+        (size.width as u32, size.height as u32) == (width, height)
+    });
+    if !fits {
+        count_allocation();
+        let bitmap = Bitmap::new(size!(width, height), Color::Clear);
+        sprite.set_image(bitmap, BitmapFlip::Unflipped);
+    }
+    PLAYDATE.graphics.push_context(sprite.get_image().unwrap());
+    PLAYDATE.graphics.clear(Color::Clear);
+    if let Some(image) = image {
+        PLAYDATE
+            .graphics
+            .draw_bitmap(image, vec2!(0, 0), BitmapFlip::Unflipped);
+    }
+    PLAYDATE.graphics.pop_context();
+    sprite.mark_dirty();
+}
+
+pub trait Pooled {
+    fn get_sprite(&self) -> &Sprite;
+}
+
+pub struct Pool<T: Pooled> {
+    free: Vec<T>,
+}
+
+impl<T: Pooled> Pool<T> {
+    pub fn new() -> Self {
+        Self { free: vec![] }
+    }
+
+    /// A free view that `fits`, so it can be re-skinned without a new
+    /// bitmap. Views that don't fit are left for later spawns, which keeps
+    /// the pool from trading bitmaps back and forth.
+    pub fn take(&mut self, fits: impl Fn(&T) -> bool) -> Option<T> {
+        let index = self.free.iter().position(fits)?;
+        Some(self.free.swap_remove(index))
+    }
+
+    /// Hides and keeps the views that `keep` rejects.
+    pub fn reclaim(&mut self, views: &mut Vec<T>, keep: impl Fn(&T) -> bool) {
+        let mut i = 0;
+        while i < views.len() {
+            if keep(&views[i]) {
+                i += 1;
+            } else {
+                let view = views.swap_remove(i);
+                view.get_sprite().set_visible(false);
+                self.free.push(view);
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use alloc::{sync::Arc, vec, vec::Vec};
+use dino_core::{power_up::PowerUpKind, Event, World};
+use playdate_rs::{graphics::Bitmap, sprite::Sprite, PLAYDATE};
+
+use crate::{
+    assets::{ids, ImageId, Sound, SoundId},
+    pool::{self, Pool, Pooled},
+};
+
+fn image_id(kind: PowerUpKind) -> ImageId {
+    match kind {
+        PowerUpKind::Shield => ids::image::power_up::SHIELD,
+        PowerUpKind::DoubleJump => ids::image::power_up::DOUBLE_JUMP,
+        PowerUpKind::SlowTime => ids::image::power_up::SLOW_TIME,
+    }
+}
+
+fn sound_id(kind: PowerUpKind) -> SoundId {
+    match kind {
+        PowerUpKind::Shield => ids::sound::power_up::SHIELD,
+        PowerUpKind::DoubleJump => ids::sound::power_up::DOUBLE_JUMP,
+        PowerUpKind::SlowTime => ids::sound::power_up::SLOW_TIME,
+    }
+}
+
+struct PowerUp {
+    id: u32,
+    sprite: Sprite,
+}
+
+impl PowerUp {
+    fn new() -> Self {
+        Self {
+            id: 0,
+            sprite: pool::new_sprite(None),
+        }
+    }
+
+    /// Shows the sprite as `power_up`, drawing `image` over the last one.
+    fn spawn(&mut self, image: &Bitmap, power_up: &dino_core::power_up::PowerUp) {
+        self.id = power_up.get_id();
+        let size = image.get_bitmap_data().size;
+        pool::reskin(&self.sprite, size.width as _, size.height as _, Some(image));
+        self.sprite
+            .set_bounds(crate::to_rect(power_up.get_bounds()));
+        self.sprite.set_visible(true);
+    }
+}
+
+impl Pooled for PowerUp {
+    fn get_sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+}
+
+impl Drop for PowerUp {
+    fn drop(&mut self) {
+        PLAYDATE.sprite.remove_sprite(&self.sprite);
+    }
+}
+
+pub struct PowerUps {
+    images: Vec<Arc<Bitmap>>,
+    sounds: Vec<Arc<Sound>>,
+    power_ups: Vec<PowerUp>,
+    pool: Pool<PowerUp>,
+}
+
+impl PowerUps {
+    pub fn new() -> Self {
+        Self {
+            images: PowerUpKind::ALL
+                .iter()
+                .map(|k| crate::assets::image(image_id(*k)))
+                .collect(),
+            sounds: PowerUpKind::ALL
+                .iter()
+                .map(|k| crate::assets::sound(sound_id(*k)))
+                .collect(),
+            power_ups: vec![],
+            pool: Pool::new(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.pool.reclaim(&mut self.power_ups, |_| false);
+    }
+
+    fn index(kind: PowerUpKind) -> usize {
+        PowerUpKind::ALL.iter().position(|k| *k == kind).unwrap()
+    }
+
+    pub fn update(&mut self, world: &World) {
+        // hide power-ups that were collected or scrolled off
+        self.pool.reclaim(&mut self.power_ups, |p| {
+            world.power_ups.iter().any(|x| x.get_id() == p.id)
+        });
+        for x in world.power_ups.iter() {
+            match self.power_ups.iter_mut().find(|p| p.id == x.get_id()) {
+                Some(power_up) => power_up.sprite.set_bounds(crate::to_rect(x.get_bounds())),
+                None => {
+                    // all power-up images have the same size
+                    let image = &self.images[Self::index(x.get_kind())];
+                    let mut power_up = self.pool.take(|_| true).unwrap_or_else(PowerUp::new);
+                    power_up.spawn(image, x);
+                    self.power_ups.push(power_up);
+                }
+            }
+        }
+        for event in world.events() {
+            match event {
+                Event::PowerUpCollected(kind) => {
+                    self.sounds[Self::index(*kind)].play();
+                }
+                Event::ShieldBroken(_) => {
+                    self.sounds[Self::index(PowerUpKind::Shield)].play();
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! The player profiles, see [`dino_core::profile`].
+//!
+//! Every profile keeps its save, wallet, skins and replays in
+//! `profiles/<name>/`, and the `profile` file names the one in use. The first
+//! launch of this version moves the `record` file the first release kept at
+//! the top of the data folder into [`DEFAULT_PROFILE`].
+
+use alloc::{format, string::String, vec, vec::Vec};
+use dino_core::{
+    input::Input,
+    layout,
+    profile::{self, ProfileMenu, ProfileMenuUpdate, DEFAULT_PROFILE, PROFILES_DIR},
+};
+use playdate_rs::{
+    fs::{File, Write},
+    graphics::Color,
+    sprite::Sprite,
+    sys::FileOptions,
+    PLAYDATE,
+};
+use spin::{Lazy, RwLock};
+
+use crate::{
+    high_scores::{self, Crank},
+    set_font,
+};
+
+const CURRENT_FILE: &str = "profile";
+/// The files the first release kept for its only player. [`crate::save`]
+/// imports them.
+const LEGACY_FILES: [&str; 1] = ["record"];
+
+/// The profile the files are read from and written to.
+static CURRENT: Lazy<RwLock<String>> = Lazy::new(|| RwLock::new(String::from(DEFAULT_PROFILE)));
+
+pub fn get_current() -> String {
+    CURRENT.read().clone()
+}
+
+/// Where the current profile keeps the file `name`.
+pub fn path(name: &str) -> String {
+    format!("{}/{}/{}", PROFILES_DIR, CURRENT.read(), name)
+}
+
+/// The profiles on the device, sorted by name.
+fn list() -> Vec<String> {
+    let mut names = vec![];
+    let _ = PLAYDATE.file.list_files(PROFILES_DIR, false, |name| {
+        if let Some(name) = name.strip_suffix('/') {
+            if profile::is_valid_name(name) {
+                names.push(String::from(name));
+            }
+        }
+    });
+    names.sort();
+    names
+}
+
+/// Picks the profile used last, or the first one if the `profile` file is
+/// missing or names none, creating the default one from the files of the
+/// first release on the first launch. Returns every profile.
+pub fn load() -> Vec<String> {
+    let mut names = list();
+    if names.is_empty() {
+        migrate_legacy_files();
+        names = list();
+    }
+    let last = read_last();
+    let name = if names.contains(&last) {
+        last
+    } else {
+        names
+            .first()
+            .cloned()
+            .unwrap_or(String::from(DEFAULT_PROFILE))
+    };
+    info!("profile: {}", name);
+    *CURRENT.write() = name;
+    names
+}
+
+/// The name in the `profile` file, empty if it can't be read.
+fn read_last() -> String {
+    let Ok(mut file) = File::open(CURRENT_FILE, FileOptions::kFileReadData) else {
+        return String::new();
+    };
+    match file.read_to_string() {
+        Ok(text) => String::from(text.trim()),
+        Err(e) => {
+            error!("unreadable {} file: {}", CURRENT_FILE, e);
+            String::new()
+        }
+    }
+}
+
+fn migrate_legacy_files() {
+    let _ = PLAYDATE.file.mkdir(PROFILES_DIR);
+    let _ = PLAYDATE
+        .file
+        .mkdir(format!("{}/{}", PROFILES_DIR, DEFAULT_PROFILE));
+    *CURRENT.write() = String::from(DEFAULT_PROFILE);
+    info!("moving the files into profile {}", DEFAULT_PROFILE);
+    // a player who never finished a run has none
+    for name in LEGACY_FILES {
+        let _ = PLAYDATE.file.rename(name, path(name));
+    }
+}
+
+/// Makes `name` the profile in use, and the one picked on the next launch.
+pub fn switch(name: &str) {
+    info!("switch to profile {}", name);
+    *CURRENT.write() = String::from(name);
+    let written = File::open(CURRENT_FILE, FileOptions::kFileWrite).and_then(|mut file| {
+        file.write_all(name.as_bytes())?;
+        file.flush()
+    });
+    if let Err(e) = written {
+        error!("cannot write the {} file: {}", CURRENT_FILE, e);
+    }
+}
+
+/// Adds an empty profile.
+fn create(name: &str) {
+    let _ = PLAYDATE.file.mkdir(format!("{}/{}", PROFILES_DIR, name));
+}
+
+/// The profile switcher, opened with Down on the Ready screen.
+pub struct ProfileScreen {
+    sprite: Sprite,
+    menu: Option<ProfileMenu>,
+    names: Vec<String>,
+    crank: Crank,
+}
+
+impl ProfileScreen {
+    pub fn new(names: Vec<String>) -> Self {
+        Self {
+            sprite: high_scores::screen_sprite(),
+            menu: None,
+            names,
+            crank: Crank::new(),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.menu.is_some()
+    }
+
+    pub fn open(&mut self) {
+        let current = get_current();
+        let index = self.names.iter().position(|n| *n == current).unwrap_or(0);
+        self.menu = Some(ProfileMenu::new(index));
+        self.crank = Crank::new();
+        self.sprite.set_visible(true);
+        self.draw();
+    }
+
+    pub fn get_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Lets the switcher take the input. A new profile's folder is made
+    /// before it is returned, and the caller switches to it.
+    pub fn update(&mut self, input: &Input) -> ProfileMenuUpdate {
+        let crank_steps = self.crank.read_steps();
+        let Some(menu) = &mut self.menu else {
+            return ProfileMenuUpdate::Closed;
+        };
+        let update = menu.update(input, crank_steps, &self.names);
+        match &update {
+            ProfileMenuUpdate::Open => {
+                self.draw();
+                return update;
+            }
+            ProfileMenuUpdate::Create(name) => {
+                create(name);
+                self.names = list();
+            }
+            ProfileMenuUpdate::Switch(_) | ProfileMenuUpdate::Closed => {}
+        }
+        self.close();
+        update
+    }
+
+    fn close(&mut self) {
+        self.menu = None;
+        self.sprite.set_visible(false);
+    }
+
+    fn draw(&mut self) {
+        let Some(menu) = &self.menu else {
+            return;
+        };
+        let bitmap = self.sprite.get_image().unwrap();
+        PLAYDATE.graphics.push_context(bitmap);
+        if let Some(entry) = menu.get_naming() {
+            let (title, text) = (layout::PROFILES_NAMING_TITLE, layout::PROFILES_NAMING_TEXT);
+            high_scores::draw_letters(title, text, entry);
+        } else {
+            let center_x = layout::CENTER_X as i32;
+            let current = get_current();
+            PLAYDATE.graphics.clear(Color::White);
+            set_font();
+            high_scores::draw_centered(layout::PROFILES_TITLE, center_x, layout::PROFILES_TITLE_Y);
+            let new_row = ProfileMenu::can_create(&self.names).then_some(layout::PROFILES_NEW_ROW);
+            let rows = self.names.iter().map(String::as_str).chain(new_row);
+            for (i, name) in rows.enumerate() {
+                let selected = i == menu.get_selected();
+                let playing = i < self.names.len() && *name == current;
+                let row = layout::profiles_row_text(name, selected, playing);
+                PLAYDATE.graphics.draw_text(
+                    &row,
+                    vec2!(layout::PROFILES_LEFT_X, layout::profiles_row_y(i)),
+                );
+            }
+            high_scores::draw_centered(layout::PROFILES_HINT, center_x, layout::PROFILES_HINT_Y);
+        }
+        PLAYDATE.graphics.pop_context();
+        self.sprite.mark_dirty();
+    }
+}
@@ -0,0 +1,152 @@
+//! Frame timings for the profiler overlay, compiled in with the `profiler`
+//! feature. Each subsystem the frame runs is timed as a lap, from the end
+//! of the previous one.
+//!
+//! The heap belongs to `playdate-rs`, which doesn't report its size, so the
+//! overlay shows what the game itself keeps around instead: the cached
+//! assets and the sprites the pools created.
+
+use alloc::{format, string::String, vec::Vec};
+use playdate_rs::{graphics::Color, PLAYDATE};
+
+use crate::{assets, overdraw, pool, set_font};
+
+/// Frames the averages and worst cases are taken over.
+const WINDOW: usize = 30;
+/// The game runs at the default 30 fps.
+const FRAME_BUDGET_MS: f32 = 1000.0 / 30.0;
+/// Below the FPS counter.
+const OVERLAY_TOP: i32 = 16;
+const OVERLAY_WIDTH: i32 = 160;
+const LINE_HEIGHT: i32 = 13;
+const AVERAGE_X: i32 = 90;
+const WORST_X: i32 = 125;
+
+struct Section {
+    name: &'static str,
+    /// Milliseconds, indexed by frame.
+    samples: [f32; WINDOW],
+}
+
+impl Section {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            samples: [0.0; WINDOW],
+        }
+    }
+
+    fn get_average(&self) -> f32 {
+        self.samples.iter().sum::<f32>() / WINDOW as f32
+    }
+
+    fn get_worst(&self) -> f32 {
+        self.samples.iter().copied().fold(0.0, f32::max)
+    }
+}
+
+pub struct Profiler {
+    enabled: bool,
+    sections: Vec<Section>,
+    total: Section,
+    /// Index of the current frame in the samples.
+    frame: usize,
+    /// Seconds into the frame the last lap ended at.
+    lap_end: f32,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            sections: Vec::new(),
+            total: Section::new("frame"),
+            frame: 0,
+            lap_end: 0.0,
+        }
+    }
+
+    /// Timing stops while the overlay is hidden.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn start_frame(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        self.frame = (self.frame + 1) % WINDOW;
+        self.lap_end = 0.0;
+        PLAYDATE.system.reset_elapsed_time();
+    }
+
+    /// Records the time since the last lap as `name`'s.
+    pub fn lap(&mut self, name: &'static str) {
+        if !self.enabled {
+            return;
+        }
+        let now = PLAYDATE.system.get_elapsed_time();
+        let ms = (now - self.lap_end) * 1000.0;
+        self.lap_end = now;
+        let index = match self.sections.iter().position(|s| s.name == name) {
+            Some(index) => index,
+            None => {
+                self.sections.push(Section::new(name));
+                self.sections.len() - 1
+            }
+        };
+        self.sections[index].samples[self.frame] = ms;
+    }
+
+    /// Logs the laps of a frame that went over budget.
+    pub fn end_frame(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        let ms = PLAYDATE.system.get_elapsed_time() * 1000.0;
+        self.total.samples[self.frame] = ms;
+        if ms > FRAME_BUDGET_MS {
+            let laps: Vec<String> = self
+                .sections
+                .iter()
+                .map(|s| format!("{} {:.1}", s.name, s.samples[self.frame]))
+                .collect();
+            warn!("slow frame: {:.1} ms ({})", ms, laps.join(", "));
+        }
+    }
+
+    /// Draws the averages and worst cases in milliseconds, straight onto
+    /// the frame. The panel is erased the frame after it's hidden.
+    pub fn draw(&self) {
+        if !self.enabled {
+            return;
+        }
+        let lines = self.sections.len() as i32 + 3;
+        let panel = rect!(x: 0, y: OVERLAY_TOP, w: OVERLAY_WIDTH, h: lines * LINE_HEIGHT);
+        PLAYDATE.graphics.fill_rect(panel, Color::White);
+        overdraw::add(panel);
+        set_font();
+        let mut y = OVERLAY_TOP;
+        let mut draw_line = |name: &str, average: String, worst: String| {
+            PLAYDATE.graphics.draw_text(name, vec2!(2, y));
+            PLAYDATE.graphics.draw_text(average, vec2!(AVERAGE_X, y));
+            PLAYDATE.graphics.draw_text(worst, vec2!(WORST_X, y));
+            y += LINE_HEIGHT;
+        };
+        draw_line("ms", "avg".into(), "max".into());
+        for section in self.sections.iter().chain([&self.total]) {
+            draw_line(
+                section.name,
+                format!("{:.1}", section.get_average()),
+                format!("{:.1}", section.get_worst()),
+            );
+        }
+        let memory = assets::memory_report();
+        let text = format!(
+            "assets {} KiB, pool {}",
+            memory.bitmap_bytes / 1024,
+            pool::get_allocations()
+        );
+        PLAYDATE.graphics.draw_text(text, vec2!(2, y));
+    }
+}
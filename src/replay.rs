@@ -0,0 +1,84 @@
+use alloc::{format, string::String};
+use dino_core::{input::Input, replay::Replay, shop::Perks, World};
+use playdate_rs::{
+    fs::{File, Write},
+    sys::FileOptions,
+    PLAYDATE,
+};
+
+use crate::profile;
+
+const REPLAY_DIR: &str = "replays";
+
+/// Records every run, so it can be checked later with `replay-verifier`.
+pub struct Recorder {
+    replay: Option<Replay>,
+    disabled: bool,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            replay: None,
+            disabled: false,
+        }
+    }
+
+    /// Stops recording for the rest of the session, once cheats changed a
+    /// run in a way a replay can't reproduce.
+    #[cfg(feature = "cheats")]
+    pub fn disable(&mut self) {
+        self.disabled = true;
+        self.replay = None;
+    }
+
+    /// Whether runs are recorded, which they are until cheats are used.
+    pub fn is_enabled(&self) -> bool {
+        !self.disabled
+    }
+
+    /// Starts recording a run played with the theme pack in `theme`, empty
+    /// for the bundled assets.
+    pub fn start(&mut self, seed: u64, world: &World, perks: Perks, theme: &str) {
+        if self.disabled {
+            return;
+        }
+        let mut replay = Replay::new(seed, world.lives.get_max(), world.get_difficulty(), perks);
+        replay.theme = Some(String::from(theme)).filter(|t| !t.is_empty());
+        replay.shapes = world.obstacles.custom_shapes();
+        replay.dino_shape = world.dino.custom_shape();
+        self.replay = Some(replay);
+    }
+
+    pub fn record(&mut self, input: Input, delta_ms: u32, world: &World) {
+        if let Some(replay) = &mut self.replay {
+            replay.record(input, delta_ms, world);
+        }
+    }
+
+    /// Saves the finished run as `replays/last.txt` in the profile's folder,
+    /// and also as `replays/best.txt` if it set a new record.
+    pub fn finish(&mut self, is_record: bool) {
+        let Some(replay) = self.replay.take() else {
+            return;
+        };
+        let text = format!("{}", replay);
+        let dir = profile::path(REPLAY_DIR);
+        let _ = PLAYDATE.file.mkdir(&dir);
+        Self::save(&format!("{}/last.txt", dir), &text);
+        if is_record {
+            Self::save(&format!("{}/best.txt", dir), &text);
+        }
+    }
+
+    /// Writes `text` to `path`, or logs why it couldn't and skips the replay.
+    fn save(path: &str, text: &str) {
+        let written = File::open(path, FileOptions::kFileWrite).and_then(|mut file| {
+            file.write_all(text.as_bytes())?;
+            file.flush()
+        });
+        if let Err(e) = written {
+            error!("cannot write the replay {}: {}", path, e);
+        }
+    }
+}
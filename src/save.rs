@@ -0,0 +1,102 @@
+//! Reading and writing the save file, see [`dino_core::save`].
+//!
+//! A save is written to `save.tmp` and then renamed over `save`, so a crash
+//! or a flat battery mid-write leaves the last one intact. The two before it
+//! are kept as `save.1` and `save.2`. When `save` is damaged it is moved to
+//! `save.bad` and the newest readable backup is used instead. The `record`
+//! file the first release kept is imported the first time. All of them live
+//! in the current profile's folder, see [`crate::profile`].
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use dino_core::save::{Recovery, Save, BACKUP_FILES, SAVE_FILE, TEMP_FILE};
+use playdate_rs::{
+    fs::{File, Read, Write},
+    io,
+    sys::FileOptions,
+    PLAYDATE,
+};
+
+use crate::profile;
+
+const DAMAGED_FILE: &str = "save.bad";
+const LEGACY_RECORD_FILE: &str = "record";
+
+/// The file's text, or `None` if it doesn't exist. Bytes that aren't UTF-8
+/// are replaced, which the checksum then catches.
+fn read_text(path: &str) -> Option<String> {
+    let mut file = File::open(path, FileOptions::kFileReadData).ok()?;
+    let mut bytes = Vec::new();
+    let _ = file.read_to_end(&mut bytes);
+    Some(String::from_utf8_lossy(&bytes).to_string())
+}
+
+pub fn load() -> (Save, Option<Recovery>) {
+    let recovered = Save::recover(|name| read_text(&profile::path(name)));
+    for (name, e) in &recovered.errors {
+        if *name == TEMP_FILE {
+            debug!("{}: {}", name, e);
+        } else {
+            error!("damaged save {}: {}", name, e);
+        }
+    }
+    if recovered.errors.iter().any(|(name, _)| *name == SAVE_FILE) {
+        // keep it for a bug report, out of the backups' way
+        let _ = PLAYDATE
+            .file
+            .rename(profile::path(SAVE_FILE), profile::path(DAMAGED_FILE));
+    }
+    match recovered.save {
+        Some((name, save)) => {
+            if recovered.recovery.is_some() {
+                warn!("recovered the save from {}", name);
+            }
+            (save, recovered.recovery)
+        }
+        None if recovered.recovery.is_some() => (Save::default(), recovered.recovery),
+        None => (migrate_legacy_record().unwrap_or_default(), None),
+    }
+}
+
+fn migrate_legacy_record() -> Option<Save> {
+    let path = profile::path(LEGACY_RECORD_FILE);
+    let text = read_text(&path)?;
+    let save = Save::from_legacy_record(&text).unwrap_or_else(|| {
+        warn!("unreadable {} file: {:?}", LEGACY_RECORD_FILE, text);
+        Save::default()
+    });
+    info!("migrating the {} file", LEGACY_RECORD_FILE);
+    // the old file stays until the new one is written
+    if write(&save).is_ok() {
+        let _ = PLAYDATE.file.unlink(path, false);
+    }
+    Some(save)
+}
+
+fn write(save: &Save) -> io::Result<()> {
+    let text = save.to_string();
+    let (save_path, temp_path) = (profile::path(SAVE_FILE), profile::path(TEMP_FILE));
+    // closes the file before it is renamed
+    File::open(&temp_path, FileOptions::kFileWrite).and_then(|mut file| {
+        file.write_all(text.as_bytes())?;
+        file.flush()
+    })?;
+    for pair in BACKUP_FILES.windows(2).rev() {
+        let _ = PLAYDATE
+            .file
+            .rename(profile::path(pair[0]), profile::path(pair[1]));
+    }
+    let _ = PLAYDATE
+        .file
+        .rename(&save_path, profile::path(BACKUP_FILES[0]));
+    let _ = PLAYDATE.file.rename(temp_path, save_path);
+    Ok(())
+}
+
+pub fn store(save: &Save) {
+    if let Err(e) = write(save) {
+        error!("cannot write the save: {}", e);
+    }
+}
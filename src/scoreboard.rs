@@ -1,101 +1,122 @@
 use playdate_rs::{
-    display::{DISPLAY_HEIGHT, DISPLAY_WIDTH},
-    fs::{File, Write},
+    display::DISPLAY_WIDTH,
     graphics::{Bitmap, BitmapFlip, Color},
-    sound::FilePlayer,
     sprite::Sprite,
-    sys::FileOptions,
     PLAYDATE,
 };
 
-use crate::{DinoGame, GameState, FONT};
+use alloc::{string::String, sync::Arc, vec::Vec};
+use dino_core::{layout, Event, World};
+
+use crate::{
+    assets::{ids, Sound},
+    layer::Layer,
+    set_font, text_width,
+};
+
+/// What the scoreboard shows, so it is only redrawn when that changes.
+#[derive(PartialEq)]
+struct Hud {
+    score: String,
+    /// Hearts to draw, none when the run has a single life.
+    lives: u32,
+    coins: u32,
+    power_ups: Vec<String>,
+}
 
 pub struct Scoreboard {
-    accumulated_time: f32,
     sprite: Sprite,
-    achievement_audio: FilePlayer,
-    record: MaxRecord,
+    shown: Option<Hud>,
+    heart: Arc<Bitmap>,
+    coin: Arc<Bitmap>,
+    achievement_audio: Arc<Sound>,
+    record: i32,
 }
 
 impl Scoreboard {
-    pub fn new() -> Self {
-        let bitmap = Bitmap::new(size!(DISPLAY_WIDTH as _, DISPLAY_HEIGHT as _), Color::Clear);
+    pub fn new(record: i32) -> Self {
+        let bitmap = Bitmap::new(
+            size!(DISPLAY_WIDTH as _, layout::HUD_HEIGHT as _),
+            Color::Clear,
+        );
         let sprite = Sprite::new();
         sprite.set_image(bitmap, BitmapFlip::Unflipped);
-        sprite.set_z_index(10000);
-        sprite.set_bounds(rect!(x: 0.0, y: 0.0, w: DISPLAY_WIDTH as _, h: DISPLAY_HEIGHT as _));
+        Layer::Hud.apply(&sprite);
+        sprite.set_bounds(rect!(x: 0.0, y: 0.0, w: DISPLAY_WIDTH as _, h: layout::HUD_HEIGHT as _));
         PLAYDATE.sprite.add_sprite(&sprite);
         Self {
-            accumulated_time: 0.0,
             sprite,
-            achievement_audio: FilePlayer::open("achievement").unwrap(),
-            record: MaxRecord::new(),
+            shown: None,
+            heart: crate::assets::image(ids::image::HEART),
+            coin: crate::coin::coin_bitmap(),
+            achievement_audio: crate::assets::sound(ids::sound::ACHIEVEMENT),
+            record,
         }
     }
 
-    pub fn reset(&mut self) {
-        self.accumulated_time = 0.0;
-    }
-
-    fn get_score(&self) -> i32 {
-        (self.accumulated_time * 10.0) as i32
+    pub fn set_record(&mut self, record: i32) {
+        self.record = record;
     }
 
-    fn update_sprite(&mut self) {
-        let score = self.get_score();
-        let text = format!("HI  {:05}  {:05}", self.record.get(), score);
+    fn update_sprite(&mut self, score: i32, world: &World) {
+        let hud = Hud {
+            score: layout::score_text(self.record, score),
+            lives: if world.lives.get_max() > 1 {
+                world.lives.get_remaining()
+            } else {
+                0
+            },
+            coins: world.coins.get_collected(),
+            power_ups: world
+                .power_ups
+                .active()
+                .map(|(kind, time_left)| layout::power_up_text(kind, time_left))
+                .collect(),
+        };
+        // most frames show the same numbers as the last one
+        if self.shown.as_ref() == Some(&hud) {
+            return;
+        }
+        let (x, y) = layout::score_position(text_width(&hud.score));
         let bitmap = self.sprite.get_image().unwrap();
         PLAYDATE.graphics.push_context(bitmap);
-        PLAYDATE.graphics.clear(crate::sprite_bg_color());
-        PLAYDATE.graphics.set_font(&FONT);
-        PLAYDATE.graphics.draw_text(
-            &text,
-            vec2!(
-                DISPLAY_WIDTH as i32 - FONT.get_text_width(&text, 0) as i32 - 2,
-                0
-            ),
-        );
-        PLAYDATE.graphics.pop_context();
-    }
-
-    pub fn update(&mut self, delta: f32) {
-        let state = DinoGame::get_game_state();
-        if state == GameState::Playing {
-            self.accumulated_time += delta;
-            let score = self.get_score();
-            if score > 0 && score % 100 == 0 {
-                self.achievement_audio.play(1);
-            }
+        PLAYDATE.graphics.clear(Color::Clear);
+        set_font();
+        PLAYDATE.graphics.draw_text(&hud.score, vec2!(x, y));
+        for i in 0..hud.lives {
+            let (x, y) = layout::life_icon_position(x, i);
+            PLAYDATE
+                .graphics
+                .draw_bitmap(&*self.heart, vec2!(x, y), BitmapFlip::Unflipped);
         }
-        if state == GameState::Dead {
-            self.record.update(self.get_score());
+        if hud.coins > 0 {
+            let text = layout::coin_text(hud.coins);
+            let (icon, pos) = layout::coin_counter_position(text_width(&text));
+            PLAYDATE.graphics.draw_bitmap(
+                &*self.coin,
+                vec2!(icon.0, icon.1),
+                BitmapFlip::Unflipped,
+            );
+            PLAYDATE.graphics.draw_text(&text, vec2!(pos.0, pos.1));
         }
-        self.update_sprite();
-    }
-}
-
-struct MaxRecord {
-    value: i32,
-}
-
-impl MaxRecord {
-    pub fn new() -> Self {
-        let value = File::open("record", FileOptions::kFileReadData)
-            .map(|mut f| f.read_to_string().unwrap().parse::<i32>().unwrap())
-            .unwrap_or_default();
-        Self { value }
-    }
-
-    pub fn get(&self) -> i32 {
-        self.value
+        for (i, text) in hud.power_ups.iter().enumerate() {
+            let (x, y) = layout::power_up_position(text_width(text), i as u32);
+            PLAYDATE.graphics.draw_text(text, vec2!(x, y));
+        }
+        PLAYDATE.graphics.pop_context();
+        self.sprite.mark_dirty();
+        self.shown = Some(hud);
     }
 
-    pub fn update(&mut self, score: i32) {
-        if score > self.value {
-            self.value = score;
-            let mut file = File::open("record", FileOptions::kFileWrite).unwrap();
-            let s = format!("{}", self.value);
-            file.write_all(s.as_bytes()).unwrap();
+    pub fn update(&mut self, world: &World) {
+        let score = world.scoreboard.get_score();
+        if world
+            .events()
+            .iter()
+            .any(|e| matches!(e, Event::Achievement(_)))
+        {
+            self.achievement_audio.play();
         }
+        self.update_sprite(score, world);
     }
 }
@@ -0,0 +1,122 @@
+use alloc::format;
+use dino_core::{
+    input::Input,
+    layout,
+    shop::{Item, Shop, ShopUpdate, Wallet},
+};
+use playdate_rs::{
+    display::{DISPLAY_HEIGHT, DISPLAY_WIDTH},
+    fs::{File, Write},
+    graphics::{Bitmap, BitmapFlip, Color},
+    sprite::Sprite,
+    sys::FileOptions,
+    PLAYDATE,
+};
+
+use crate::{layer::Layer, profile, set_font, text_width};
+
+const WALLET_FILE: &str = "wallet";
+
+/// The coins and shop items owned, kept in the profile's `wallet` file. An
+/// unreadable file counts as an empty wallet.
+pub fn load_wallet() -> Wallet {
+    let Ok(mut file) = File::open(profile::path(WALLET_FILE), FileOptions::kFileReadData) else {
+        return Wallet::default();
+    };
+    match file.read_to_string() {
+        Ok(text) => Wallet::parse(&text),
+        Err(e) => {
+            error!("unreadable {} file: {}", WALLET_FILE, e);
+            Wallet::default()
+        }
+    }
+}
+
+pub fn save_wallet(wallet: &Wallet) {
+    let s = format!("{}", wallet);
+    let written =
+        File::open(profile::path(WALLET_FILE), FileOptions::kFileWrite).and_then(|mut file| {
+            file.write_all(s.as_bytes())?;
+            file.flush()
+        });
+    if let Err(e) = written {
+        error!("cannot write the {} file: {}", WALLET_FILE, e);
+    }
+}
+
+/// The full-screen shop, opened with Ⓑ on the Ready screen.
+pub struct ShopScreen {
+    sprite: Sprite,
+    shop: Option<Shop>,
+}
+
+impl ShopScreen {
+    pub fn new() -> Self {
+        let bitmap = Bitmap::new(size!(DISPLAY_WIDTH as _, DISPLAY_HEIGHT as _), Color::White);
+        let sprite = Sprite::new();
+        sprite.set_image(bitmap, BitmapFlip::Unflipped);
+        Layer::Screen.apply(&sprite);
+        sprite.set_bounds(rect!(x: 0.0, y: 0.0, w: DISPLAY_WIDTH as _, h: DISPLAY_HEIGHT as _));
+        sprite.set_visible(false);
+        PLAYDATE.sprite.add_sprite(&sprite);
+        Self { sprite, shop: None }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.shop.is_some()
+    }
+
+    pub fn open(&mut self, wallet: &Wallet) {
+        self.shop = Some(Shop::new());
+        self.sprite.set_visible(true);
+        self.draw(wallet);
+    }
+
+    /// Lets the shop take the input, saving the wallet after a purchase.
+    pub fn update(&mut self, input: &Input, wallet: &mut Wallet) {
+        let Some(shop) = &mut self.shop else {
+            return;
+        };
+        match shop.update(input, wallet) {
+            ShopUpdate::Open => {}
+            ShopUpdate::Bought(_) => save_wallet(wallet),
+            ShopUpdate::Closed => {
+                self.shop = None;
+                self.sprite.set_visible(false);
+                return;
+            }
+        }
+        self.draw(wallet);
+    }
+
+    fn draw(&mut self, wallet: &Wallet) {
+        let Some(shop) = &self.shop else {
+            return;
+        };
+        let centered = |text: &str, y: i32| {
+            let x = layout::centered_x(text_width(text));
+            PLAYDATE.graphics.draw_text(text, vec2!(x, y));
+        };
+        let bitmap = self.sprite.get_image().unwrap();
+        PLAYDATE.graphics.push_context(bitmap);
+        PLAYDATE.graphics.clear(Color::White);
+        set_font();
+        centered("SHOP", layout::SHOP_TITLE_Y);
+        centered(&layout::shop_coins_text(wallet), layout::SHOP_COINS_Y);
+        for (i, item) in Item::ALL.iter().enumerate() {
+            let y = layout::shop_row_y(i);
+            let selected = *item == shop.get_selected();
+            let name = layout::shop_item_text(*item, wallet, selected);
+            PLAYDATE
+                .graphics
+                .draw_text(&name, vec2!(layout::SHOP_LEFT_X, y));
+            let price = layout::shop_price_text(*item);
+            let x = layout::SHOP_RIGHT_X - text_width(&price) as i32;
+            PLAYDATE.graphics.draw_text(&price, vec2!(x, y));
+        }
+        centered(shop.get_message(), layout::SHOP_MESSAGE_Y);
+        centered(layout::SHOP_HINT, layout::SHOP_HINT_Y);
+        PLAYDATE.graphics.pop_context();
+        self.sprite.mark_dirty();
+    }
+}
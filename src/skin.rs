@@ -0,0 +1,37 @@
+use alloc::format;
+use dino_core::skin::Wardrobe;
+use playdate_rs::{
+    fs::{File, Write},
+    sys::FileOptions,
+};
+
+use crate::profile;
+
+const SKINS_FILE: &str = "skins";
+
+/// The unlocked skins and the one in use, kept in the profile's `skins`
+/// file. An unreadable file counts as the classic skin only.
+pub fn load_wardrobe() -> Wardrobe {
+    let Ok(mut file) = File::open(profile::path(SKINS_FILE), FileOptions::kFileReadData) else {
+        return Wardrobe::default();
+    };
+    match file.read_to_string() {
+        Ok(text) => Wardrobe::parse(&text),
+        Err(e) => {
+            error!("unreadable {} file: {}", SKINS_FILE, e);
+            Wardrobe::default()
+        }
+    }
+}
+
+pub fn save_wardrobe(wardrobe: &Wardrobe) {
+    let s = format!("{}", wardrobe);
+    let written =
+        File::open(profile::path(SKINS_FILE), FileOptions::kFileWrite).and_then(|mut file| {
+            file.write_all(s.as_bytes())?;
+            file.flush()
+        });
+    if let Err(e) = written {
+        error!("cannot write the {} file: {}", SKINS_FILE, e);
+    }
+}
@@ -0,0 +1,97 @@
+use alloc::string::String;
+use dino_core::{
+    input::Input,
+    layout,
+    obstacle::ObstacleKind,
+    stats::{Stats, StatsBrowser, StatsPage},
+};
+use playdate_rs::{graphics::Color, sprite::Sprite, PLAYDATE};
+
+use crate::{high_scores, set_font, text_width};
+
+fn draw_right(text: &str, right_x: i32, y: i32) {
+    let x = right_x - text_width(text) as i32;
+    PLAYDATE.graphics.draw_text(text, vec2!(x, y));
+}
+
+/// The lifetime stats of the profile, opened with Down on the high score
+/// tables.
+pub struct StatsScreen {
+    sprite: Sprite,
+    browser: Option<StatsBrowser>,
+}
+
+impl StatsScreen {
+    pub fn new() -> Self {
+        Self {
+            sprite: high_scores::screen_sprite(),
+            browser: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.browser.is_some()
+    }
+
+    pub fn open(&mut self, stats: &Stats) {
+        self.browser = Some(StatsBrowser::new());
+        self.sprite.set_visible(true);
+        self.draw(stats);
+    }
+
+    pub fn update(&mut self, input: &Input, stats: &Stats) {
+        let Some(browser) = &mut self.browser else {
+            return;
+        };
+        if !browser.update(input) {
+            self.browser = None;
+            self.sprite.set_visible(false);
+            return;
+        }
+        self.draw(stats);
+    }
+
+    fn draw(&mut self, stats: &Stats) {
+        let Some(browser) = &self.browser else {
+            return;
+        };
+        let center_x = layout::CENTER_X as i32;
+        let bitmap = self.sprite.get_image().unwrap();
+        PLAYDATE.graphics.push_context(bitmap);
+        PLAYDATE.graphics.clear(Color::White);
+        set_font();
+        high_scores::draw_centered(layout::STATS_TITLE, center_x, layout::STATS_TITLE_Y);
+        let page = browser.get_page();
+        let page_text = layout::stats_page_text(page);
+        high_scores::draw_centered(&page_text, center_x, layout::STATS_PAGE_Y);
+        match page {
+            StatsPage::Totals => {
+                for (i, [label, value]) in layout::stats_total_rows(stats).iter().enumerate() {
+                    let y = layout::stats_row_y(i);
+                    PLAYDATE
+                        .graphics
+                        .draw_text(label, vec2!(layout::STATS_LEFT_X, y));
+                    draw_right(value, layout::STATS_RIGHT_X, y);
+                }
+            }
+            StatsPage::Obstacles => {
+                let header = layout::STATS_OBSTACLES_HEADER.map(String::from);
+                let rows = ObstacleKind::ALL
+                    .iter()
+                    .map(|kind| layout::stats_obstacle_row(*kind, stats));
+                for (i, [name, cleared, deaths]) in Some(header).into_iter().chain(rows).enumerate()
+                {
+                    let y = layout::stats_row_y(i);
+                    PLAYDATE
+                        .graphics
+                        .draw_text(&name, vec2!(layout::STATS_LEFT_X, y));
+                    draw_right(&cleared, layout::STATS_MIDDLE_RIGHT_X, y);
+                    draw_right(&deaths, layout::STATS_RIGHT_X, y);
+                }
+            }
+        }
+        high_scores::draw_centered(layout::STATS_HINT, center_x, layout::STATS_HINT_Y);
+        PLAYDATE.graphics.pop_context();
+        self.sprite.mark_dirty();
+    }
+}
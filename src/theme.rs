@@ -0,0 +1,46 @@
+use alloc::{format, string::String, vec, vec::Vec};
+use dino_core::theme::{Theme, MANIFEST, THEMES_DIR};
+use playdate_rs::{fs::File, sys::FileOptions, PLAYDATE};
+use spin::{Lazy, RwLock};
+
+/// The theme the views load their assets from.
+static THEME: Lazy<RwLock<Theme>> = Lazy::new(|| RwLock::new(Theme::default()));
+
+/// The bundled assets followed by the packs found in `themes/`. Packs with
+/// a broken manifest are skipped.
+pub fn discover() -> Vec<Theme> {
+    let mut dirs = vec![];
+    let _ = PLAYDATE.file.list_files(THEMES_DIR, false, |name| {
+        if let Some(dir) = name.strip_suffix('/') {
+            dirs.push(String::from(dir));
+        }
+    });
+    dirs.sort();
+    let mut themes = vec![Theme::default()];
+    for dir in dirs {
+        let dir = format!("{}/{}", THEMES_DIR, dir);
+        let Ok(mut file) = File::open(format!("{}/{}", dir, MANIFEST), FileOptions::kFileReadData)
+        else {
+            continue;
+        };
+        match Theme::parse(&dir, &file.read_to_string().unwrap()) {
+            Ok(theme) => themes.push(theme),
+            Err(e) => warn!("{}/{}: {}", dir, MANIFEST, e),
+        }
+    }
+    themes
+}
+
+pub fn set_current(theme: &Theme) {
+    *THEME.write() = theme.clone();
+}
+
+/// Where to load the bundled image `name` from in the current theme.
+pub fn image_path(name: &str) -> String {
+    THEME.read().image_path(name)
+}
+
+#[cfg(feature = "audio")]
+pub fn sound_path(name: &str) -> String {
+    THEME.read().sound_path(name)
+}
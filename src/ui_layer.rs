@@ -1,33 +1,97 @@
-use crate::FONT;
+use crate::{
+    assets::{ids, AssetError},
+    font_height, set_font, text_width,
+};
+use dino_core::{layout, save::Recovery, skin::Wardrobe};
 use playdate_rs::{
-    display::{DISPLAY_HEIGHT, DISPLAY_WIDTH},
     graphics::{Bitmap, BitmapFlip, Color},
-    math::Vec2,
     sprite::Sprite,
     PLAYDATE,
 };
 
-use crate::{DinoGame, GameState};
-
-const CENTER: Vec2<f32> = vec2!(x: DISPLAY_WIDTH as f32 / 2.0, y: DISPLAY_HEIGHT as f32 / 2.0);
+use crate::{layer::Layer, to_rect, DinoGame, GameState};
 
 pub struct UILayer {
     start_message: MessageBox,
+    shop_message: MessageBox,
+    high_scores_message: MessageBox,
+    profile_message: Option<MessageBox>,
+    skin_message: Option<MessageBox>,
+    error_message: Option<MessageBox>,
+    save_message: Option<MessageBox>,
     restart_panel: RestartPanel,
 }
 
 impl UILayer {
     pub fn new() -> Self {
         Self {
-            start_message: MessageBox::new("Press Ⓐ to start", CENTER),
+            start_message: MessageBox::new(layout::START_MESSAGE, layout::START_MESSAGE_CENTER),
+            shop_message: MessageBox::new(layout::SHOP_MESSAGE, layout::SHOP_MESSAGE_CENTER),
+            high_scores_message: MessageBox::new(
+                layout::HIGH_SCORES_MESSAGE,
+                layout::HIGH_SCORES_MESSAGE_CENTER,
+            ),
+            profile_message: None,
+            skin_message: None,
+            error_message: None,
+            save_message: None,
             restart_panel: RestartPanel::new(),
         }
     }
 
+    /// Shows the profile in use on the Ready screen.
+    pub fn set_profile(&mut self, name: &str) {
+        let text = layout::profile_text(name);
+        self.profile_message = Some(MessageBox::new(text, layout::PROFILE_MESSAGE_CENTER));
+    }
+
+    /// Shows the selected skin between runs, once there is more than one to
+    /// pick from.
+    pub fn set_wardrobe(&mut self, wardrobe: &Wardrobe) {
+        self.skin_message = None;
+        if wardrobe.unlocked().count() > 1 {
+            let text = layout::skin_text(wardrobe.get_selected());
+            self.skin_message = Some(MessageBox::new(text, layout::SKIN_MESSAGE_CENTER));
+        }
+    }
+
+    /// Names the first asset that failed to load, until the views are
+    /// rebuilt.
+    pub fn set_asset_errors(&mut self, errors: &[AssetError]) {
+        self.error_message = errors.first().map(|e| {
+            let text = layout::asset_error_text(e.get_name());
+            MessageBox::new(text, layout::ASSET_ERROR_CENTER)
+        });
+    }
+
+    /// Tells how a damaged save was recovered, on the Ready screen.
+    pub fn set_save_recovery(&mut self, recovery: Option<Recovery>) {
+        self.save_message = recovery.map(|r| {
+            let text = layout::save_error_text(r == Recovery::Backup);
+            MessageBox::new(text, layout::SAVE_ERROR_CENTER)
+        });
+    }
+
     pub fn update(&mut self, delta: f32) {
         let game_state = DinoGame::get_game_state();
         self.start_message
             .update(delta, game_state == GameState::Ready);
+        self.shop_message
+            .update(delta, game_state == GameState::Ready);
+        self.high_scores_message
+            .update(delta, game_state == GameState::Ready);
+        if let Some(message) = &mut self.profile_message {
+            message.update(delta, game_state == GameState::Ready);
+        }
+        if let Some(message) = &mut self.skin_message {
+            message.update(delta, game_state != GameState::Playing);
+        }
+        if let Some(message) = &mut self.error_message {
+            message.update(delta, true);
+        }
+        if let Some(message) = &mut self.save_message {
+            message.update(delta, game_state == GameState::Ready);
+        }
         self.restart_panel
             .update(delta, game_state == GameState::Dead);
     }
@@ -39,21 +103,22 @@ struct MessageBox {
 }
 
 impl MessageBox {
-    pub fn new(text: impl AsRef<str>, center: Vec2<f32>) -> Self {
+    pub fn new(text: impl AsRef<str>, center: (f32, f32)) -> Self {
         // Get text size
-        let text_width = FONT.get_text_width(&text, 0) as i32;
-        let text_height = FONT.get_height() as i32;
+        let text_width = text_width(&text) as i32;
+        let text_height = font_height() as i32;
         // Draw text to a bitmap
         let bitmap = Bitmap::new(size!(text_width as _, text_height as _), Color::White);
         PLAYDATE.graphics.push_context(&bitmap);
-        PLAYDATE.graphics.set_font(&FONT);
+        set_font();
         PLAYDATE.graphics.draw_text(text, vec2!(0, 0));
         PLAYDATE.graphics.pop_context();
         // Create sprite
         let sprite = Sprite::new();
         sprite.set_image(bitmap, BitmapFlip::Unflipped);
-        sprite.set_z_index(20000);
-        sprite.set_bounds(rect!(x: center.x - text_width as f32 / 2.0, y: center.y - text_height as f32 / 2.0, w: text_width as _, h: text_height as _));
+        Layer::Message.apply(&sprite);
+        let (x, y) = layout::centered(center, text_width as f32, text_height as f32);
+        sprite.set_bounds(rect!(x: x, y: y, w: text_width as _, h: text_height as _));
         PLAYDATE.sprite.add_sprite(&sprite);
         Self {
             sprite,
@@ -67,7 +132,7 @@ impl MessageBox {
             return;
         }
         self.elapsed += delta;
-        if (self.elapsed as i32) & 1 == 1 {
+        if (self.elapsed as i32) & 1 == 1 && !DinoGame::is_flashing_reduced() {
             self.sprite.set_visible(false);
         } else {
             self.sprite.set_visible(true);
@@ -75,6 +140,12 @@ impl MessageBox {
     }
 }
 
+impl Drop for MessageBox {
+    fn drop(&mut self) {
+        PLAYDATE.sprite.remove_sprite(&self.sprite);
+    }
+}
+
 struct RestartPanel {
     game_over_image: Sprite,
     restart_icon: Sprite,
@@ -85,37 +156,22 @@ impl RestartPanel {
     fn new() -> Self {
         // Create game over icon
         let game_over_image = Sprite::new();
-        let bitmap = Bitmap::open("game-over").unwrap();
-        let bitmap_scaled = Bitmap::new(size!(195, 15), Color::Clear);
-        PLAYDATE.graphics.push_context(&bitmap_scaled);
-        PLAYDATE
-            .graphics
-            .draw_scaled_bitmap(&bitmap, vec2!(0, 0), vec2!(0.5, 0.5));
-        PLAYDATE.graphics.pop_context();
-        game_over_image.set_image(bitmap_scaled, BitmapFlip::Unflipped);
-        game_over_image.set_z_index(10000);
-        game_over_image.set_bounds(
-            rect!(x: CENTER.x - 195.0 / 2.0, y:  CENTER.y - 16.0 - 20.0 - 30.0 , w: 390.0, h: 30.0),
-        );
+        let bitmap = crate::assets::image(ids::image::GAME_OVER);
+        game_over_image.set_image((*bitmap).clone(), BitmapFlip::Unflipped);
+        Layer::Hud.apply(&game_over_image);
+        game_over_image.set_bounds(to_rect(layout::GAME_OVER));
         PLAYDATE.sprite.add_sprite(&game_over_image);
         // Create restart icon
         let restart_icon = Sprite::new();
-        let bitmap = Bitmap::open("restart").unwrap();
-        let bitmap_scaled = Bitmap::new(size!(36, 32), Color::Clear);
-        PLAYDATE.graphics.push_context(&bitmap_scaled);
-        PLAYDATE
-            .graphics
-            .draw_scaled_bitmap(&bitmap, vec2!(0, 0), vec2!(0.5, 0.5));
-        PLAYDATE.graphics.pop_context();
-        restart_icon.set_image(bitmap_scaled, BitmapFlip::Unflipped);
-        restart_icon.set_z_index(10000);
-        restart_icon
-            .set_bounds(rect!(x: CENTER.x - 18.0, y:  CENTER.y - 16.0 - 20.0, w: 36.0, h: 32.0));
+        let bitmap = crate::assets::image(ids::image::RESTART);
+        restart_icon.set_image((*bitmap).clone(), BitmapFlip::Unflipped);
+        Layer::Hud.apply(&restart_icon);
+        restart_icon.set_bounds(to_rect(layout::RESTART_ICON));
         PLAYDATE.sprite.add_sprite(&restart_icon);
         Self {
             game_over_image,
             restart_icon,
-            message: MessageBox::new("Press Ⓐ to restart", CENTER + vec2!(0.0, 18.0)),
+            message: MessageBox::new(layout::RESTART_MESSAGE, layout::RESTART_MESSAGE_CENTER),
         }
     }
 
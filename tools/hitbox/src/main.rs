@@ -0,0 +1,175 @@
+//! Derives collision boxes from the sprite images, see `dino_host::hitbox`.
+//!
+//! ```text
+//! hitbox-tool [--assets <dir>] [--tolerance <px>] [--rust] [--preview <png>]
+//! ```
+//!
+//! The boxes are printed as theme manifest entries, see `dino_core::theme`,
+//! so they can go straight into a pack. `--rust` prints the bundled boxes of
+//! `dino-core/src/hitbox.rs` instead. `--preview` draws every sprite
+//! enlarged with its boxes on top.
+
+use std::{path::PathBuf, process::ExitCode};
+
+use dino_core::math::Rect;
+use dino_host::{
+    hitbox::{self, Sprite, DEFAULT_TOLERANCE},
+    text, Assets, Bitmap, Color,
+};
+
+const USAGE: &str =
+    "usage: hitbox-tool [--assets <dir>] [--tolerance <px>] [--rust] [--preview <png>]";
+/// How much the preview enlarges the sprites.
+const ZOOM: u32 = 4;
+const PREVIEW_MARGIN: u32 = 8;
+
+struct Args {
+    assets: PathBuf,
+    tolerance: u32,
+    rust: bool,
+    preview: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        assets: Assets::default_dir(),
+        tolerance: DEFAULT_TOLERANCE,
+        rust: false,
+        preview: None,
+    };
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--assets" => args.assets = value()?.into(),
+            "--tolerance" => {
+                let value = value()?;
+                args.tolerance = value
+                    .parse()
+                    .map_err(|_| format!("invalid tolerance: {value}"))?;
+            }
+            "--rust" => args.rust = true,
+            "--preview" => args.preview = Some(value()?.into()),
+            "-h" | "--help" => return Err(USAGE.to_owned()),
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+    Ok(args)
+}
+
+/// Draws the sprites side by side, enlarged, with opaque pixels dithered
+/// and the boxes outlined.
+fn preview(sprites: &[(Sprite, Vec<Rect>)]) -> Bitmap {
+    let label_height = text::get_height() + PREVIEW_MARGIN;
+    let width = sprites
+        .iter()
+        .map(|(s, _)| s.width() * ZOOM + PREVIEW_MARGIN)
+        .sum::<u32>()
+        + PREVIEW_MARGIN;
+    let height = sprites
+        .iter()
+        .map(|(s, _)| s.height() * ZOOM)
+        .max()
+        .unwrap_or(0)
+        + label_height
+        + PREVIEW_MARGIN * 2;
+    let mut out = Bitmap::new(width, height, Color::White);
+    let mut left = PREVIEW_MARGIN as i32;
+    let top = PREVIEW_MARGIN as i32;
+    for (sprite, boxes) in sprites {
+        for y in 0..sprite.height() * ZOOM {
+            for x in 0..sprite.width() * ZOOM {
+                if sprite.is_opaque(x / ZOOM, y / ZOOM) && (x + y) % 2 == 0 {
+                    out.set(left + x as i32, top + y as i32, Color::Black);
+                }
+            }
+        }
+        for b in boxes {
+            let (x, y) = (
+                left + (b.x * ZOOM as f32) as i32,
+                top + (b.y * ZOOM as f32) as i32,
+            );
+            let (w, h) = (
+                (b.width * ZOOM as f32) as i32,
+                (b.height * ZOOM as f32) as i32,
+            );
+            for i in 0..w {
+                out.set(x + i, y, Color::Black);
+                out.set(x + i, y + h - 1, Color::Black);
+            }
+            for i in 0..h {
+                out.set(x, y + i, Color::Black);
+                out.set(x + w - 1, y + i, Color::Black);
+            }
+        }
+        let label = format!("{}", boxes.len());
+        let label_y = top + (sprite.height() * ZOOM + PREVIEW_MARGIN) as i32;
+        text::draw_text(&mut out, &label, left, label_y);
+        left += (sprite.width() * ZOOM + PREVIEW_MARGIN) as i32;
+    }
+    out
+}
+
+fn print_manifest(sprites: &[(Sprite, Vec<Rect>)], tolerance: u32) {
+    println!("# hitbox-tool --tolerance {}", tolerance);
+    for (sprite, boxes) in sprites {
+        let boxes: Vec<String> = boxes
+            .iter()
+            .map(|b| format!("{},{},{},{}", b.x, b.y, b.width, b.height))
+            .collect();
+        println!("{} {}", sprite.entry, boxes.join(" "));
+    }
+}
+
+/// Prints the boxes as the constants of `dino_core::hitbox`.
+fn print_rust(sprites: &[(Sprite, Vec<Rect>)]) {
+    for (i, (sprite, boxes)) in sprites.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("pub const {}: &[Rect] = &[", sprite.constant);
+        for b in boxes {
+            println!(
+                "    Rect::new({:?}, {:?}, {:?}, {:?}),",
+                b.x, b.y, b.width, b.height
+            );
+        }
+        println!("];");
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+    let sprites = match hitbox::load_sprites(&args.assets) {
+        Ok(sprites) => sprites,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(1);
+        }
+    };
+    let sprites: Vec<(Sprite, Vec<Rect>)> = sprites
+        .into_iter()
+        .map(|s| {
+            let boxes = hitbox::cover(&s, args.tolerance);
+            (s, boxes)
+        })
+        .collect();
+    if args.rust {
+        print_rust(&sprites);
+    } else {
+        print_manifest(&sprites, args.tolerance);
+    }
+    if let Some(path) = &args.preview {
+        if let Err(e) = preview(&sprites).save(path) {
+            eprintln!("{}: {e}", path.display());
+            return ExitCode::from(1);
+        }
+    }
+    ExitCode::SUCCESS
+}
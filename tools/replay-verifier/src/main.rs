@@ -0,0 +1,118 @@
+//! Re-simulates a replay recorded on the device and checks its score.
+//!
+//! ```text
+//! replay-verifier <replay> [--claimed <score>] [--unfinished]
+//! ```
+//!
+//! The claimed score defaults to the one stored in the replay. Exits with 1 if
+//! it doesn't match the verified score, the re-simulation diverges from the
+//! recording, the replay has settings the game can't have produced, or the
+//! run didn't end and `--unfinished` isn't given. Exits with 2 if the replay
+//! can't be read.
+//!
+//! Shapes changed by a theme pack are taken on trust, since the pack isn't
+//! part of the replay.
+
+use std::process::ExitCode;
+
+use dino_core::replay::Replay;
+
+const USAGE: &str = "usage: replay-verifier <replay> [--claimed <score>] [--unfinished]";
+
+struct Args {
+    path: String,
+    claimed: Option<i32>,
+    /// Accepts a run the dino survived, e.g. one recorded until quitting.
+    unfinished: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut path = None;
+    let mut claimed = None;
+    let mut unfinished = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--claimed" => {
+                let score = args.next().ok_or("--claimed needs a score")?;
+                claimed = Some(
+                    score
+                        .parse()
+                        .map_err(|_| format!("invalid score: {score}"))?,
+                );
+            }
+            "--unfinished" => unfinished = true,
+            "-h" | "--help" => return Err(USAGE.to_owned()),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+    Ok(Args {
+        path: path.ok_or(USAGE)?,
+        claimed,
+        unfinished,
+    })
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+    let text = match std::fs::read_to_string(&args.path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("{}: {e}", args.path);
+            return ExitCode::from(2);
+        }
+    };
+    let replay = match Replay::parse(&text) {
+        Ok(replay) => replay,
+        Err(e) => {
+            eprintln!("{}: {e}", args.path);
+            return ExitCode::from(2);
+        }
+    };
+    let claimed = args.claimed.unwrap_or(replay.score);
+    let result = replay.simulate();
+    println!("seed:           {:016x}", replay.seed);
+    println!("frames:         {}", replay.frames.len());
+    if let Some(theme) = &replay.theme {
+        println!("theme:          {theme} (shapes not checked)");
+    }
+    match result.death_tick {
+        Some(tick) => println!("death tick:     {tick}"),
+        None => println!("death tick:     - (run did not end)"),
+    }
+    println!("verified score: {}", result.score);
+    println!("claimed score:  {claimed}");
+    match result.divergence {
+        Some(divergence) => println!("divergence:     {divergence}"),
+        None => println!("divergence:     none"),
+    }
+    let forgery = replay.find_forgery();
+    if let Some(forgery) = forgery {
+        println!("forgery:        {forgery}");
+    }
+    if claimed != result.score {
+        println!("FAILED: claimed score does not match");
+        return ExitCode::FAILURE;
+    }
+    if result.divergence.is_some() {
+        println!("FAILED: replay does not match its re-simulation");
+        return ExitCode::FAILURE;
+    }
+    if forgery.is_some() {
+        println!("FAILED: replay has settings the game can't produce");
+        return ExitCode::FAILURE;
+    }
+    if replay.death_tick.is_none() && !args.unfinished {
+        println!("FAILED: run did not end, pass --unfinished to accept it");
+        return ExitCode::FAILURE;
+    }
+    println!("OK");
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,176 @@
+//! Runs the verifier on a freshly recorded replay, and on tampered copies of
+//! it.
+
+use std::{path::PathBuf, process::Command};
+
+use dino_core::{
+    dino::DinoShape,
+    input::{Buttons, Input},
+    math::Rect,
+    obstacle::{ObstacleKind, Shape},
+    replay::Replay,
+    GameState, World,
+};
+
+const SEED: u64 = 7;
+const FRAME_MS: u32 = 33;
+
+/// Records a run that jumps every second until the dino dies, or until
+/// `max_ticks`.
+fn record(world: &mut World, frame_ms: u32, max_ticks: u32) -> Replay {
+    world.start(SEED);
+    let mut replay = Replay::new(
+        SEED,
+        world.lives.get_max(),
+        world.get_difficulty(),
+        world.get_perks(),
+    );
+    replay.shapes = world.obstacles.custom_shapes();
+    replay.dino_shape = world.dino.custom_shape();
+    while world.get_state() == GameState::Playing && world.get_tick() < max_ticks {
+        let input = if world.get_tick().is_multiple_of(30) {
+            Input::push(Buttons::A)
+        } else {
+            Input::default()
+        };
+        world.update(input, frame_ms);
+        replay.record(input, frame_ms, world);
+    }
+    replay
+}
+
+fn record_run() -> Replay {
+    record(&mut World::new(), FRAME_MS, u32::MAX)
+}
+
+/// Writes `text` as replay `name` and returns the verifier's exit code.
+fn verify(name: &str, text: &str, args: &[&str]) -> i32 {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{name}.txt"));
+    std::fs::write(&path, text).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_replay-verifier"))
+        .arg(&path)
+        .args(args)
+        .output()
+        .unwrap();
+    output.status.code().unwrap()
+}
+
+#[test]
+fn recorded_replay_verifies() {
+    let replay = record_run();
+    assert_eq!(verify("recorded", &replay.to_string(), &[]), 0);
+    let score = replay.score.to_string();
+    assert_eq!(
+        verify("recorded", &replay.to_string(), &["--claimed", &score]),
+        0
+    );
+}
+
+#[test]
+fn tampered_score_is_rejected() {
+    let mut replay = record_run();
+    assert_eq!(
+        verify("claimed", &replay.to_string(), &["--claimed", "99999"]),
+        1
+    );
+    replay.score += 100;
+    assert_eq!(verify("score", &replay.to_string(), &[]), 1);
+}
+
+#[test]
+fn tampered_seed_is_rejected() {
+    let mut replay = record_run();
+    replay.seed = SEED + 1;
+    assert_eq!(verify("seed", &replay.to_string(), &[]), 1);
+}
+
+#[test]
+fn unreadable_replay_is_rejected() {
+    assert_eq!(verify("unreadable", "not a replay\n", &[]), 2);
+}
+
+#[test]
+fn unknown_perks_are_rejected() {
+    let text = record_run().to_string().replace("perks 00", "perks 80");
+    assert_eq!(verify("perks", &text, &[]), 2);
+}
+
+#[test]
+fn forged_lives_are_rejected() {
+    let mut world = World::new();
+    world.lives.set_max(2);
+    let replay = record(&mut world, FRAME_MS, u32::MAX);
+    assert!(replay.simulate().divergence.is_none());
+    assert_eq!(verify("lives", &replay.to_string(), &[]), 1);
+}
+
+#[test]
+fn shapes_need_a_theme() {
+    let mut world = World::new();
+    for kind in ObstacleKind::ALL {
+        let size = kind.default_shape().size;
+        world.obstacles.set_shape(
+            kind,
+            Shape {
+                size,
+                hitboxes: Vec::new(),
+            },
+        );
+    }
+    let mut replay = record(&mut world, FRAME_MS, 3000);
+    assert_eq!(verify("shapes", &replay.to_string(), &["--unfinished"]), 1);
+    replay.theme = Some("themes/ghost cacti".to_owned());
+    assert_eq!(verify("themed", &replay.to_string(), &["--unfinished"]), 0);
+}
+
+#[test]
+fn long_frames_are_rejected() {
+    let replay = record(&mut World::new(), 500, u32::MAX);
+    assert!(replay.simulate().divergence.is_none());
+    assert_eq!(verify("frames", &replay.to_string(), &[]), 1);
+}
+
+#[test]
+fn missing_checkpoints_are_rejected() {
+    let mut replay = record_run();
+    replay.checkpoints.remove(1);
+    assert_eq!(verify("checkpoints", &replay.to_string(), &[]), 1);
+}
+
+#[test]
+fn unfinished_runs_need_a_flag() {
+    let replay = record(&mut World::new(), FRAME_MS, 60);
+    assert_eq!(replay.death_tick, None);
+    assert_eq!(verify("unfinished", &replay.to_string(), &[]), 1);
+    assert_eq!(
+        verify("unfinished", &replay.to_string(), &["--unfinished"]),
+        0
+    );
+}
+
+#[test]
+fn dino_shape_needs_a_theme() {
+    let mut world = World::new();
+    let head = vec![Rect::new(38.0, 0.0, 24.0, 15.0)];
+    world.dino.set_shape(DinoShape {
+        standing: head.clone(),
+        ducking: head,
+    });
+    let mut replay = record(&mut world, FRAME_MS, 3000);
+    assert_eq!(verify("dino", &replay.to_string(), &["--unfinished"]), 1);
+    replay.theme = Some("themes/small dino".to_owned());
+    let text = replay.to_string();
+    assert_eq!(verify("themed-dino", &text, &["--unfinished"]), 0);
+    let text: String = text
+        .lines()
+        .map(|l| {
+            if l.starts_with("dino ducking") {
+                "dino ducking"
+            } else {
+                l
+            }
+        })
+        .map(|l| format!("{l}\n"))
+        .collect();
+    assert_eq!(verify("empty-dino", &text, &["--unfinished"]), 2);
+}
@@ -0,0 +1,94 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use dino_core::input::{Buttons, Input};
+
+/// Without key release events a key counts as held for this long after its
+/// last press or auto-repeat.
+const HOLD_TIMEOUT: Duration = Duration::from_millis(500);
+
+const BUTTONS: [Buttons; 6] = [
+    Buttons::LEFT,
+    Buttons::RIGHT,
+    Buttons::UP,
+    Buttons::DOWN,
+    Buttons::B,
+    Buttons::A,
+];
+
+fn map_key(code: KeyCode) -> Option<Buttons> {
+    match code {
+        KeyCode::Char('z') | KeyCode::Char(' ') => Some(Buttons::A),
+        KeyCode::Char('x') => Some(Buttons::B),
+        KeyCode::Left => Some(Buttons::LEFT),
+        KeyCode::Right => Some(Buttons::RIGHT),
+        KeyCode::Up => Some(Buttons::UP),
+        KeyCode::Down => Some(Buttons::DOWN),
+        _ => None,
+    }
+}
+
+/// Turns terminal key events into Playdate-style button state.
+pub struct Keyboard {
+    held: [Option<Instant>; 6],
+    pushed: Buttons,
+    release_events: bool,
+    quit: bool,
+}
+
+impl Keyboard {
+    pub fn new(release_events: bool) -> Self {
+        Self {
+            held: [None; 6],
+            pushed: Buttons::NONE,
+            release_events,
+            quit: false,
+        }
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    pub fn handle(&mut self, event: KeyEvent, now: Instant) {
+        let ctrl_c =
+            event.code == KeyCode::Char('c') && event.modifiers.contains(KeyModifiers::CONTROL);
+        if ctrl_c || event.code == KeyCode::Esc || event.code == KeyCode::Char('q') {
+            self.quit = true;
+            return;
+        }
+        let Some(button) = map_key(event.code) else {
+            return;
+        };
+        let i = BUTTONS.iter().position(|b| *b == button).unwrap();
+        match event.kind {
+            KeyEventKind::Press | KeyEventKind::Repeat => {
+                if !self.is_held(i, now) {
+                    self.pushed = self.pushed | button;
+                }
+                self.held[i] = Some(now);
+            }
+            KeyEventKind::Release => self.held[i] = None,
+        }
+    }
+
+    fn is_held(&self, i: usize, now: Instant) -> bool {
+        match self.held[i] {
+            Some(_) if self.release_events => true,
+            Some(t) => now - t < HOLD_TIMEOUT,
+            None => false,
+        }
+    }
+
+    /// The input for the next frame. Pushes are reported once.
+    pub fn sample(&mut self, now: Instant) -> Input {
+        let mut current = Buttons::NONE;
+        for (i, button) in BUTTONS.iter().enumerate() {
+            if self.is_held(i, now) {
+                current = current | *button;
+            }
+        }
+        let pushed = std::mem::take(&mut self.pushed);
+        Input { current, pushed }
+    }
+}
@@ -0,0 +1,300 @@
+//! Plays Dino in a terminal using the same simulation as the Playdate build.
+//!
+//! Keys: Z or Space is Ⓐ, X is Ⓑ, the arrow keys are the D-pad and Esc or Q
+//! quits. The terminal must be at least 200×60 characters.
+
+mod keyboard;
+mod screen;
+
+use std::{
+    fs, io,
+    path::PathBuf,
+    process::ExitCode,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::{
+    cursor,
+    event::{
+        self, Event as TermEvent, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
+    execute,
+    style::{Color as TermColor, Colors, ResetColor, SetColors},
+    terminal,
+};
+use dino_core::{
+    input::{Buttons, Input},
+    replay::Replay,
+    shop::{Item, Shop, ShopUpdate, Wallet},
+    skin::Wardrobe,
+    theme::Theme,
+    Event, GameState, World,
+};
+use dino_host::{Assets, Bitmap, Color, Scene};
+use keyboard::Keyboard;
+use screen::Mode;
+
+const FRAME_TIME: Duration = Duration::from_millis(1000 / 30);
+
+struct Options {
+    mode: Mode,
+    assets: PathBuf,
+    theme: Option<PathBuf>,
+    record: Option<PathBuf>,
+    lives: u32,
+}
+
+fn usage() -> ExitCode {
+    eprintln!(
+        "usage: dino-terminal [--half-blocks] [--lives <n>] [--assets <dir>] [--theme <dir>] [--record <replay>]"
+    );
+    ExitCode::from(2)
+}
+
+fn parse_args() -> Option<Options> {
+    let mut options = Options {
+        mode: Mode::Braille,
+        assets: Assets::default_dir(),
+        theme: None,
+        record: None,
+        lives: 1,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--half-blocks" => options.mode = Mode::HalfBlocks,
+            "--assets" => options.assets = args.next()?.into(),
+            "--theme" => options.theme = Some(args.next()?.into()),
+            "--record" => options.record = Some(args.next()?.into()),
+            "--lives" => options.lives = args.next()?.parse().ok()?,
+            _ => return None,
+        }
+    }
+    Some(options)
+}
+
+fn time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+struct Game {
+    world: World,
+    scene: Scene,
+    replay: Option<Replay>,
+    /// The `--theme` folder, recorded in replays.
+    theme_dir: Option<String>,
+    record: i32,
+    wallet: Wallet,
+    wardrobe: Wardrobe,
+    shop: Option<Shop>,
+    frame: Bitmap,
+}
+
+impl Game {
+    fn start(&mut self) {
+        let seed = time_seed();
+        let perks = self.wallet.take_perks();
+        self.world.set_perks(perks);
+        self.world.start(seed);
+        self.scene.reset();
+        if self.wallet.is_unlocked(Item::NightStart) {
+            self.scene.start_at_night();
+        }
+        let mut replay = Replay::new(
+            seed,
+            self.world.lives.get_max(),
+            self.world.get_difficulty(),
+            perks,
+        );
+        replay.theme = self.theme_dir.clone();
+        replay.shapes = self.world.obstacles.custom_shapes();
+        replay.dino_shape = self.world.dino.custom_shape();
+        self.replay = Some(replay);
+    }
+
+    /// Lets the shop take the input while it's open. Ⓑ opens it from the
+    /// Ready screen.
+    fn update_shop(&mut self, input: Input) -> Input {
+        if let Some(shop) = &mut self.shop {
+            if shop.update(&input, &mut self.wallet) == ShopUpdate::Closed {
+                self.shop = None;
+            }
+            return Input::default();
+        }
+        if self.world.get_state() == GameState::Ready && input.pushed.contains(Buttons::B) {
+            self.shop = Some(Shop::new());
+            return Input::default();
+        }
+        input
+    }
+
+    /// Left and Right pick a skin between runs.
+    fn update_wardrobe(&mut self, input: Input) {
+        if self.world.get_state() == GameState::Playing {
+            return;
+        }
+        let step = if input.pushed.contains(Buttons::LEFT) {
+            -1
+        } else if input.pushed.contains(Buttons::RIGHT) {
+            1
+        } else {
+            return;
+        };
+        self.wardrobe.cycle(step);
+        self.scene.set_wardrobe(&self.wardrobe);
+    }
+}
+
+fn run(options: &Options, out: &mut impl io::Write) -> io::Result<()> {
+    let theme = match &options.theme {
+        Some(dir) => Assets::load_theme(dir)?,
+        None => Theme::default(),
+    };
+    let assets = Assets::load_with_theme(&options.assets, &theme)?;
+    let release_events = terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if release_events {
+        execute!(
+            out,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+        )?;
+    }
+    let mut keyboard = Keyboard::new(release_events);
+    let mut world = World::new();
+    world.lives.set_max(options.lives);
+    theme.apply(&mut world);
+    let mut game = Game {
+        world,
+        scene: Scene::new(assets, time_seed()),
+        replay: None,
+        theme_dir: options.theme.as_ref().map(|d| d.display().to_string()),
+        record: 0,
+        wallet: Wallet::default(),
+        wardrobe: Wardrobe::default(),
+        shop: None,
+        frame: Bitmap::new(
+            dino_core::display::DISPLAY_WIDTH,
+            dino_core::display::DISPLAY_HEIGHT,
+            Color::White,
+        ),
+    };
+    let mut last = Instant::now();
+    let result = loop {
+        let frame_start = Instant::now();
+        while event::poll(Duration::ZERO)? {
+            if let TermEvent::Key(key) = event::read()? {
+                keyboard.handle(key, Instant::now());
+            }
+        }
+        if keyboard.should_quit() {
+            break Ok(());
+        }
+        let now = Instant::now();
+        let delta_ms = (now - last).as_millis() as u32;
+        last = now;
+
+        let input = game.update_shop(keyboard.sample(now));
+        game.update_wardrobe(input);
+        if game.world.get_state() != GameState::Playing && input.pushed.contains(Buttons::A) {
+            game.start();
+        }
+        let replay = &mut game.replay;
+        game.world
+            .advance(input, delta_ms, |input, step_ms, world| {
+                if let Some(replay) = replay {
+                    replay.record(input, step_ms, world);
+                }
+            });
+        for event in game.world.events() {
+            if let Event::Achievement(score) = event {
+                if !game.wardrobe.unlock_for(*score).is_empty() {
+                    game.scene.set_wardrobe(&game.wardrobe);
+                }
+            }
+        }
+        if game
+            .world
+            .events()
+            .iter()
+            .any(|e| matches!(e, Event::Died(_)))
+        {
+            let score = game.world.scoreboard.get_score();
+            game.record = game.record.max(score);
+            game.wallet.add_coins(game.world.coins.get_collected());
+            if let (Some(path), Some(replay)) = (&options.record, game.replay.take()) {
+                fs::write(path, replay.to_string())?;
+            }
+        }
+        game.scene.update(&game.world, delta_ms as f32 / 1000.0);
+        if let Some(shop) = &game.shop {
+            game.scene.draw_shop(shop, &game.wallet, &mut game.frame);
+        } else {
+            game.scene.draw(&game.world, game.record, &mut game.frame);
+            if game.scene.is_inverted() {
+                game.frame.invert();
+            }
+        }
+        screen::draw(out, &game.frame, options.mode)?;
+
+        if let Some(rest) = FRAME_TIME.checked_sub(frame_start.elapsed()) {
+            thread::sleep(rest);
+        }
+    };
+    if release_events {
+        execute!(out, PopKeyboardEnhancementFlags)?;
+    }
+    result
+}
+
+fn main() -> ExitCode {
+    let Some(options) = parse_args() else {
+        return usage();
+    };
+    let (columns, rows) = match terminal::size() {
+        Ok(size) => size,
+        Err(e) => {
+            eprintln!("error: cannot query terminal size: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    if columns < screen::COLUMNS || rows < screen::ROWS {
+        eprintln!(
+            "error: terminal is {}x{}, at least {}x{} is needed",
+            columns,
+            rows,
+            screen::COLUMNS,
+            screen::ROWS
+        );
+        return ExitCode::from(2);
+    }
+
+    let mut out = io::stdout();
+    let setup = terminal::enable_raw_mode().and_then(|_| {
+        execute!(
+            out,
+            terminal::EnterAlternateScreen,
+            cursor::Hide,
+            SetColors(Colors::new(TermColor::Black, TermColor::White)),
+            terminal::Clear(terminal::ClearType::All)
+        )
+    });
+    let result = setup.and_then(|_| run(&options, &mut out));
+    let _ = execute!(
+        out,
+        ResetColor,
+        cursor::Show,
+        terminal::LeaveAlternateScreen
+    );
+    let _ = terminal::disable_raw_mode();
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::io::{self, Write};
+
+use crossterm::{cursor, queue, style::Print};
+use dino_core::display::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
+use dino_host::Bitmap;
+
+/// How the 400×240 frame is packed into character cells. Both modes need a
+/// 200×60 terminal.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+    /// One braille character per 2×4 pixels, full resolution.
+    Braille,
+    /// One half-block character per 2×4 pixels, at half resolution.
+    HalfBlocks,
+}
+
+pub const COLUMNS: u16 = (DISPLAY_WIDTH / 2) as u16;
+pub const ROWS: u16 = (DISPLAY_HEIGHT / 4) as u16;
+
+// Braille dot bits for the pixels of a 2×4 cell, row by row.
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+fn cell(frame: &Bitmap, mode: Mode, col: u32, row: u32) -> char {
+    let (x0, y0) = (col * 2, row * 4);
+    match mode {
+        Mode::Braille => {
+            let mut bits = 0;
+            for (dy, dots) in BRAILLE_DOTS.iter().enumerate() {
+                for (dx, dot) in dots.iter().enumerate() {
+                    if frame.is_black(x0 + dx as u32, y0 + dy as u32) {
+                        bits |= dot;
+                    }
+                }
+            }
+            char::from_u32(0x2800 + bits).unwrap()
+        }
+        Mode::HalfBlocks => {
+            let any_black =
+                |y: u32| (0..2).any(|dy| (0..2).any(|dx| frame.is_black(x0 + dx, y + dy)));
+            match (any_black(y0), any_black(y0 + 2)) {
+                (false, false) => ' ',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (true, true) => '█',
+            }
+        }
+    }
+}
+
+pub fn draw(out: &mut impl Write, frame: &Bitmap, mode: Mode) -> io::Result<()> {
+    let mut line = String::with_capacity(COLUMNS as usize * 3);
+    for row in 0..ROWS {
+        line.clear();
+        for col in 0..COLUMNS {
+            line.push(cell(frame, mode, col as u32, row as u32));
+        }
+        queue!(out, cursor::MoveTo(0, row), Print(&line))?;
+    }
+    out.flush()
+}